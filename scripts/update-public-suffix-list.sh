@@ -0,0 +1,9 @@
+#!/bin/sh
+# Updates the snapshot of the Public Suffix List that is embedded by the `psl` feature.
+set -eu
+
+cd "$(dirname "$0")/.."
+curl --fail --silent --show-error --location \
+    --output uriparse-core/src/authority/public_suffix_list.dat.tmp \
+    https://publicsuffix.org/list/public_suffix_list.dat
+mv uriparse-core/src/authority/public_suffix_list.dat.tmp uriparse-core/src/authority/public_suffix_list.dat
@@ -0,0 +1,42 @@
+//! Constructors used by the expansion of the `uri!` macro in the `uriparse-macros` crate.
+//!
+//! These are not part of the public API and may change at any time.
+
+use crate::authority::{Authority, Host, IPvFuture, RegisteredName};
+use crate::path::{Path, Segment};
+use crate::uri::URI;
+
+pub const fn authority(
+    username: Option<&'static str>,
+    password: Option<&'static str>,
+    host: Host<'static>,
+    port: Option<u16>,
+) -> Authority<'static> {
+    Authority::from_static_parts(username, password, host, port)
+}
+
+pub const fn ipvfuture(value: &'static str) -> IPvFuture<'static> {
+    IPvFuture::from_static(value)
+}
+
+pub const fn path(absolute: bool, segments: &'static [Segment<'static>]) -> Path<'static> {
+    Path::from_static_segments(absolute, segments)
+}
+
+pub const fn registered_name(value: &'static str) -> RegisteredName<'static> {
+    RegisteredName::from_static(value)
+}
+
+pub const fn segment(value: &'static str) -> Segment<'static> {
+    Segment::from_static(value)
+}
+
+pub const fn uri(
+    scheme: &'static str,
+    authority: Option<Authority<'static>>,
+    path: Path<'static>,
+    query: Option<&'static str>,
+    fragment: Option<&'static str>,
+) -> URI<'static> {
+    URI::from_static_parts(scheme, authority, path, query, fragment)
+}
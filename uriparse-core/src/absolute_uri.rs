@@ -0,0 +1,547 @@
+//! Absolute URIs
+//!
+//! See [[RFC3986, Section 4.3](https://tools.ietf.org/html/rfc3986#section-4.3)].
+//!
+//! An absolute URI is a [`URI`] without a fragment component. Some protocols only accept this form,
+//! for example the `absolute-form` of an HTTP request target or the redirect URI of an OAuth
+//! client, so the [`AbsoluteURI`] type rejects fragments when it is constructed.
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::str::FromStr;
+
+use crate::authority::{Authority, Host, Password, Username};
+use crate::path::Path;
+use crate::query::Query;
+use crate::scheme::Scheme;
+use crate::uri::{URIError, URI};
+use crate::uri_reference::{Component, ParseError, URIReference};
+
+/// An absolute URI as defined in
+/// [[RFC3986, Section 4.3](https://tools.ietf.org/html/rfc3986#section-4.3)].
+///
+/// An absolute URI is a URI without a fragment.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbsoluteURI<'uri> {
+    /// All absolute URIs are also URIs, so we just maintain a [`URI`] underneath.
+    uri: URI<'uri>,
+}
+
+impl<'uri> AbsoluteURI<'uri> {
+    /// Returns the underlying [`URI`] of the absolute URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://example.com/").unwrap();
+    /// assert!(!uri.as_uri().has_fragment());
+    /// ```
+    pub fn as_uri(&self) -> &URI<'uri> {
+        &self.uri
+    }
+
+    /// Returns the authority, if present, of the absolute URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://example.com:80/my/path").unwrap();
+    /// assert_eq!(uri.authority().unwrap().to_string(), "example.com:80");
+    /// ```
+    pub fn authority(&self) -> Option<&Authority<'uri>> {
+        self.uri.authority()
+    }
+
+    /// Writes the absolute URI to the given [`fmt::Write`] sink without allocating an intermediate
+    /// string, see [`URIReference::fmt_to`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://example.com/path").unwrap();
+    /// let mut output = String::new();
+    /// uri.fmt_to(&mut output).unwrap();
+    /// assert_eq!(output, "http://example.com/path");
+    /// ```
+    pub fn fmt_to<TWriter>(&self, writer: TWriter) -> fmt::Result
+    where
+        TWriter: fmt::Write,
+    {
+        self.uri.fmt_to(writer)
+    }
+
+    /// Returns whether the absolute URI has an authority component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://example.com").unwrap();
+    /// assert!(uri.has_authority());
+    ///
+    /// let uri = AbsoluteURI::try_from("urn:test").unwrap();
+    /// assert!(!uri.has_authority());
+    /// ```
+    pub fn has_authority(&self) -> bool {
+        self.uri.has_authority()
+    }
+
+    /// Returns whether the absolute URI has a query component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://example.com/?query").unwrap();
+    /// assert!(uri.has_query());
+    ///
+    /// let uri = AbsoluteURI::try_from("http://example.com").unwrap();
+    /// assert!(!uri.has_query());
+    /// ```
+    pub fn has_query(&self) -> bool {
+        self.uri.has_query()
+    }
+
+    /// Returns the host, if present, of the absolute URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://username@example.com").unwrap();
+    /// assert_eq!(uri.host().unwrap().to_string(), "example.com");
+    /// ```
+    pub fn host(&self) -> Option<&Host<'uri>> {
+        self.uri.host()
+    }
+
+    /// Converts the [`AbsoluteURI`] into an owned copy.
+    ///
+    /// If you construct the absolute URI from a source with a non-static lifetime, you may run
+    /// into lifetime problems due to the way the struct is designed. Calling this function will
+    /// ensure that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the absolute URI will just copy the
+    /// references, and thus the lifetime will remain the same.
+    pub fn into_owned(self) -> AbsoluteURI<'static> {
+        AbsoluteURI {
+            uri: self.uri.into_owned(),
+        }
+    }
+
+    /// Consumes the [`AbsoluteURI`] and returns its parts: scheme, authority, path, and query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://example.com:80/my/path?my=query").unwrap();
+    /// let (scheme, authority, path, query) = uri.into_parts();
+    ///
+    /// assert_eq!(scheme, "http");
+    /// assert_eq!(authority.unwrap().to_string(), "example.com:80");
+    /// assert_eq!(path, "/my/path");
+    /// assert_eq!(query.unwrap(), "my=query");
+    /// ```
+    pub fn into_parts(
+        self,
+    ) -> (
+        Scheme<'uri>,
+        Option<Authority<'uri>>,
+        Path<'uri>,
+        Option<Query<'uri>>,
+    ) {
+        let (scheme, authority, path, query, _) = self.uri.into_parts();
+        (scheme, authority, path, query)
+    }
+
+    /// Returns whether the absolute URI is normalized.
+    ///
+    /// See [`URI::is_normalized`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let mut uri = AbsoluteURI::try_from("http://EXAMPLE.com/?a=b").unwrap();
+    /// assert!(!uri.is_normalized());
+    /// uri.normalize();
+    /// assert!(uri.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        self.uri.is_normalized()
+    }
+
+    /// Normalizes the absolute URI.
+    ///
+    /// See [`URI::normalize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let mut uri = AbsoluteURI::try_from("http://EXAMPLE.com:80/a/../b").unwrap();
+    /// uri.normalize();
+    /// assert_eq!(uri.to_string(), "http://example.com/b");
+    /// ```
+    pub fn normalize(&mut self) {
+        self.uri.normalize();
+    }
+
+    /// Returns the scheme as it was spelled in the source the absolute URI was parsed from, see
+    /// [`URIReference::original_scheme_str`](crate::URIReference::original_scheme_str).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("Urn:example").unwrap();
+    /// assert_eq!(uri.scheme().as_str(), "urn");
+    /// assert_eq!(uri.original_scheme_str(), "Urn");
+    /// ```
+    pub fn original_scheme_str(&self) -> &str {
+        self.uri.original_scheme_str()
+    }
+
+    /// Parses an absolute URI from the given string or byte string, keeping track of where parsing
+    /// failed.
+    ///
+    /// See [`URIReference::parse`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::{AbsoluteURI, AbsoluteURIError, Component};
+    ///
+    /// let error = AbsoluteURI::parse("http://example.com/#fragment").unwrap_err();
+    /// assert_eq!(error.component(), Component::Fragment);
+    /// assert_eq!(error.position(), 19);
+    /// assert_eq!(error.into_error(), AbsoluteURIError::HasFragment);
+    /// ```
+    pub fn parse<TValue>(value: &'uri TValue) -> Result<Self, ParseError<AbsoluteURIError>>
+    where
+        TValue: AsRef<[u8]> + ?Sized,
+    {
+        let source = value.as_ref();
+        let uri = URI::parse(source).map_err(|error| error.map_error(AbsoluteURIError::URI))?;
+
+        match uri.fragment() {
+            Some(fragment) => Err(ParseError::new(
+                Component::Fragment,
+                AbsoluteURIError::HasFragment,
+                source.len() - fragment.as_str().len() - 1,
+            )),
+            None => Ok(AbsoluteURI { uri }),
+        }
+    }
+
+    /// Returns the path of the absolute URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://127.0.0.1/my/path").unwrap();
+    /// assert_eq!(uri.path(), "/my/path");
+    /// ```
+    pub fn path(&self) -> &Path<'uri> {
+        self.uri.path()
+    }
+
+    /// Returns the password, if present, of the absolute URI.
+    ///
+    /// Usage of a password in URIs is deprecated.
+    pub fn password(&self) -> Option<&Password<'uri>> {
+        self.uri.password()
+    }
+
+    /// Returns the port, if present, of the absolute URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://example.com:8080/").unwrap();
+    /// assert_eq!(uri.port().unwrap(), 8080);
+    /// ```
+    pub fn port(&self) -> Option<u16> {
+        self.uri.port()
+    }
+
+    /// Returns the query, if present, of the absolute URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://127.0.0.1?my=query").unwrap();
+    /// assert_eq!(uri.query().unwrap(), "my=query");
+    /// ```
+    pub fn query(&self) -> Option<&Query<'uri>> {
+        self.uri.query()
+    }
+
+    /// Returns the scheme of the absolute URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://127.0.0.1/").unwrap();
+    /// assert_eq!(uri.scheme(), "http");
+    /// ```
+    pub fn scheme(&self) -> &Scheme<'uri> {
+        self.uri.scheme()
+    }
+
+    /// Returns the exact length of the absolute URI when converted to a string, see
+    /// [`URIReference::serialized_len`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://example.com/a/b?c").unwrap();
+    /// assert_eq!(uri.serialized_len(), uri.to_string().len());
+    /// ```
+    pub fn serialized_len(&self) -> usize {
+        self.uri.serialized_len()
+    }
+
+    /// Returns a new absolute URI which is identical but has a lifetime tied to this absolute URI.
+    ///
+    /// This function will perform a memory allocation.
+    pub fn to_borrowed(&self) -> AbsoluteURI<'_> {
+        AbsoluteURI {
+            uri: self.uri.to_borrowed(),
+        }
+    }
+
+    /// Returns the username, if present, of the absolute URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://username@example.com").unwrap();
+    /// assert_eq!(uri.username().unwrap(), "username");
+    /// ```
+    pub fn username(&self) -> Option<&Username<'uri>> {
+        self.uri.username()
+    }
+
+    /// Writes the absolute URI to the given [`io::Write`] sink without allocating an intermediate
+    /// string, see [`URIReference::write_to`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::AbsoluteURI;
+    ///
+    /// let uri = AbsoluteURI::try_from("http://example.com/path").unwrap();
+    /// let mut output = Vec::new();
+    /// uri.write_to(&mut output).unwrap();
+    /// assert_eq!(output, "http://example.com/path".as_bytes());
+    /// ```
+    pub fn write_to<TWriter>(&self, writer: TWriter) -> io::Result<()>
+    where
+        TWriter: io::Write,
+    {
+        self.uri.write_to(writer)
+    }
+}
+
+impl Display for AbsoluteURI<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        self.uri.fmt(formatter)
+    }
+}
+
+impl<'uri> From<AbsoluteURI<'uri>> for String {
+    fn from(value: AbsoluteURI<'uri>) -> Self {
+        String::from(value.uri)
+    }
+}
+
+impl<'uri> From<AbsoluteURI<'uri>> for URI<'uri> {
+    fn from(value: AbsoluteURI<'uri>) -> Self {
+        value.uri
+    }
+}
+
+impl<'uri> From<AbsoluteURI<'uri>> for URIReference<'uri> {
+    fn from(value: AbsoluteURI<'uri>) -> Self {
+        value.uri.into()
+    }
+}
+
+impl FromStr for AbsoluteURI<'static> {
+    type Err = AbsoluteURIError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        AbsoluteURI::try_from(value).map(AbsoluteURI::into_owned)
+    }
+}
+
+impl<'uri> TryFrom<&'uri [u8]> for AbsoluteURI<'uri> {
+    type Error = AbsoluteURIError;
+
+    fn try_from(value: &'uri [u8]) -> Result<Self, Self::Error> {
+        AbsoluteURI::parse(value).map_err(ParseError::into_error)
+    }
+}
+
+impl<'uri> TryFrom<&'uri str> for AbsoluteURI<'uri> {
+    type Error = AbsoluteURIError;
+
+    fn try_from(value: &'uri str) -> Result<Self, Self::Error> {
+        AbsoluteURI::try_from(value.as_bytes())
+    }
+}
+
+impl TryFrom<String> for AbsoluteURI<'static> {
+    type Error = AbsoluteURIError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        AbsoluteURI::try_from(value.as_str()).map(AbsoluteURI::into_owned)
+    }
+}
+
+impl<'uri> TryFrom<URI<'uri>> for AbsoluteURI<'uri> {
+    type Error = AbsoluteURIError;
+
+    fn try_from(value: URI<'uri>) -> Result<Self, Self::Error> {
+        if value.has_fragment() {
+            Err(AbsoluteURIError::HasFragment)
+        } else {
+            Ok(AbsoluteURI { uri: value })
+        }
+    }
+}
+
+impl<'uri> TryFrom<URIReference<'uri>> for AbsoluteURI<'uri> {
+    type Error = AbsoluteURIError;
+
+    fn try_from(value: URIReference<'uri>) -> Result<Self, Self::Error> {
+        AbsoluteURI::try_from(URI::try_from(value)?)
+    }
+}
+
+/// An error representing an invalid absolute URI.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum AbsoluteURIError {
+    /// The URI has a fragment component, which is not allowed in an absolute URI.
+    HasFragment,
+
+    /// The source was not a valid URI.
+    URI(URIError),
+}
+
+impl Display for AbsoluteURIError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::AbsoluteURIError::*;
+
+        match self {
+            HasFragment => write!(formatter, "absolute URI has fragment"),
+            URI(error) => error.fmt(formatter),
+        }
+    }
+}
+
+impl Error for AbsoluteURIError {}
+
+impl From<URIError> for AbsoluteURIError {
+    fn from(value: URIError) -> Self {
+        AbsoluteURIError::URI(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_absolute_uri() {
+        let uri = AbsoluteURI::try_from("http://example.com/path?query").unwrap();
+        assert_eq!(uri.to_string(), "http://example.com/path?query");
+
+        let uri: URI<'_> = uri.into();
+        assert_eq!(AbsoluteURI::try_from(uri).unwrap().scheme(), "http");
+
+        assert_eq!(
+            AbsoluteURI::try_from("http://example.com/#fragment"),
+            Err(AbsoluteURIError::HasFragment)
+        );
+        assert_eq!(
+            AbsoluteURI::try_from("http://example.com/#"),
+            Err(AbsoluteURIError::HasFragment)
+        );
+        assert_eq!(
+            AbsoluteURI::try_from("/path"),
+            Err(AbsoluteURIError::URI(URIError::NotURI))
+        );
+
+        let reference = URIReference::try_from("urn:example:test").unwrap();
+        assert!(AbsoluteURI::try_from(reference).is_ok());
+
+        let uri = URI::try_from("http://example.com/#fragment").unwrap();
+        assert!(AbsoluteURI::try_from(uri.into_base_uri()).is_ok());
+    }
+}
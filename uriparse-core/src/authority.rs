@@ -0,0 +1,4674 @@
+//! Authority Component
+//!
+//! See [[RFC3986, Section 3.2](https://tools.ietf.org/html/rfc3986#section-3.2)].
+//!
+//! # Examples
+//!
+//! ```
+//! use std::convert::TryFrom;
+//!
+//! use uriparse::Authority;
+//!
+//! let authority = Authority::try_from("example.com").unwrap();
+//! let host = authority.into_parts().2;
+//! let authority =
+//!     Authority::from_parts(Some("username"), Some("password"), host, Some(80)).unwrap();
+//! assert_eq!(authority.to_string(), "username:password@example.com:80");
+//! ```
+//!
+//! The components of an authority can also be replaced in-place:
+//!
+//! ```
+//! use std::convert::TryFrom;
+//!
+//! use uriparse::Authority;
+//!
+//! let mut authority = Authority::try_from("user:secret@example.com:8080").unwrap();
+//! authority.set_password(None::<&str>).unwrap();
+//! authority.set_host("proxy.internal").unwrap();
+//! authority.map_port(|port| port.map(|port| port + 1));
+//! assert_eq!(authority.to_string(), "user@proxy.internal:8081");
+//! ```
+//!
+//! # Equality
+//!
+//! While many components in this library support string comparison, [`Authority`] does not. This
+//! comes down to it just being too expensive to do a proper host comparison. To do so would require
+//! conversion to [`IpAddr`], which in the case of [`Ipv6Addr`] can be expensive.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::convert::{Infallible, TryFrom};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::mem;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
+use std::ops::Deref;
+use std::str::{self, FromStr};
+use std::vec;
+
+#[cfg(feature = "idna")]
+pub mod idna;
+#[cfg(feature = "psl")]
+mod psl;
+#[cfg(feature = "services")]
+mod services;
+
+#[cfg(feature = "services")]
+pub use self::services::port_for_service;
+
+use crate::scheme::Scheme;
+use crate::utility::{
+    get_percent_encoded_value, get_percent_encoded_value_at, normalize_string, percent_decode,
+    percent_encode, percent_encoded_cmp, percent_encoded_equality, percent_encoded_hash,
+    UNRESERVED_CHAR_MAP,
+};
+
+/// The text that replaces passwords when formatting for debugging or with
+/// [`Authority::display_redacted`].
+const REDACTED_PASSWORD: &str = "****";
+
+/// A map of byte characters that determines if a character is a valid IPv4 or registered name
+/// character.
+#[rustfmt::skip]
+const IPV4_AND_REGISTERED_NAME_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$', b'%', b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9',    0, b';',    0, b'=',    0,    0, // 3
+    0, b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A map of byte characters that determines if a character is a valid future IP literal character.
+#[rustfmt::skip]
+const IPV_FUTURE_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$',    0, b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0, b'=',    0,    0, // 3
+    0, b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A map of byte characters that determines if a character is a valid user information character.
+#[rustfmt::skip]
+const USER_INFO_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$', b'%', b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0, b'=',    0,    0, // 3
+    0, b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A map of byte characters that can be left unencoded in a password.
+///
+/// This is the same as the user information characters except that `'%'` is excluded.
+#[rustfmt::skip]
+const PASSWORD_ENCODE_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$',    0, b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0, b'=',    0,    0, // 3
+    0, b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A map of byte characters that can be left unencoded in a registered name.
+///
+/// This is the same as the registered name characters except that `'%'` is excluded.
+#[rustfmt::skip]
+const REGISTERED_NAME_ENCODE_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$',    0, b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9',    0, b';',    0, b'=',    0,    0, // 3
+    0, b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A map of byte characters that can be left unencoded in a username.
+///
+/// This is the same as the user information characters except that `'%'` and `':'` are excluded.
+#[rustfmt::skip]
+const USERNAME_ENCODE_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$',    0, b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9',    0, b';',    0, b'=',    0,    0, // 3
+    0, b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// The authority component as defined in
+/// [[RFC3986, Section 3.2](https://tools.ietf.org/html/rfc3986#section-3.2)].
+///
+/// Any conversions to a string will **not** hide the password component of the authority. Be
+/// careful if you decide to perform logging.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Authority<'authority> {
+    /// The host component of the authority as defined in
+    /// [[RFC3986, Section 3.2.2](https://tools.ietf.org/html/rfc3986#section-3.2.2)].
+    host: Host<'authority>,
+
+    /// The password component of the authority as defined in
+    /// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+    password: Option<Password<'authority>>,
+
+    /// The port component of the authority as defined in
+    /// [[RFC3986, Section 3.2.3](https://tools.ietf.org/html/rfc3986#section-3.2.3)].
+    port: Option<u16>,
+
+    /// The username component of the authority as defined in
+    /// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+    username: Option<Username<'authority>>,
+}
+
+impl<'authority> Authority<'authority> {
+    pub fn as_borrowed(&self) -> Authority<'_> {
+        let host = self.host.as_borrowed();
+        let password = self.password.as_ref().map(Password::as_borrowed);
+        let username = self.username.as_ref().map(Username::as_borrowed);
+
+        Authority {
+            host,
+            password,
+            port: self.port,
+            username,
+        }
+    }
+
+    /// Constructs a default builder for an authority.
+    ///
+    /// This provides an alternative means of constructing an authority besides parsing and
+    /// [`Authority::from_parts`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::builder()
+    ///     .with_credentials("user@example.com", Some("p:ss"))
+    ///     .try_with_host("example.com")
+    ///     .unwrap()
+    ///     .with_port(Some(8080))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(authority.to_string(), "user%40example.com:p:ss@example.com:8080");
+    /// ```
+    pub fn builder<'new_authority>() -> AuthorityBuilder<'new_authority> {
+        AuthorityBuilder::default()
+    }
+
+    /// Returns a value that displays the authority with its password replaced by `"****"`.
+    ///
+    /// This is useful for logging authorities without leaking credentials. Passwords are redacted
+    /// in the same way when debug formatted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("user:secret@example.com:80").unwrap();
+    /// assert_eq!(authority.display_redacted().to_string(), "user:****@example.com:80");
+    /// assert!(!format!("{:?}", authority).contains("secret"));
+    /// ```
+    pub fn display_redacted(&self) -> impl Display + '_ {
+        RedactedAuthority { authority: self }
+    }
+
+    /// Returns whether the authority is equal to the given authority after normalization, without
+    /// modifying either of them.
+    ///
+    /// Hosts are compared case-insensitively and usernames and passwords are compared ignoring
+    /// differences in percent-encoding. If a scheme is given, an absent port is considered equal to
+    /// the default port of the scheme (see [`Scheme::default_port`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, Scheme};
+    ///
+    /// let authority = Authority::try_from("user@EXAMPLE.com").unwrap();
+    /// let other = Authority::try_from("%75ser@example.com:443").unwrap();
+    /// assert!(authority.eq_normalized(&other, Some(&Scheme::HTTPS)));
+    /// assert!(!authority.eq_normalized(&other, Some(&Scheme::HTTP)));
+    /// assert!(!authority.eq_normalized(&other, None));
+    /// ```
+    pub fn eq_normalized(&self, other: &Authority<'_>, scheme: Option<&Scheme<'_>>) -> bool {
+        let default_port = scheme.and_then(|scheme| scheme.default_port());
+
+        self.host == other.host
+            && self.port.or(default_port) == other.port.or(default_port)
+            && self.username == other.username
+            && self.password == other.password
+    }
+
+    /// Writes the authority to the formatter, replacing the password by `"****"` if requested.
+    fn fmt_with_redaction(&self, formatter: &mut Formatter, redact_password: bool) -> fmt::Result {
+        if let Some(ref username) = self.username {
+            username.fmt(formatter)?;
+
+            if let Some(ref password) = self.password {
+                formatter.write_char(':')?;
+
+                if redact_password {
+                    formatter.write_str(REDACTED_PASSWORD)?;
+                } else {
+                    password.fmt(formatter)?;
+                }
+            }
+
+            formatter.write_char('@')?;
+        }
+
+        write!(formatter, "{}", self.host)?;
+
+        if let Some(port) = self.port {
+            formatter.write_char(':')?;
+            port.fmt(formatter)?;
+        }
+
+        Ok(())
+    }
+
+    /// Constructs a new [`Authority`] from the individual parts: username, password, host, and
+    /// port.
+    ///
+    /// The lifetime used by the resulting value will be the lifetime of the part that is most
+    /// restricted in scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::from_parts(
+    ///     Some("username"),
+    ///     Some("password"),
+    ///     "example.com",
+    ///     Some(80)
+    /// ).unwrap();
+    /// assert_eq!(authority.to_string(), "username:password@example.com:80");
+    /// ```
+    pub fn from_parts<
+        'new_authority,
+        TUsername,
+        TPassword,
+        THost,
+        TUsernameError,
+        TPasswordError,
+        THostError,
+    >(
+        username: Option<TUsername>,
+        password: Option<TPassword>,
+        host: THost,
+        port: Option<u16>,
+    ) -> Result<Authority<'new_authority>, AuthorityError>
+    where
+        Username<'new_authority>: TryFrom<TUsername, Error = TUsernameError>,
+        Password<'new_authority>: TryFrom<TPassword, Error = TPasswordError>,
+        Host<'new_authority>: TryFrom<THost, Error = THostError>,
+        AuthorityError: From<TUsernameError> + From<TPasswordError> + From<THostError>,
+    {
+        let username = match username {
+            Some(username) => Some(Username::try_from(username)?),
+            None => None,
+        };
+
+        let password = match password {
+            Some(password) => Some(Password::try_from(password)?),
+            None => None,
+        };
+
+        let host = Host::try_from(host)?;
+
+        Ok(Authority {
+            host,
+            password,
+            port,
+            username,
+        })
+    }
+
+    /// Constructs an authority from the given parts in a `const` context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the username or password is invalid. In a `const` or `static` item, this results
+    /// in a compilation error instead.
+    pub(crate) const fn from_static_parts(
+        username: Option<&'static str>,
+        password: Option<&'static str>,
+        host: Host<'static>,
+        port: Option<u16>,
+    ) -> Authority<'static> {
+        let username = match username {
+            Some(username) => Some(Username::from_static(username)),
+            None => None,
+        };
+
+        let password = match password {
+            Some(password) => Some(Password::from_static(password)),
+            None => None,
+        };
+
+        Authority {
+            host,
+            password,
+            port,
+            username,
+        }
+    }
+
+    /// Constructs a new [`Authority`] from the individual parts given as arbitrary unencoded text.
+    ///
+    /// Unlike [`Authority::from_parts`], this cannot fail: any character that is not allowed in a
+    /// part, as well as `'%'` itself, is percent-encoded (see [`Username::encode`],
+    /// [`Password::encode`] and [`RegisteredName::encode`]). A host that is an IPv4 or IPv6
+    /// address without brackets (e.g. `"::1"`) becomes an IP address host, anything else becomes a
+    /// registered name. If a password is given without a username, the username will be `""`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::from_unencoded_parts(
+    ///     Some("user@example.com"),
+    ///     Some("p@ss:word"),
+    ///     "example.com",
+    ///     Some(8080),
+    /// );
+    /// assert_eq!(
+    ///     authority.to_string(),
+    ///     "user%40example.com:p%40ss:word@example.com:8080"
+    /// );
+    ///
+    /// let authority = Authority::from_unencoded_parts(None, None, "::1", None);
+    /// assert_eq!(authority.to_string(), "[::1]");
+    ///
+    /// let authority = Authority::from_unencoded_parts(None, None, "my host", None);
+    /// assert_eq!(authority.to_string(), "my%20host");
+    /// ```
+    pub fn from_unencoded_parts(
+        username: Option<&str>,
+        password: Option<&str>,
+        host: &str,
+        port: Option<u16>,
+    ) -> Authority<'static> {
+        let password = password.map(Password::encode);
+        let username = match (username, &password) {
+            (Some(username), _) => Some(Username::encode(username)),
+            (None, Some(_)) => Some(Username::encode("")),
+            (None, None) => None,
+        };
+        let host = match host.parse() {
+            Ok(address) => Host::from(IpAddr::V4(address)),
+            Err(_) => match host.parse() {
+                Ok(address) => Host::from(IpAddr::V6(address)),
+                Err(_) => Host::RegisteredName(RegisteredName::encode(host)),
+            },
+        };
+
+        Authority {
+            host,
+            password,
+            port,
+            username,
+        }
+    }
+
+    /// Returns whether there is a password in the authority as defined in
+    /// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+    ///
+    /// There will only be a password if the URI has a user information component *and* the
+    /// component contains the `':'` delimiter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("username:password@example.com").unwrap();
+    /// assert!(authority.has_password());
+    /// ```
+    pub fn has_password(&self) -> bool {
+        self.password.is_some()
+    }
+
+    /// Returns whether there is a password in the authority as defined in
+    /// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("example.com:8080").unwrap();
+    /// assert!(authority.has_port());
+    /// ```
+    pub fn has_port(&self) -> bool {
+        self.port.is_some()
+    }
+
+    /// Returns whether there is a username in the authority as defined in
+    /// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+    ///
+    /// There will *always* be a username as long as there is a `'@'` delimiter present in the
+    /// authority.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("username@example.com").unwrap();
+    /// assert!(authority.has_username());
+    /// ```
+    pub fn has_username(&self) -> bool {
+        self.username.is_some()
+    }
+
+    /// The host component of the authority as defined in
+    /// [[RFC3986, Section 3.2.2](https://tools.ietf.org/html/rfc3986#section-3.2.2)].
+    ///
+    /// An authority component always has a host, though it may be an empty registered name.
+    ///
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("username:password@example.com").unwrap();
+    /// assert_eq!(authority.host().to_string().as_str(), "example.com");
+    /// ```
+    pub fn host(&self) -> &Host<'authority> {
+        &self.host
+    }
+
+    /// Converts the [`Authority`] into an owned copy.
+    ///
+    /// If you construct the authority from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the authority will just copy the eferences, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Authority<'static> {
+        let password = self.password.map(Password::into_owned);
+        let username = self.username.map(Username::into_owned);
+        let host = self.host.into_owned();
+
+        Authority {
+            host,
+            port: self.port,
+            password,
+            username,
+        }
+    }
+
+    /// Consumes the [`Authority`] and returns its parts: username, password, host, and port.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("username:password@example.com:80").unwrap();
+    /// let (username, password, host, port) = authority.into_parts();
+    ///
+    /// assert_eq!(username.unwrap(), "username");
+    /// assert_eq!(password.unwrap(), "password");
+    /// assert_eq!(host.to_string(), "example.com");
+    /// assert_eq!(port.unwrap(), 80);
+    /// ```
+    pub fn into_parts(
+        self,
+    ) -> (
+        Option<Username<'authority>>,
+        Option<Password<'authority>>,
+        Host<'authority>,
+        Option<u16>,
+    ) {
+        (self.username, self.password, self.host, self.port)
+    }
+
+    /// Returns whether the authority is normalized.
+    ///
+    /// A normalized authority will have all of its sub-components normalized.
+    ///
+    /// This function runs in constant-time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("username:password@example.com").unwrap();
+    /// assert!(authority.is_normalized());
+    ///
+    /// let mut authority = Authority::try_from("username:p%61ssword@EXAMPLE.COM").unwrap();
+    /// assert!(!authority.is_normalized());
+    /// authority.normalize();
+    /// assert!(authority.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        if let Some(username) = self.username.as_ref() {
+            if !username.is_normalized() {
+                return false;
+            }
+        }
+
+        if let Some(password) = self.password.as_ref() {
+            if !password.is_normalized() {
+                return false;
+            }
+        }
+
+        self.host.is_normalized()
+    }
+
+    /// Maps the host using the given map function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, Host};
+    ///
+    /// let mut authority = Authority::try_from("example.com").unwrap();
+    /// authority.map_host(|_| Host::try_from("127.0.0.1").unwrap());
+    /// assert_eq!(authority.to_string(), "127.0.0.1");
+    /// ```
+    pub fn map_host<TMapper>(&mut self, mapper: TMapper) -> &Host<'authority>
+    where
+        TMapper: FnOnce(Host<'authority>) -> Host<'authority>,
+    {
+        let temp_host = Host::RegisteredName(RegisteredName {
+            normalized: true,
+            registered_name: Cow::from(""),
+        });
+        let host = mapper(mem::replace(&mut self.host, temp_host));
+        self.set_host(host)
+            .expect("mapped host resulted in invalid state")
+    }
+
+    /// Maps the password using the given map function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, Password};
+    ///
+    /// let mut authority = Authority::try_from("example.com").unwrap();
+    /// authority.map_password(|_| Some(Password::try_from("password").unwrap()));
+    /// assert_eq!(authority.to_string(), ":password@example.com");
+    /// ```
+    pub fn map_password<TMapper>(&mut self, mapper: TMapper) -> Option<&Password<'authority>>
+    where
+        TMapper: FnOnce(Option<Password<'authority>>) -> Option<Password<'authority>>,
+    {
+        let password = mapper(self.password.take());
+        self.set_password(password)
+            .expect("mapped password resulted in invalid state")
+    }
+
+    /// Maps the port using the given map function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let mut authority = Authority::try_from("example.com").unwrap();
+    /// authority.map_port(|_| Some(8080));
+    /// assert_eq!(authority.to_string(), "example.com:8080");
+    /// ```
+    pub fn map_port<TMapper>(&mut self, mapper: TMapper) -> Option<u16>
+    where
+        TMapper: FnOnce(Option<u16>) -> Option<u16>,
+    {
+        let port = mapper(self.port);
+        self.set_port(port)
+    }
+
+    /// Maps the username using the given map function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, Username};
+    ///
+    /// let mut authority = Authority::try_from("example.com").unwrap();
+    /// authority.map_username(|_| Some(Username::try_from("username").unwrap()));
+    /// assert_eq!(authority.to_string(), "username@example.com");
+    /// ```
+    pub fn map_username<TMapper>(&mut self, mapper: TMapper) -> Option<&Username<'authority>>
+    where
+        TMapper: FnOnce(Option<Username<'authority>>) -> Option<Username<'authority>>,
+    {
+        let username = mapper(self.username.take());
+        self.set_username(username)
+            .expect("mapped username resulted in invalid state")
+    }
+
+    /// Normalizes the authority.
+    ///
+    /// A normalized authority will have all of its sub-components normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let mut authority = Authority::try_from("username:password@example.com").unwrap();
+    /// authority.normalize();
+    /// assert_eq!(authority.to_string(), "username:password@example.com");
+    ///
+    /// let mut authority = Authority::try_from("username:p%61ssword@EXAMPLE.COM").unwrap();
+    /// assert_eq!(authority.to_string(), "username:p%61ssword@EXAMPLE.COM");
+    /// authority.normalize();
+    /// assert_eq!(authority.to_string(), "username:password@example.com");
+    ///
+    /// let mut authority = Authority::try_from("[0:0:0:0:0:0:0:1]:80").unwrap();
+    /// authority.normalize();
+    /// assert_eq!(authority.to_string(), "[::1]:80");
+    /// ```
+    pub fn normalize(&mut self) {
+        if let Some(username) = self.username.as_mut() {
+            username.normalize();
+        }
+
+        if let Some(password) = self.password.as_mut() {
+            password.normalize();
+        }
+
+        self.host.normalize();
+    }
+
+    /// The password component of the authority as defined in
+    /// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+    ///
+    /// The password will be `None` if the user information component of the authority did not
+    /// contain a `':'`. Otherwise, it will be whatever is after the `':'` until the `'@'`
+    /// character. It may be empty as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("username:password@example.com").unwrap();
+    /// assert_eq!(authority.password().unwrap(), "password");
+    /// ```
+    pub fn password(&self) -> Option<&Password<'authority>> {
+        self.password.as_ref()
+    }
+
+    /// The port component of the authority as defined in
+    /// [[RFC3986, Section 3.2.3]](https://tools.ietf.org/html/rfc3986#section-3.2.3).
+    ///
+    /// The port will be `None` if a port was not specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("example.com:80").unwrap();
+    /// assert_eq!(authority.port().unwrap(), 80);
+    /// ```
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// Returns the port of the authority, or the default port of the given scheme if the
+    /// authority does not have one (see [`Scheme::default_port`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, Scheme};
+    ///
+    /// let authority = Authority::try_from("example.com:8080").unwrap();
+    /// assert_eq!(authority.port_or_scheme_default(&Scheme::HTTP), Some(8080));
+    ///
+    /// let authority = Authority::try_from("example.com").unwrap();
+    /// assert_eq!(authority.port_or_scheme_default(&Scheme::HTTPS), Some(443));
+    /// assert_eq!(authority.port_or_scheme_default(&Scheme::URN), None);
+    /// ```
+    pub fn port_or_scheme_default(&self, scheme: &Scheme<'_>) -> Option<u16> {
+        self.port.or_else(|| scheme.default_port())
+    }
+
+    /// Sets the host of the authority.
+    ///
+    /// An error will be returned if the conversion to a [`Host`] fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use std::net::Ipv6Addr;
+    ///
+    /// use uriparse::{Authority, Host};
+    ///
+    /// let mut authority = Authority::try_from("example.com:8080").unwrap();
+    /// authority.set_host("127.0.0.1");
+    /// assert_eq!(authority.to_string(), "127.0.0.1:8080");
+    /// authority.set_host(Host::IPv6Address("::1".parse().unwrap()));
+    /// assert_eq!(authority.to_string(), "[::1]:8080");
+    /// ```
+    pub fn set_host<THost, THostError>(
+        &mut self,
+        host: THost,
+    ) -> Result<&Host<'authority>, AuthorityError>
+    where
+        Host<'authority>: TryFrom<THost, Error = THostError>,
+        AuthorityError: From<THostError>,
+    {
+        self.host = Host::try_from(host)?;
+        Ok(self.host())
+    }
+
+    /// Sets the password of the authority.
+    ///
+    /// An error will be returned if the conversion to a [`Password`] fails.
+    ///
+    /// If the given password is not `None`, then the username will be set to `""` if it is
+    /// currently not set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let mut authority = Authority::try_from("example.com").unwrap();
+    /// authority.set_password(Some("secret"));
+    /// assert_eq!(authority.to_string(), ":secret@example.com");
+    /// ```
+    pub fn set_password<TPassword, TPasswordError>(
+        &mut self,
+        password: Option<TPassword>,
+    ) -> Result<Option<&Password<'authority>>, AuthorityError>
+    where
+        Password<'authority>: TryFrom<TPassword, Error = TPasswordError>,
+        AuthorityError: From<TPasswordError>,
+    {
+        self.password = match password {
+            Some(password) => {
+                let password = Password::try_from(password)?;
+
+                if self.username.is_none() {
+                    self.username = Some(Username {
+                        normalized: true,
+                        username: Cow::from(""),
+                    });
+                }
+
+                Some(password)
+            }
+            None => None,
+        };
+        Ok(self.password())
+    }
+
+    /// Sets the port of the authority.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let mut authority = Authority::try_from("example.com").unwrap();
+    /// authority.set_port(Some(8080));
+    /// assert_eq!(authority.to_string(), "example.com:8080");
+    /// ```
+    pub fn set_port(&mut self, port: Option<u16>) -> Option<u16> {
+        self.port = port;
+        self.port
+    }
+
+    /// Sets the username of the authority.
+    ///
+    /// An error will be returned if the conversion to a [`Username`] fails.
+    ///
+    /// If the given username is `None`, this will also remove any set password.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, Username};
+    ///
+    /// let mut authority = Authority::try_from("example.com").unwrap();
+    /// authority.set_username(Some("myname"));
+    /// assert_eq!(authority.to_string(), "myname@example.com");
+    ///
+    /// let mut authority = Authority::try_from("user:pass@example.com").unwrap();
+    /// authority.set_username(None::<Username>);
+    /// assert_eq!(authority.to_string(), "example.com");
+    /// ```
+    pub fn set_username<TUsername, TUsernameError>(
+        &mut self,
+        username: Option<TUsername>,
+    ) -> Result<Option<&Username<'authority>>, AuthorityError>
+    where
+        Username<'authority>: TryFrom<TUsername, Error = TUsernameError>,
+        AuthorityError: From<TUsernameError>,
+    {
+        self.username = match username {
+            Some(username) => Some(Username::try_from(username)?),
+            None => {
+                self.password = None;
+                None
+            }
+        };
+        Ok(self.username())
+    }
+
+    /// Returns the socket address of the authority if its host is an IP address.
+    ///
+    /// The given default port is used if the authority does not have a port. An IPv6 zone
+    /// identifier is used as the scope ID of the address after percent-decoding it. Interface
+    /// names are not resolved, so [`None`] is returned if the zone identifier is not numeric, as
+    /// it is if the host is a registered name. See [`Authority::to_socket_addrs`] for resolving
+    /// registered names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use std::net::SocketAddr;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("127.0.0.1:8080").unwrap();
+    /// let address: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+    /// assert_eq!(authority.to_socket_addr(80), Some(address));
+    ///
+    /// let authority = Authority::try_from("[::1]").unwrap();
+    /// let address: SocketAddr = "[::1]:80".parse().unwrap();
+    /// assert_eq!(authority.to_socket_addr(80), Some(address));
+    ///
+    /// let authority = Authority::try_from("[fe80::1%253]").unwrap();
+    /// let address: SocketAddr = "[fe80::1%3]:80".parse().unwrap();
+    /// assert_eq!(authority.to_socket_addr(80), Some(address));
+    ///
+    /// let authority = Authority::try_from("[fe80::1%25eth0]").unwrap();
+    /// assert_eq!(authority.to_socket_addr(80), None);
+    ///
+    /// let authority = Authority::try_from("example.com:8080").unwrap();
+    /// assert_eq!(authority.to_socket_addr(80), None);
+    /// ```
+    pub fn to_socket_addr(&self, default_port: u16) -> Option<SocketAddr> {
+        let port = self.port.unwrap_or(default_port);
+
+        match &self.host {
+            Host::IPv6AddressWithZone(address, zone) => {
+                let scope_id = percent_decode(zone).parse().ok()?;
+                Some(SocketAddrV6::new(*address, port, 0, scope_id).into())
+            }
+            host => Some(SocketAddr::new(host.to_ip_addr()?, port)),
+        }
+    }
+
+    /// Resolves the authority into socket addresses using the resolver of the standard library.
+    ///
+    /// The given default port is used if the authority does not have a port. IP address hosts are
+    /// returned as-is without consulting the resolver, while registered names are percent-decoded
+    /// before being resolved. Future IP literals cannot be resolved, so they result in an error of
+    /// kind [`io::ErrorKind::InvalidInput`] wrapping [`HostError::AddressMechanismNotSupported`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use std::net::SocketAddr;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("127.0.0.1").unwrap();
+    /// let addresses: Vec<SocketAddr> = authority.to_socket_addrs(80).unwrap().collect();
+    /// assert_eq!(addresses, vec!["127.0.0.1:80".parse().unwrap()]);
+    /// ```
+    pub fn to_socket_addrs(&self, default_port: u16) -> io::Result<vec::IntoIter<SocketAddr>> {
+        match &self.host {
+            Host::IPvFuture(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                HostError::AddressMechanismNotSupported,
+            )),
+            Host::RegisteredName(name) => {
+                let port = self.port.unwrap_or(default_port);
+                (&*percent_decode(name.as_str()), port).to_socket_addrs()
+            }
+            _ => match self.to_socket_addr(default_port) {
+                Some(address) => Ok(vec![address].into_iter()),
+                None => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    HostError::NonNumericZoneIdentifier,
+                )),
+            },
+        }
+    }
+
+    /// Parses the authority from the given byte string, tolerating the malformations allowed by the
+    /// given options.
+    ///
+    /// This is meant for parsing authorities found in the wild, e.g. by crawlers, the way browsers
+    /// do. With the default options, this behaves exactly like [`Authority::try_from`]. If the
+    /// input has to be modified to be parsed, the returned authority will own its components.
+    ///
+    /// Note that an empty port after a trailing `':'` (e.g. `"example.com:"`) is allowed by
+    /// [[RFC3986, Section 3.2.3](https://tools.ietf.org/html/rfc3986#section-3.2.3)], so it is
+    /// always accepted and dropped, even in strict parsing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, AuthorityParseOptions};
+    ///
+    /// let value = b" user@example.com@host.example:8080\n";
+    /// assert!(Authority::try_from(&value[..]).is_err());
+    ///
+    /// let options = AuthorityParseOptions {
+    ///     allow_multiple_at_signs: true,
+    ///     trim_whitespace: true,
+    /// };
+    /// let authority = Authority::try_from_lenient(value, &options).unwrap();
+    /// assert_eq!(authority.username().unwrap(), "user%40example.com");
+    /// assert_eq!(authority.host().to_string(), "host.example");
+    /// assert_eq!(authority.port(), Some(8080));
+    /// ```
+    pub fn try_from_lenient(
+        value: &'authority [u8],
+        options: &AuthorityParseOptions,
+    ) -> Result<Authority<'authority>, AuthorityError> {
+        let mut value = value;
+        let mut owned_value: Option<Vec<u8>> = None;
+
+        if options.trim_whitespace {
+            let is_trimmed = |byte: &u8| *byte <= b' ';
+            let start = value
+                .iter()
+                .position(|byte| !is_trimmed(byte))
+                .unwrap_or(value.len());
+            let end = value
+                .iter()
+                .rposition(|byte| !is_trimmed(byte))
+                .map_or(start, |index| index + 1);
+            value = &value[start..end];
+
+            if value
+                .iter()
+                .any(|&byte| matches!(byte, b'\t' | b'\n' | b'\r'))
+            {
+                owned_value = Some(
+                    value
+                        .iter()
+                        .cloned()
+                        .filter(|&byte| !matches!(byte, b'\t' | b'\n' | b'\r'))
+                        .collect(),
+                );
+            }
+        }
+
+        if options.allow_multiple_at_signs {
+            let current_value = owned_value.as_deref().unwrap_or(value);
+            let end_index = current_value
+                .iter()
+                .position(|&byte| matches!(byte, b'/' | b'?' | b'#'))
+                .unwrap_or(current_value.len());
+            let at_signs = current_value[..end_index]
+                .iter()
+                .filter(|&&byte| byte == b'@')
+                .count();
+
+            if at_signs > 1 {
+                let mut encoded_value = Vec::with_capacity(current_value.len() + 2 * at_signs);
+                let mut remaining = at_signs;
+
+                for (index, &byte) in current_value.iter().enumerate() {
+                    if byte == b'@' && index < end_index && remaining > 1 {
+                        encoded_value.extend_from_slice(b"%40");
+                        remaining -= 1;
+                    } else {
+                        encoded_value.push(byte);
+                    }
+                }
+
+                owned_value = Some(encoded_value);
+            }
+        }
+
+        match owned_value {
+            Some(value) => Authority::try_from(value.as_slice()).map(Authority::into_owned),
+            None => Authority::try_from(value),
+        }
+    }
+
+    /// The username component of the authority as defined in
+    /// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+    ///
+    /// The username will be `None` if the user information component of the authority did not
+    /// contain a `':'`. Otherwise, it will be whatever is after the `':'` until the `'@'`
+    /// character. It may be empty as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("username:password@example.com").unwrap();
+    /// assert_eq!(authority.password().unwrap(), "password");
+    /// ```
+    pub fn username(&self) -> Option<&Username<'authority>> {
+        self.username.as_ref()
+    }
+}
+
+impl Display for Authority<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        self.fmt_with_redaction(formatter, false)
+    }
+}
+
+/// An authority that is displayed with its password replaced by `"****"`.
+struct RedactedAuthority<'reference, 'authority> {
+    authority: &'reference Authority<'authority>,
+}
+
+impl Display for RedactedAuthority<'_, '_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        self.authority.fmt_with_redaction(formatter, true)
+    }
+}
+
+impl<'authority> From<Authority<'authority>> for String {
+    fn from(value: Authority<'authority>) -> String {
+        value.to_string()
+    }
+}
+
+impl From<(IpAddr, u16)> for Authority<'static> {
+    fn from((address, port): (IpAddr, u16)) -> Self {
+        Authority {
+            host: Host::from(address),
+            password: None,
+            port: Some(port),
+            username: None,
+        }
+    }
+}
+
+/// The scope ID of an IPv6 address is used as its zone identifier if it is non-zero.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::SocketAddr;
+///
+/// use uriparse::Authority;
+///
+/// let address: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+/// assert_eq!(Authority::from(address).to_string(), "127.0.0.1:8080");
+///
+/// let address: SocketAddr = "[fe80::1%3]:443".parse().unwrap();
+/// let authority = Authority::from(address);
+/// assert_eq!(authority.to_string(), "[fe80::1%253]:443");
+/// assert_eq!(authority.to_socket_addr(80), Some(address));
+/// ```
+impl From<SocketAddr> for Authority<'static> {
+    fn from(value: SocketAddr) -> Self {
+        match value {
+            SocketAddr::V4(address) => Authority::from(address),
+            SocketAddr::V6(address) => Authority::from(address),
+        }
+    }
+}
+
+impl From<SocketAddrV4> for Authority<'static> {
+    fn from(value: SocketAddrV4) -> Self {
+        Authority {
+            host: Host::from(value),
+            password: None,
+            port: Some(value.port()),
+            username: None,
+        }
+    }
+}
+
+impl From<SocketAddrV6> for Authority<'static> {
+    fn from(value: SocketAddrV6) -> Self {
+        Authority {
+            host: Host::from(value),
+            password: None,
+            port: Some(value.port()),
+            username: None,
+        }
+    }
+}
+
+impl FromStr for Authority<'static> {
+    type Err = AuthorityError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Authority::try_from(value).map(Authority::into_owned)
+    }
+}
+
+/// Orders authorities by their components in the order they appear: username, password, host and
+/// port. Absent components are ordered before present ones.
+impl Ord for Authority<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.username
+            .cmp(&other.username)
+            .then_with(|| self.password.cmp(&other.password))
+            .then_with(|| self.host.cmp(&other.host))
+            .then_with(|| self.port.cmp(&other.port))
+    }
+}
+
+impl PartialOrd for Authority<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'authority> TryFrom<&'authority [u8]> for Authority<'authority> {
+    type Error = AuthorityError;
+
+    fn try_from(value: &'authority [u8]) -> Result<Self, Self::Error> {
+        let (authority, rest) = parse_authority(value).map_err(|(error, _)| error)?;
+
+        if rest.is_empty() {
+            Ok(authority)
+        } else if authority.has_port() {
+            Err(AuthorityError::Port(PortError::InvalidCharacter))
+        } else if authority.host().is_ipv6_address() {
+            Err(AuthorityError::Host(HostError::InvalidIPv6Character))
+        } else {
+            Err(AuthorityError::Host(
+                HostError::InvalidIPv4OrRegisteredNameCharacter,
+            ))
+        }
+    }
+}
+
+impl<'authority> TryFrom<&'authority str> for Authority<'authority> {
+    type Error = AuthorityError;
+
+    fn try_from(value: &'authority str) -> Result<Self, Self::Error> {
+        Authority::try_from(value.as_bytes())
+    }
+}
+
+/// A builder type for [`Authority`].
+///
+/// You must use the [`AuthorityBuilder::host`] function before building as authorities always have
+/// a host. Everything else is optional.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AuthorityBuilder<'authority> {
+    /// The host component of the authority as defined in
+    /// [[RFC3986, Section 3.2.2](https://tools.ietf.org/html/rfc3986#section-3.2.2)].
+    host: Option<Host<'authority>>,
+
+    /// The password component of the authority as defined in
+    /// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+    password: Option<Password<'authority>>,
+
+    /// The port component of the authority as defined in
+    /// [[RFC3986, Section 3.2.3](https://tools.ietf.org/html/rfc3986#section-3.2.3)].
+    port: Option<u16>,
+
+    /// The username component of the authority as defined in
+    /// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+    username: Option<Username<'authority>>,
+}
+
+impl<'authority> AuthorityBuilder<'authority> {
+    /// Consumes the builder and tries to build an [`Authority`].
+    ///
+    /// This function will error if a host was not specified in the builder. If a password was
+    /// specified without a username, the username will be set to `""`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::{AuthorityBuilder, AuthorityError};
+    ///
+    /// let result = AuthorityBuilder::new().with_port(Some(80)).build();
+    /// assert_eq!(result, Err(AuthorityError::MissingHost));
+    /// ```
+    pub fn build(self) -> Result<Authority<'authority>, AuthorityError> {
+        let host = match self.host {
+            Some(host) => host,
+            None => return Err(AuthorityError::MissingHost),
+        };
+
+        let username = match (self.username, &self.password) {
+            (None, Some(_)) => Some(Username {
+                normalized: true,
+                username: Cow::from(""),
+            }),
+            (username, _) => username,
+        };
+
+        Ok(Authority {
+            host,
+            password: self.password,
+            port: self.port,
+            username,
+        })
+    }
+
+    /// Sets the username and password of the authority from arbitrary unencoded text.
+    ///
+    /// Any character that is not allowed in the username or password, as well as `'%'` itself, is
+    /// percent-encoded (see [`Username::encode`] and [`Password::encode`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::AuthorityBuilder;
+    ///
+    /// let mut builder = AuthorityBuilder::new();
+    /// builder
+    ///     .credentials("user:name", Some("100% secret"))
+    ///     .try_host("example.com")
+    ///     .unwrap();
+    /// let authority = builder.build().unwrap();
+    /// assert_eq!(authority.to_string(), "user%3Aname:100%25%20secret@example.com");
+    /// ```
+    pub fn credentials(&mut self, username: &str, password: Option<&str>) -> &mut Self {
+        self.username = Some(Username::encode(username));
+        self.password = password.map(Password::encode);
+        self
+    }
+
+    /// Sets the host of the authority.
+    ///
+    /// It is required to specify a host. Not doing so will result in an error during the
+    /// [`AuthorityBuilder::build`] function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    ///
+    /// use uriparse::{AuthorityBuilder, Host};
+    ///
+    /// let mut builder = AuthorityBuilder::new();
+    /// builder.host(Host::from(Ipv4Addr::LOCALHOST));
+    /// let authority = builder.build().unwrap();
+    /// assert_eq!(authority.to_string(), "127.0.0.1");
+    /// ```
+    pub fn host(&mut self, host: Host<'authority>) -> &mut Self {
+        self.host = Some(host);
+        self
+    }
+
+    /// Constructs a new builder with nothing set.
+    pub fn new() -> Self {
+        AuthorityBuilder::default()
+    }
+
+    /// Sets the password of the authority.
+    ///
+    /// It is optional to specify a password.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{AuthorityBuilder, Password};
+    ///
+    /// let mut builder = AuthorityBuilder::new();
+    /// builder
+    ///     .password(Some(Password::try_from("secret").unwrap()))
+    ///     .try_host("example.com")
+    ///     .unwrap();
+    /// let authority = builder.build().unwrap();
+    /// assert_eq!(authority.to_string(), ":secret@example.com");
+    /// ```
+    pub fn password(&mut self, password: Option<Password<'authority>>) -> &mut Self {
+        self.password = password;
+        self
+    }
+
+    /// Sets the port of the authority.
+    ///
+    /// It is optional to specify a port.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::AuthorityBuilder;
+    ///
+    /// let mut builder = AuthorityBuilder::new();
+    /// builder.try_host("example.com").unwrap().port(Some(8080));
+    /// let authority = builder.build().unwrap();
+    /// assert_eq!(authority.to_string(), "example.com:8080");
+    /// ```
+    pub fn port(&mut self, port: Option<u16>) -> &mut Self {
+        self.port = port;
+        self
+    }
+
+    /// Sets the host of the authority.
+    ///
+    /// If the given host is not a valid host (i.e. the conversion fails), an error is returned and
+    /// the builder is left unchanged.
+    ///
+    /// It is required to specify a host.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::AuthorityBuilder;
+    ///
+    /// let mut builder = AuthorityBuilder::new();
+    /// assert!(builder.try_host("[::1").is_err());
+    /// builder.try_host("[::1]").unwrap();
+    /// let authority = builder.build().unwrap();
+    /// assert_eq!(authority.to_string(), "[::1]");
+    /// ```
+    pub fn try_host<THost, THostError>(&mut self, host: THost) -> Result<&mut Self, HostError>
+    where
+        Host<'authority>: TryFrom<THost, Error = THostError>,
+        HostError: From<THostError>,
+    {
+        self.host = Some(Host::try_from(host)?);
+        Ok(self)
+    }
+
+    /// Sets the password of the authority.
+    ///
+    /// If the given password is not a valid password (i.e. the conversion fails), an error is
+    /// returned and the builder is left unchanged.
+    ///
+    /// It is optional to specify a password.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::AuthorityBuilder;
+    ///
+    /// let mut builder = AuthorityBuilder::new();
+    /// assert!(builder.try_password(Some("%ZZ")).is_err());
+    /// builder
+    ///     .try_password(Some("secret"))
+    ///     .unwrap()
+    ///     .try_host("example.com")
+    ///     .unwrap();
+    /// let authority = builder.build().unwrap();
+    /// assert_eq!(authority.to_string(), ":secret@example.com");
+    /// ```
+    pub fn try_password<TPassword, TPasswordError>(
+        &mut self,
+        password: Option<TPassword>,
+    ) -> Result<&mut Self, PasswordError>
+    where
+        Password<'authority>: TryFrom<TPassword, Error = TPasswordError>,
+        PasswordError: From<TPasswordError>,
+    {
+        self.password = match password {
+            Some(password) => Some(Password::try_from(password)?),
+            None => None,
+        };
+        Ok(self)
+    }
+
+    /// Sets the username of the authority.
+    ///
+    /// If the given username is not a valid username (i.e. the conversion fails), an error is
+    /// returned and the builder is left unchanged.
+    ///
+    /// It is optional to specify a username.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::AuthorityBuilder;
+    ///
+    /// let mut builder = AuthorityBuilder::new();
+    /// assert!(builder.try_username(Some("user:name")).is_err());
+    /// builder
+    ///     .try_username(Some("user"))
+    ///     .unwrap()
+    ///     .try_host("example.com")
+    ///     .unwrap();
+    /// let authority = builder.build().unwrap();
+    /// assert_eq!(authority.to_string(), "user@example.com");
+    /// ```
+    pub fn try_username<TUsername, TUsernameError>(
+        &mut self,
+        username: Option<TUsername>,
+    ) -> Result<&mut Self, UsernameError>
+    where
+        Username<'authority>: TryFrom<TUsername, Error = TUsernameError>,
+        UsernameError: From<TUsernameError>,
+    {
+        self.username = match username {
+            Some(username) => Some(Username::try_from(username)?),
+            None => None,
+        };
+        Ok(self)
+    }
+
+    /// Consumes the builder and sets the host of the authority.
+    ///
+    /// If the given host is not a valid host (i.e. the conversion fails), an error is returned.
+    ///
+    /// It is required to specify a host.
+    pub fn try_with_host<THost, THostError>(mut self, host: THost) -> Result<Self, HostError>
+    where
+        Host<'authority>: TryFrom<THost, Error = THostError>,
+        HostError: From<THostError>,
+    {
+        self.try_host(host)?;
+        Ok(self)
+    }
+
+    /// Consumes the builder and sets the password of the authority.
+    ///
+    /// If the given password is not a valid password (i.e. the conversion fails), an error is
+    /// returned.
+    ///
+    /// It is optional to specify a password.
+    pub fn try_with_password<TPassword, TPasswordError>(
+        mut self,
+        password: Option<TPassword>,
+    ) -> Result<Self, PasswordError>
+    where
+        Password<'authority>: TryFrom<TPassword, Error = TPasswordError>,
+        PasswordError: From<TPasswordError>,
+    {
+        self.try_password(password)?;
+        Ok(self)
+    }
+
+    /// Consumes the builder and sets the username of the authority.
+    ///
+    /// If the given username is not a valid username (i.e. the conversion fails), an error is
+    /// returned.
+    ///
+    /// It is optional to specify a username.
+    pub fn try_with_username<TUsername, TUsernameError>(
+        mut self,
+        username: Option<TUsername>,
+    ) -> Result<Self, UsernameError>
+    where
+        Username<'authority>: TryFrom<TUsername, Error = TUsernameError>,
+        UsernameError: From<TUsernameError>,
+    {
+        self.try_username(username)?;
+        Ok(self)
+    }
+
+    /// Sets the username of the authority.
+    ///
+    /// It is optional to specify a username.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{AuthorityBuilder, Username};
+    ///
+    /// let mut builder = AuthorityBuilder::new();
+    /// builder
+    ///     .username(Some(Username::try_from("user").unwrap()))
+    ///     .try_host("example.com")
+    ///     .unwrap();
+    /// let authority = builder.build().unwrap();
+    /// assert_eq!(authority.to_string(), "user@example.com");
+    /// ```
+    pub fn username(&mut self, username: Option<Username<'authority>>) -> &mut Self {
+        self.username = username;
+        self
+    }
+
+    /// Consumes the builder and sets the username and password of the authority from arbitrary
+    /// unencoded text.
+    ///
+    /// See [`AuthorityBuilder::credentials`].
+    pub fn with_credentials(mut self, username: &str, password: Option<&str>) -> Self {
+        self.credentials(username, password);
+        self
+    }
+
+    /// Consumes the builder and sets the host of the authority.
+    ///
+    /// It is required to specify a host.
+    pub fn with_host(mut self, host: Host<'authority>) -> Self {
+        self.host(host);
+        self
+    }
+
+    /// Consumes the builder and sets the password of the authority.
+    ///
+    /// It is optional to specify a password.
+    pub fn with_password(mut self, password: Option<Password<'authority>>) -> Self {
+        self.password(password);
+        self
+    }
+
+    /// Consumes the builder and sets the port of the authority.
+    ///
+    /// It is optional to specify a port.
+    pub fn with_port(mut self, port: Option<u16>) -> Self {
+        self.port(port);
+        self
+    }
+
+    /// Consumes the builder and sets the username of the authority.
+    ///
+    /// It is optional to specify a username.
+    pub fn with_username(mut self, username: Option<Username<'authority>>) -> Self {
+        self.username(username);
+        self
+    }
+}
+
+/// The host component of the authority as defined in
+/// [[RFC3986, Section 3.2.2](https://tools.ietf.org/html/rfc3986#section-3.2.2)].
+///
+/// The RFC mentions support for future IP address literals of the form `"[v*.address]"` where
+/// `'*'` is a hexadecimal version. As of this moment there exist none, so they are represented by
+/// [`Host::IPvFuture`] without any further interpretation of the address.
+///
+/// Also, the host is case-insensitive meaning that `"example.com"` and `"ExAmPlE.CoM"` refer to the
+/// same host. Furthermore, percent-encoding plays no role in equality checking for characters in
+/// the unreserved character set meaning that `"example.com"` and `"ex%61mple.com"` are identical.
+/// Both of these attributes are reflected in the equality and hash functions.
+///
+/// However, be aware that just because percent-encoding plays no role in equality checking does not
+/// mean that the host is normalized. If the host needs to be normalized, use the
+/// [`Host::normalize`] function.
+#[derive(Clone, Debug, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Host<'host> {
+    /// An IPv4 address. Based on the `std`'s implementation, leading zeros for octets are allowed
+    /// for up to three digits. So for example, `"000.000.000.000"` is still considered a valid IPv4
+    /// address, but `"000.000.000.0000"` is not. Thus, it would be considered a registered name.
+    IPv4Address(Ipv4Addr),
+
+    /// An IPv6 address. This will always be encased in brackets (`'['` and `']'`).
+    IPv6Address(Ipv6Addr),
+
+    /// An IPv6 address with a zone identifier as defined in
+    /// [[RFC6874](https://tools.ietf.org/html/rfc6874)], e.g. `"[fe80::1%25eth0]"`. The zone
+    /// identifier is stored as it appears in the URI, i.e. without the leading `"%25"` but still
+    /// percent-encoded. It is compared case-sensitively, but percent-encoding plays no role for
+    /// characters in the unreserved character set, so `"eth0"` and `"%65th0"` are identical.
+    IPv6AddressWithZone(Ipv6Addr, Cow<'host, str>),
+
+    /// A future IP literal. This will always be encased in brackets (`'['` and `']'`).
+    IPvFuture(IPvFuture<'host>),
+
+    /// Any other host that does not follow the syntax of an IP address. This includes even hosts of
+    /// the form `"999.999.999.999"`. One might expect this to produce an invalid IPv4 error, but
+    /// the RFC states that it is a "first-match-wins" algorithm, and that host does not match the
+    /// IPv4 literal syntax.
+    ///
+    /// This may be changed in the future, since arguments can be made from either side.
+    RegisteredName(RegisteredName<'host>),
+}
+
+impl Host<'_> {
+    /// Returns a new host which is identical but has a lifetime tied to this host.
+    pub fn as_borrowed(&self) -> Host<'_> {
+        use self::Host::*;
+
+        match self {
+            IPv4Address(ipv4) => IPv4Address(*ipv4),
+            IPv6Address(ipv6) => IPv6Address(*ipv6),
+            IPv6AddressWithZone(ipv6, zone) => IPv6AddressWithZone(*ipv6, Cow::from(zone.as_ref())),
+            IPvFuture(ipvfuture) => IPvFuture(ipvfuture.as_borrowed()),
+            RegisteredName(name) => RegisteredName(name.as_borrowed()),
+        }
+    }
+
+    /// Converts the [`Host`] into an owned copy.
+    ///
+    /// If you construct the host from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the host will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Host<'static> {
+        use self::Host::*;
+
+        match self {
+            IPv4Address(ipv4) => IPv4Address(ipv4),
+            IPv6Address(ipv6) => IPv6Address(ipv6),
+            IPv6AddressWithZone(ipv6, zone) => {
+                IPv6AddressWithZone(ipv6, Cow::from(zone.into_owned()))
+            }
+            IPvFuture(ipvfuture) => IPvFuture(ipvfuture.into_owned()),
+            RegisteredName(name) => RegisteredName(name.into_owned()),
+        }
+    }
+
+    /// Returns whether the host is globally reachable.
+    ///
+    /// IP addresses are global unless they fall within a special-purpose range, e.g. the
+    /// unspecified, loopback, private, link-local, shared and documentation ranges, as listed in
+    /// the IANA special-purpose address registries. IPv4-mapped IPv6 addresses are classified as
+    /// the IPv4 address they map to.
+    ///
+    /// Registered names are global unless they are one of the well-known names recognized by
+    /// [`Host::is_loopback`] or [`Host::is_link_local`]. Names are not resolved, so a global name
+    /// may still resolve to a non-global address. IPvFuture hosts and empty hosts are never global.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// assert!(Host::try_from("93.184.216.34").unwrap().is_global());
+    /// assert!(Host::try_from("[2606:2800:220:1::]").unwrap().is_global());
+    /// assert!(Host::try_from("example.com").unwrap().is_global());
+    /// assert!(Host::try_from("192.0.0.9").unwrap().is_global());
+    /// assert!(Host::try_from("192.0.0.10").unwrap().is_global());
+    ///
+    /// assert!(!Host::try_from("10.0.0.1").unwrap().is_global());
+    /// assert!(!Host::try_from("192.0.0.8").unwrap().is_global());
+    /// assert!(!Host::try_from("100.64.0.1").unwrap().is_global());
+    /// assert!(!Host::try_from("[2001:db8::1]").unwrap().is_global());
+    /// assert!(!Host::try_from("[::ffff:127.0.0.1]").unwrap().is_global());
+    /// assert!(!Host::try_from("LOCALHOST").unwrap().is_global());
+    /// ```
+    pub fn is_global(&self) -> bool {
+        match self {
+            Host::RegisteredName(name) => {
+                let name = special_use_name(name);
+                !name.is_empty() && !is_link_local_name(&name) && !is_loopback_name(&name)
+            }
+            host => match host.to_ip_addr().map(unmap_ip_addr) {
+                Some(IpAddr::V4(address)) => is_global_ipv4(address),
+                Some(IpAddr::V6(address)) => is_global_ipv6(address),
+                None => false,
+            },
+        }
+    }
+
+    /// Returns whether the host is an IPv4 address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("192.168.1.1").unwrap();
+    /// assert!(host.is_ipv4_address());
+    /// ```
+    pub fn is_ipv4_address(&self) -> bool {
+        matches!(self, Host::IPv4Address(_))
+    }
+
+    /// Returns whether the host is an IPv6 address, with or without a zone identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[::1]").unwrap();
+    /// assert!(host.is_ipv6_address());
+    ///
+    /// let host = Host::try_from("[fe80::1%25eth0]").unwrap();
+    /// assert!(host.is_ipv6_address());
+    /// ```
+    pub fn is_ipv6_address(&self) -> bool {
+        matches!(self, Host::IPv6Address(_) | Host::IPv6AddressWithZone(_, _))
+    }
+
+    /// Returns whether the host is link-local.
+    ///
+    /// This is the case for IPv4 addresses in `169.254.0.0/16`, IPv6 addresses in `fe80::/10`, and
+    /// registered names under the `"local"` domain used by multicast DNS
+    /// [[RFC6762](https://tools.ietf.org/html/rfc6762)]. Registered names are compared after
+    /// percent-decoding, case-insensitively, and ignoring a trailing `'.'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// assert!(Host::try_from("169.254.169.254").unwrap().is_link_local());
+    /// assert!(Host::try_from("[fe80::1%25eth0]").unwrap().is_link_local());
+    /// assert!(Host::try_from("printer.local").unwrap().is_link_local());
+    /// assert!(!Host::try_from("example.com").unwrap().is_link_local());
+    /// ```
+    pub fn is_link_local(&self) -> bool {
+        match self {
+            Host::RegisteredName(name) => is_link_local_name(&special_use_name(name)),
+            host => match host.to_ip_addr().map(unmap_ip_addr) {
+                Some(IpAddr::V4(address)) => address.is_link_local(),
+                Some(IpAddr::V6(address)) => address.segments()[0] & 0xffc0 == 0xfe80,
+                None => false,
+            },
+        }
+    }
+
+    /// Returns whether the host is a loopback host.
+    ///
+    /// This is the case for IPv4 addresses in `127.0.0.0/8`, the IPv6 address `::1`, and the
+    /// registered name `"localhost"` as well as any name under it
+    /// [[RFC6761, Section 6.3](https://tools.ietf.org/html/rfc6761#section-6.3)]. Registered names
+    /// are compared after percent-decoding, case-insensitively, and ignoring a trailing `'.'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// assert!(Host::try_from("127.0.0.1").unwrap().is_loopback());
+    /// assert!(Host::try_from("[::1]").unwrap().is_loopback());
+    /// assert!(Host::try_from("[::ffff:127.0.0.1]").unwrap().is_loopback());
+    /// assert!(Host::try_from("localhost").unwrap().is_loopback());
+    /// assert!(Host::try_from("api.l%6Fcalhost.").unwrap().is_loopback());
+    /// assert!(!Host::try_from("localhost.example.com").unwrap().is_loopback());
+    /// ```
+    pub fn is_loopback(&self) -> bool {
+        match self {
+            Host::RegisteredName(name) => is_loopback_name(&special_use_name(name)),
+            host => match host.to_ip_addr().map(unmap_ip_addr) {
+                Some(address) => address.is_loopback(),
+                None => false,
+            },
+        }
+    }
+
+    /// Returns whether the host is normalized.
+    ///
+    /// IPv4 and IPv6 hosts will always be normalized. Registered names are considered normalized
+    /// if all characters are lowercase, no bytes that are in the unreserved character set are
+    /// percent-encoded, and all alphabetical characters in percent-encodings are uppercase. The
+    /// same applies to IPv6 zone identifiers, except that they keep their case.
+    ///
+    /// This function runs in constant-time, except for IPv6 addresses with a zone identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("192.168.1.1").unwrap();
+    /// assert!(host.is_normalized());
+    ///
+    /// let mut host = Host::try_from("EXAMPLE.COM").unwrap();
+    /// assert!(!host.is_normalized());
+    /// host.normalize();
+    /// assert!(host.is_normalized());
+    ///
+    /// let host = Host::try_from("[fe80::1%25Eth%2F0]").unwrap();
+    /// assert!(host.is_normalized());
+    ///
+    /// let mut host = Host::try_from("[fe80::1%25%65th0]").unwrap();
+    /// assert!(!host.is_normalized());
+    /// host.normalize();
+    /// assert!(host.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        match self {
+            Host::IPv6AddressWithZone(_, zone) => check_zone_identifier_normalized(zone.as_bytes()),
+            Host::IPvFuture(ipvfuture) => ipvfuture.is_normalized(),
+            Host::RegisteredName(name) => name.is_normalized(),
+            _ => true,
+        }
+    }
+
+    /// Returns whether the host is an IP address in a private range.
+    ///
+    /// This is the case for IPv4 addresses in `10.0.0.0/8`, `172.16.0.0/12` and `192.168.0.0/16`
+    /// [[RFC1918](https://tools.ietf.org/html/rfc1918)] and IPv6 unique local addresses in
+    /// `fc00::/7` [[RFC4193](https://tools.ietf.org/html/rfc4193)]. Registered names are never
+    /// private.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// assert!(Host::try_from("192.168.1.1").unwrap().is_private());
+    /// assert!(Host::try_from("[fd00::1]").unwrap().is_private());
+    /// assert!(!Host::try_from("8.8.8.8").unwrap().is_private());
+    /// ```
+    pub fn is_private(&self) -> bool {
+        match self.to_ip_addr().map(unmap_ip_addr) {
+            Some(IpAddr::V4(address)) => address.is_private(),
+            Some(IpAddr::V6(address)) => address.segments()[0] & 0xfe00 == 0xfc00,
+            None => false,
+        }
+    }
+
+    /// Returns whether the host is a registered name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("example.com").unwrap();
+    /// assert!(host.is_registered_name());
+    /// ```
+    pub fn is_registered_name(&self) -> bool {
+        matches!(self, Host::RegisteredName(_))
+    }
+
+    /// Returns whether the host is the given domain or a subdomain of it.
+    ///
+    /// Labels are compared after converting them from their ASCII compatible encoding,
+    /// percent-decoding and lowercasing them, and a trailing `'.'` is ignored. A host is only
+    /// considered a subdomain of a domain that is not a public suffix according to the Public
+    /// Suffix List, e.g. no host is a subdomain of `"com"` or `"co.uk"`, although a host is still
+    /// equal to them. IP address and IPvFuture hosts are never subdomains.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("WWW.Ex%61mple.com").unwrap();
+    /// assert!(host.is_subdomain_of("example.com"));
+    /// assert!(host.is_subdomain_of("www.example.com."));
+    /// assert!(!host.is_subdomain_of("com"));
+    /// assert!(!host.is_subdomain_of("ample.com"));
+    /// ```
+    #[cfg(feature = "psl")]
+    pub fn is_subdomain_of(&self, domain: &str) -> bool {
+        let name = match self {
+            Host::RegisteredName(name) => name.as_str(),
+            _ => return false,
+        };
+        let labels = psl_labels(name);
+        let domain = psl_labels(domain);
+
+        if labels.is_empty() || domain.is_empty() || !labels.ends_with(&domain) {
+            return false;
+        }
+
+        labels.len() == domain.len() || psl::public_suffix_length(&domain) < domain.len()
+    }
+
+    /// Normalizes the host such that all characters are lowercase, no bytes that are in the
+    /// unreserved character set are percent-encoded, and all alphabetical characters in
+    /// percent-encodings are uppercase.
+    ///
+    /// If the host is already normalized, the function will return immediately. Otherwise, if
+    /// the host is not owned, this function will perform an allocation to clone it. The
+    /// normalization itself though, is done in-place with no extra memory allocations required.
+    ///
+    /// IPv4 and IPv6 hosts are always considered normalized, since they are stored parsed and are
+    /// always written in their canonical form. For IPv6 addresses, this is the form recommended by
+    /// [[RFC5952, Section 4](https://tools.ietf.org/html/rfc5952#section-4)], i.e. lowercase
+    /// hexadecimal digits without leading zeros and the longest run of zeros compressed to `"::"`.
+    /// Their zone identifiers are normalized like registered names, except that they keep their
+    /// case. A zone identifier that is not valid, which can only be the case if the host was
+    /// constructed directly, is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use std::convert::TryFrom;
+    /// use std::net::Ipv6Addr;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let mut host = Host::try_from("192.168.1.1").unwrap();
+    /// host.normalize();
+    /// assert_eq!(host.to_string(), "192.168.1.1");
+    ///
+    /// let mut host = Host::try_from("[0:0:0:0:0:0:0:1]").unwrap();
+    /// host.normalize();
+    /// assert_eq!(host.to_string(), "[::1]");
+    ///
+    /// let host = Host::try_from("[2001:DB8:0:0:1:0:0:1]").unwrap();
+    /// assert_eq!(host.to_string(), "[2001:db8::1:0:0:1]");
+    ///
+    /// let mut host = Host::try_from("[fe80::1%25%45th%2f0]").unwrap();
+    /// host.normalize();
+    /// assert_eq!(host.to_string(), "[fe80::1%25Eth%2F0]");
+    ///
+    /// let mut host = Host::IPv6AddressWithZone(Ipv6Addr::LOCALHOST, Cow::Borrowed("%41\u{e9}"));
+    /// host.normalize();
+    /// assert_eq!(host.to_string(), "[::1%25%41\u{e9}]");
+    ///
+    /// let mut host = Host::try_from("%ff%41").unwrap();
+    /// assert_eq!(host.to_string(), "%ff%41");
+    /// host.normalize();
+    /// assert_eq!(host.to_string(), "%FFA");
+    /// ```
+    pub fn normalize(&mut self) {
+        match self {
+            Host::IPv6AddressWithZone(_, zone)
+                if check_zone_identifier(zone.as_bytes())
+                    && !check_zone_identifier_normalized(zone.as_bytes()) =>
+            {
+                // Unsafe: The function above [`check_zone_identifier`] ensures this is valid
+                // ASCII-US.
+                unsafe { normalize_string(zone.to_mut(), true) };
+            }
+            Host::IPvFuture(ipvfuture) => ipvfuture.normalize(),
+            Host::RegisteredName(name) => name.normalize(),
+            _ => (),
+        }
+    }
+
+    /// Returns the registrable domain of the host according to the Public Suffix List, i.e. its
+    /// public suffix plus one more label.
+    ///
+    /// Labels are matched against the list after converting them from their ASCII compatible
+    /// encoding, percent-decoding and lowercasing them. The returned registered name is a suffix
+    /// of the original name, so it keeps its original encoding. A trailing `'.'` is not part of it.
+    ///
+    /// [`None`] is returned for IP address and IPvFuture hosts, for names that are a public suffix
+    /// themselves, and for names that contain empty labels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("www.Example.com").unwrap();
+    /// assert_eq!(host.registrable_domain().unwrap(), "Example.com");
+    ///
+    /// let host = Host::try_from("www.example.co.uk").unwrap();
+    /// assert_eq!(host.registrable_domain().unwrap(), "example.co.uk");
+    ///
+    /// let host = Host::try_from("com").unwrap();
+    /// assert!(host.registrable_domain().is_none());
+    ///
+    /// let host = Host::try_from("127.0.0.1").unwrap();
+    /// assert!(host.registrable_domain().is_none());
+    /// ```
+    #[cfg(feature = "psl")]
+    pub fn registrable_domain(&self) -> Option<RegisteredName<'_>> {
+        let name = match self {
+            Host::RegisteredName(name) => name.as_str(),
+            _ => return None,
+        };
+        let name = name.strip_suffix('.').unwrap_or(name);
+        let labels = psl_labels(name);
+
+        if labels.iter().any(String::is_empty) {
+            return None;
+        }
+
+        let length = psl::public_suffix_length(&labels) + 1;
+
+        if length > labels.len() {
+            return None;
+        }
+
+        let start = match name.rmatch_indices('.').nth(length - 1) {
+            Some((index, _)) => index + 1,
+            None => 0,
+        };
+        let registrable_domain = &name[start..];
+        let (_, normalized) = check_ipv4_or_registered_name(registrable_domain.as_bytes());
+
+        Some(RegisteredName {
+            normalized,
+            registered_name: Cow::from(registrable_domain),
+        })
+    }
+
+    /// Converts the host into its ASCII compatible encoding as defined in
+    /// [[RFC5890](https://tools.ietf.org/html/rfc5890)].
+    ///
+    /// Each label of a registered name that decodes to non-ASCII characters is lowercased and
+    /// converted to Punycode with an `"xn--"` prefix. The returned host is normalized. IP address
+    /// hosts are returned as is.
+    ///
+    /// Only lowercasing is applied to the labels. The mapping and validation of
+    /// [[UTS #46](https://www.unicode.org/reports/tr46/)] are not, so for example `"ß"` is not
+    /// mapped to `"ss"`, full-width characters are kept, and most disallowed code points are
+    /// encoded anyway. The result may therefore differ from what browsers and DNS resolvers use.
+    ///
+    /// An error will be returned if the registered name does not decode to valid UTF-8, or if a
+    /// label that is not ASCII contains an ASCII character other than a letter, digit, or hyphen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("B%C3%BCcher.de").unwrap();
+    /// assert_eq!(host.to_ascii().unwrap().to_string(), "xn--bcher-kva.de");
+    ///
+    /// let host = Host::try_from("a%20%C3%BC.de").unwrap();
+    /// assert!(host.to_ascii().is_err());
+    /// ```
+    #[cfg(feature = "idna")]
+    pub fn to_ascii(&self) -> Result<Host<'static>, idna::IdnaError> {
+        match self {
+            Host::RegisteredName(name) => {
+                let name = idna::to_ascii(name.as_str())?;
+                let mut host = Host::try_from(name.as_str())
+                    .map_err(|_| idna::IdnaError::InvalidLabelCharacter)?
+                    .into_owned();
+                host.normalize();
+                Ok(host)
+            }
+            host => Ok(host.clone().into_owned()),
+        }
+    }
+
+    /// Returns the IP address of the host, or [`None`] if the host is a registered name.
+    ///
+    /// The address has already been validated during parsing, so no further parsing is done.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("192.168.1.1").unwrap();
+    /// assert_eq!(host.to_ip_addr(), Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1))));
+    ///
+    /// let host = Host::try_from("[::1]").unwrap();
+    /// assert_eq!(host.to_ip_addr(), Some(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+    ///
+    /// let host = Host::try_from("example.com").unwrap();
+    /// assert_eq!(host.to_ip_addr(), None);
+    /// ```
+    pub fn to_ip_addr(&self) -> Option<IpAddr> {
+        match self {
+            Host::IPv4Address(address) => Some(IpAddr::V4(*address)),
+            Host::IPv6Address(address) | Host::IPv6AddressWithZone(address, _) => {
+                Some(IpAddr::V6(*address))
+            }
+            Host::IPvFuture(_) | Host::RegisteredName(_) => None,
+        }
+    }
+
+    /// Returns the host as a string without the brackets around IPv6 addresses.
+    ///
+    /// This is the form expected outside of URIs, e.g. by [`std::net`] or for TLS server name
+    /// indication. A zone identifier is percent-decoded and separated by a plain `'%'` instead of
+    /// `"%25"`. IPvFuture hosts keep their brackets since they have no representation outside of
+    /// URIs. This is the same as formatting the host with the alternate flag, i.e.
+    /// `format!("{:#}", host)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use std::net::Ipv6Addr;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[::1]").unwrap();
+    /// assert_eq!(host.to_string(), "[::1]");
+    /// assert_eq!(host.to_string_no_brackets(), "::1");
+    /// assert_eq!(host.to_string_no_brackets().parse(), Ok(Ipv6Addr::LOCALHOST));
+    ///
+    /// let host = Host::try_from("[fe80::1%25eth0]").unwrap();
+    /// assert_eq!(host.to_string_no_brackets(), "fe80::1%eth0");
+    ///
+    /// let host = Host::try_from("example.com").unwrap();
+    /// assert_eq!(format!("{:#}", host), "example.com");
+    /// ```
+    pub fn to_string_no_brackets(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// Converts the labels of the host that are in their ASCII compatible encoding back to Unicode.
+    ///
+    /// Since a host may only contain ASCII characters, the decoded labels are lowercased and
+    /// percent-encoded. All other labels and IP address hosts are left as is.
+    ///
+    /// An error will be returned if a label prefixed with `"xn--"` does not contain valid Punycode,
+    /// or if it is not the encoding [`Host::to_ascii`] would produce for the decoded label, e.g.
+    /// `"xn--"` or `"xn--2-"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("xn--bcher-kva.de").unwrap();
+    /// assert_eq!(host.to_unicode().unwrap().to_string(), "b%C3%BCcher.de");
+    /// ```
+    #[cfg(feature = "idna")]
+    pub fn to_unicode(&self) -> Result<Host<'static>, idna::IdnaError> {
+        match self {
+            Host::RegisteredName(name) => {
+                let name = idna::to_unicode(name.as_str())?;
+                Ok(Host::try_from(name.as_str())
+                    .expect("percent-encoded Unicode labels should always form a valid host")
+                    .into_owned())
+            }
+            host => Ok(host.clone().into_owned()),
+        }
+    }
+
+    /// Returns the zone identifier of the host, if it is an IPv6 address with one.
+    ///
+    /// The zone identifier is returned as it appears in the URI, i.e. still percent-encoded. Zone
+    /// identifiers that only differ in the percent-encoding of unreserved characters compare equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[fe80::1%25eth0]").unwrap();
+    /// assert_eq!(host.zone_identifier(), Some("eth0"));
+    /// assert_eq!(host.to_string(), "[fe80::1%25eth0]");
+    /// assert_eq!(host, Host::try_from("[fe80::1%25%65th0]").unwrap());
+    /// assert_ne!(host, Host::try_from("[fe80::1%25ETH0]").unwrap());
+    ///
+    /// let host = Host::try_from("[fe80::1]").unwrap();
+    /// assert_eq!(host.zone_identifier(), None);
+    /// ```
+    pub fn zone_identifier(&self) -> Option<&str> {
+        match self {
+            Host::IPv6AddressWithZone(_, zone) => Some(zone),
+            _ => None,
+        }
+    }
+}
+
+/// The alternate flag (`{:#}`) omits the brackets around IPv6 addresses, see
+/// [`Host::to_string_no_brackets`].
+impl Display for Host<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::Host::*;
+
+        match self {
+            IPv4Address(address) => address.fmt(formatter),
+            IPv6Address(address) if formatter.alternate() => address.fmt(formatter),
+            IPv6Address(address) => {
+                formatter.write_char('[')?;
+                address.fmt(formatter)?;
+                formatter.write_char(']')
+            }
+            IPv6AddressWithZone(address, zone) if formatter.alternate() => {
+                address.fmt(formatter)?;
+                formatter.write_char('%')?;
+                formatter.write_str(&percent_decode(zone))
+            }
+            IPv6AddressWithZone(address, zone) => {
+                formatter.write_char('[')?;
+                address.fmt(formatter)?;
+                formatter.write_str("%25")?;
+                formatter.write_str(zone)?;
+                formatter.write_char(']')
+            }
+            IPvFuture(ipvfuture) => {
+                formatter.write_char('[')?;
+                ipvfuture.fmt(formatter)?;
+                formatter.write_char(']')
+            }
+            RegisteredName(name) => formatter.write_str(name.as_str()),
+        }
+    }
+}
+
+impl<'host> From<Host<'host>> for String {
+    fn from(value: Host<'host>) -> String {
+        value.to_string()
+    }
+}
+
+impl From<IpAddr> for Host<'static> {
+    fn from(value: IpAddr) -> Self {
+        match value {
+            IpAddr::V4(address) => Host::IPv4Address(address),
+            IpAddr::V6(address) => Host::IPv6Address(address),
+        }
+    }
+}
+
+impl From<Ipv4Addr> for Host<'static> {
+    fn from(value: Ipv4Addr) -> Self {
+        Host::IPv4Address(value)
+    }
+}
+
+impl From<Ipv6Addr> for Host<'static> {
+    fn from(value: Ipv6Addr) -> Self {
+        Host::IPv6Address(value)
+    }
+}
+
+impl From<SocketAddr> for Host<'static> {
+    fn from(value: SocketAddr) -> Self {
+        match value {
+            SocketAddr::V4(address) => Host::from(address),
+            SocketAddr::V6(address) => Host::from(address),
+        }
+    }
+}
+
+impl From<SocketAddrV4> for Host<'static> {
+    fn from(value: SocketAddrV4) -> Self {
+        Host::IPv4Address(*value.ip())
+    }
+}
+
+/// A non-zero scope ID is used as the zone identifier of the address.
+impl From<SocketAddrV6> for Host<'static> {
+    fn from(value: SocketAddrV6) -> Self {
+        match value.scope_id() {
+            0 => Host::IPv6Address(*value.ip()),
+            scope_id => Host::IPv6AddressWithZone(*value.ip(), Cow::from(scope_id.to_string())),
+        }
+    }
+}
+
+impl FromStr for Host<'static> {
+    type Err = HostError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Host::try_from(value).map(Host::into_owned)
+    }
+}
+
+impl Hash for Host<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        use self::Host::*;
+
+        mem::discriminant(self).hash(state);
+
+        match self {
+            IPv4Address(address) => address.hash(state),
+            IPv6Address(address) => address.hash(state),
+            IPv6AddressWithZone(address, zone) => {
+                address.hash(state);
+                percent_encoded_hash(zone.as_bytes(), state, true);
+            }
+            IPvFuture(ipvfuture) => ipvfuture.hash(state),
+            RegisteredName(name) => name.hash(state),
+        }
+    }
+}
+
+/// Hosts of different kinds are ordered in the order the variants are declared in.
+impl Ord for Host<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        use self::Host::*;
+
+        fn rank(host: &Host<'_>) -> u8 {
+            match host {
+                IPv4Address(_) => 0,
+                IPv6Address(_) => 1,
+                IPv6AddressWithZone(_, _) => 2,
+                IPvFuture(_) => 3,
+                RegisteredName(_) => 4,
+            }
+        }
+
+        match (self, other) {
+            (IPv4Address(left), IPv4Address(right)) => left.cmp(right),
+            (IPv6Address(left), IPv6Address(right)) => left.cmp(right),
+            (IPv6AddressWithZone(left, left_zone), IPv6AddressWithZone(right, right_zone)) => {
+                left.cmp(right).then_with(|| {
+                    percent_encoded_cmp(left_zone.as_bytes(), right_zone.as_bytes(), true)
+                })
+            }
+            (IPvFuture(left), IPvFuture(right)) => left.cmp(right),
+            (RegisteredName(left), RegisteredName(right)) => left.cmp(right),
+            _ => rank(self).cmp(&rank(other)),
+        }
+    }
+}
+
+impl PartialEq for Host<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        use self::Host::*;
+
+        match (self, other) {
+            (IPv4Address(left), IPv4Address(right)) => left == right,
+            (IPv6Address(left), IPv6Address(right)) => left == right,
+            (IPv6AddressWithZone(left, left_zone), IPv6AddressWithZone(right, right_zone)) => {
+                left == right
+                    && percent_encoded_equality(left_zone.as_bytes(), right_zone.as_bytes(), true)
+            }
+            (IPvFuture(left), IPvFuture(right)) => left == right,
+            (RegisteredName(left), RegisteredName(right)) => left == right,
+            _ => false,
+        }
+    }
+}
+
+impl PartialOrd for Host<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'host> TryFrom<Host<'host>> for IpAddr {
+    /// The host is given back if it is a registered name.
+    type Error = Host<'host>;
+
+    fn try_from(value: Host<'host>) -> Result<Self, Self::Error> {
+        value.to_ip_addr().ok_or(value)
+    }
+}
+
+impl<'host> TryFrom<&'host [u8]> for Host<'host> {
+    type Error = HostError;
+
+    fn try_from(value: &'host [u8]) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            let registered_name = RegisteredName {
+                normalized: true,
+                registered_name: Cow::from(""),
+            };
+            return Ok(Host::RegisteredName(registered_name));
+        }
+
+        match (value.first(), value.last()) {
+            (Some(b'['), Some(b']')) => {
+                if let Some(prefix) = value.get(1) {
+                    if prefix.eq_ignore_ascii_case(&b'v') {
+                        // IPvFuture
+
+                        return IPvFuture::try_from(&value[1..value.len() - 1])
+                            .map(Host::IPvFuture);
+                    }
+                }
+
+                // IPv6
+
+                let ipv6 = &value[1..value.len() - 1];
+                let (ipv6, zone) = match ipv6.iter().position(|&byte| byte == b'%') {
+                    Some(index) => match ipv6[index..].strip_prefix(b"%25") {
+                        Some(zone) => (&ipv6[..index], Some(zone)),
+                        None => return Err(HostError::InvalidZoneIdentifier),
+                    },
+                    None => (ipv6, None),
+                };
+
+                if !check_ipv6(ipv6) {
+                    return Err(HostError::InvalidIPv6Character);
+                }
+
+                // Unsafe: The function above [`check_ipv6`] ensures this is valid ASCII-US.
+                let ipv6: Ipv6Addr = unsafe { str::from_utf8_unchecked(ipv6) }
+                    .parse()
+                    .map_err(|_| HostError::InvalidIPv6Format)?;
+
+                match zone {
+                    Some(zone) => {
+                        if !check_zone_identifier(zone) {
+                            return Err(HostError::InvalidZoneIdentifier);
+                        }
+
+                        // Unsafe: The function above [`check_zone_identifier`] ensures this is
+                        // valid ASCII-US.
+                        let zone = unsafe { str::from_utf8_unchecked(zone) };
+                        Ok(Host::IPv6AddressWithZone(ipv6, Cow::from(zone)))
+                    }
+                    None => Ok(Host::IPv6Address(ipv6)),
+                }
+            }
+            _ => {
+                let (valid, normalized) = check_ipv4_or_registered_name(value);
+
+                if valid {
+                    // Unsafe: The function above [`check_ipv4_or_registered_name`] ensures
+                    // this is valid ASCII-US.
+                    let value_string = unsafe { str::from_utf8_unchecked(value) };
+
+                    match value_string.parse() {
+                        Ok(ipv4) => Ok(Host::IPv4Address(ipv4)),
+                        Err(_) => Ok(Host::RegisteredName(RegisteredName {
+                            normalized,
+                            registered_name: Cow::from(value_string),
+                        })),
+                    }
+                } else {
+                    Err(HostError::InvalidIPv4OrRegisteredNameCharacter)
+                }
+            }
+        }
+    }
+}
+
+impl<'host> TryFrom<&'host str> for Host<'host> {
+    type Error = HostError;
+
+    fn try_from(value: &'host str) -> Result<Self, Self::Error> {
+        Host::try_from(value.as_bytes())
+    }
+}
+
+/// A future IP literal as defined in
+/// [[RFC3986, Section 3.2.2](https://tools.ietf.org/html/rfc3986#section-3.2.2)], e.g.
+/// `"v7.fe80::a+en1"`.
+///
+/// The literal consists of a hexadecimal version and an address, which are both case-insensitive.
+/// This is reflected in the equality and hash functions.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use uriparse::{Host, HostError};
+///
+/// let host = Host::try_from("[v7.fe80::a+en1]").unwrap();
+///
+/// match host {
+///     Host::IPvFuture(ipvfuture) => {
+///         assert_eq!(ipvfuture.version(), "7");
+///         assert_eq!(ipvfuture.address(), "fe80::a+en1");
+///     }
+///     _ => unreachable!(),
+/// }
+///
+/// assert_eq!(Host::try_from("[vG.address]"), Err(HostError::InvalidIPvFutureCharacter));
+/// assert_eq!(Host::try_from("[v1.]"), Err(HostError::InvalidIPvFutureFormat));
+/// assert_eq!(Host::try_from("[v1]"), Err(HostError::InvalidIPvFutureFormat));
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IPvFuture<'ipvfuture> {
+    /// The address of the literal, which is everything after the first `'.'`.
+    address: Cow<'ipvfuture, str>,
+
+    /// Whether the literal is normalized.
+    normalized: bool,
+
+    /// The hexadecimal version of the literal, which is everything between the `'v'` and the
+    /// first `'.'`.
+    version: Cow<'ipvfuture, str>,
+}
+
+impl IPvFuture<'_> {
+    /// Returns the address of the literal, which is everything after the first `'.'`.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Returns a new future IP literal which is identical but has a lifetime tied to this literal.
+    pub fn as_borrowed(&self) -> IPvFuture<'_> {
+        IPvFuture {
+            address: Cow::from(self.address.as_ref()),
+            normalized: self.normalized,
+            version: Cow::from(self.version.as_ref()),
+        }
+    }
+
+    /// Constructs a future IP literal from the given string without the enclosing brackets in a
+    /// `const` context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid future IP literal. In a `const` or `static` item, this
+    /// results in a compilation error instead.
+    pub(crate) const fn from_static(value: &'static str) -> IPvFuture<'static> {
+        let bytes = value.as_bytes();
+
+        if bytes.is_empty() || !bytes[0].eq_ignore_ascii_case(&b'v') {
+            panic!("invalid future IP literal format");
+        }
+
+        let mut index = 1;
+        let mut normalized = bytes[0].is_ascii_lowercase();
+
+        while index < bytes.len() && bytes[index] != b'.' {
+            if !bytes[index].is_ascii_hexdigit() {
+                panic!("invalid future IP literal character");
+            }
+
+            if bytes[index].is_ascii_uppercase() {
+                normalized = false;
+            }
+
+            index += 1;
+        }
+
+        if index == 1 || index + 1 >= bytes.len() {
+            panic!("invalid future IP literal format");
+        }
+
+        let (version, address) = value.split_at(index);
+        let (_, version) = version.split_at(1);
+        let (_, address) = address.split_at(1);
+
+        if !check_ipvfuture(address.as_bytes()) {
+            panic!("invalid future IP literal character");
+        }
+
+        let mut index = 0;
+
+        while index < address.len() {
+            if address.as_bytes()[index].is_ascii_uppercase() {
+                normalized = false;
+            }
+
+            index += 1;
+        }
+
+        IPvFuture {
+            address: Cow::Borrowed(address),
+            normalized,
+            version: Cow::Borrowed(version),
+        }
+    }
+
+    /// Converts the [`IPvFuture`] into an owned copy.
+    ///
+    /// This is different from just cloning. Cloning the literal will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> IPvFuture<'static> {
+        IPvFuture {
+            address: Cow::from(self.address.into_owned()),
+            normalized: self.normalized,
+            version: Cow::from(self.version.into_owned()),
+        }
+    }
+
+    /// Returns whether the literal is normalized.
+    ///
+    /// A literal is considered normalized if all characters are lowercase.
+    ///
+    /// This function runs in constant-time.
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Normalizes the literal such that all characters are lowercase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let mut host = Host::try_from("[vA.ADDRESS]").unwrap();
+    /// assert!(!host.is_normalized());
+    /// host.normalize();
+    /// assert_eq!(host.to_string(), "[va.address]");
+    /// ```
+    pub fn normalize(&mut self) {
+        if !self.normalized {
+            self.address.to_mut().make_ascii_lowercase();
+            self.version.to_mut().make_ascii_lowercase();
+            self.normalized = true;
+        }
+    }
+
+    /// Returns the hexadecimal version of the literal, which is everything between the `'v'` and
+    /// the first `'.'`.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+impl Display for IPvFuture<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "v{}.{}", self.version, self.address)
+    }
+}
+
+impl Eq for IPvFuture<'_> {}
+
+impl Hash for IPvFuture<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        percent_encoded_hash(self.version.as_bytes(), state, false);
+        percent_encoded_hash(self.address.as_bytes(), state, false);
+    }
+}
+
+impl Ord for IPvFuture<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn lowercase(value: &str) -> impl Iterator<Item = u8> + '_ {
+            value.bytes().map(|byte| byte.to_ascii_lowercase())
+        }
+
+        lowercase(&self.version)
+            .cmp(lowercase(&other.version))
+            .then_with(|| lowercase(&self.address).cmp(lowercase(&other.address)))
+    }
+}
+
+impl PartialEq for IPvFuture<'_> {
+    fn eq(&self, other: &IPvFuture) -> bool {
+        self.version.eq_ignore_ascii_case(&other.version)
+            && self.address.eq_ignore_ascii_case(&other.address)
+    }
+}
+
+impl PartialOrd for IPvFuture<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'ipvfuture> TryFrom<&'ipvfuture [u8]> for IPvFuture<'ipvfuture> {
+    type Error = HostError;
+
+    /// Parses a future IP literal without the enclosing brackets, e.g. `"v7.fe80::a+en1"`.
+    fn try_from(value: &'ipvfuture [u8]) -> Result<Self, Self::Error> {
+        let (prefix, value) = value
+            .split_first()
+            .ok_or(HostError::InvalidIPvFutureFormat)?;
+
+        if !prefix.eq_ignore_ascii_case(&b'v') {
+            return Err(HostError::InvalidIPvFutureFormat);
+        }
+
+        let index = value
+            .iter()
+            .position(|&byte| byte == b'.')
+            .ok_or(HostError::InvalidIPvFutureFormat)?;
+        let (version, address) = (&value[..index], &value[index + 1..]);
+
+        if version.is_empty() || address.is_empty() {
+            return Err(HostError::InvalidIPvFutureFormat);
+        }
+
+        if !version.iter().all(u8::is_ascii_hexdigit) || !check_ipvfuture(address) {
+            return Err(HostError::InvalidIPvFutureCharacter);
+        }
+
+        let normalized = prefix.is_ascii_lowercase()
+            && !version.iter().any(u8::is_ascii_uppercase)
+            && !address.iter().any(u8::is_ascii_uppercase);
+
+        // Unsafe: The checks above ensure these are valid ASCII-US.
+        let (version, address) = unsafe {
+            (
+                str::from_utf8_unchecked(version),
+                str::from_utf8_unchecked(address),
+            )
+        };
+
+        Ok(IPvFuture {
+            address: Cow::from(address),
+            normalized,
+            version: Cow::from(version),
+        })
+    }
+}
+
+impl<'ipvfuture> TryFrom<&'ipvfuture str> for IPvFuture<'ipvfuture> {
+    type Error = HostError;
+
+    fn try_from(value: &'ipvfuture str) -> Result<Self, Self::Error> {
+        IPvFuture::try_from(value.as_bytes())
+    }
+}
+
+/// The password component of the authority as defined in
+/// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+///
+/// Even though this library supports parsing the password from the user information, it should be
+/// noted that the format "username:password" is deprecated. Also, be careful logging this!
+///
+/// The password is case-sensitive. Furthermore, percent-encoding plays no role in equality checking
+/// for characters in the unreserved character set meaning that `"password"` and `"p%61ssword"` are
+/// identical. Both of these attributes are reflected in the equality and hash functions.
+///
+/// Be aware that just because percent-encoding plays no role in equality checking does not
+/// mean that the password is normalized. If the password needs to be normalized, use the
+/// [`Password::normalize`] function.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Password<'password> {
+    /// Whether the password is normalized.
+    normalized: bool,
+
+    /// The internal password source that is either owned or borrowed.
+    password: Cow<'password, str>,
+}
+
+impl Password<'_> {
+    /// Returns a new password which is identical but has a lifetime tied to this password.
+    pub fn as_borrowed(&self) -> Password<'_> {
+        use self::Cow::*;
+
+        let password = match &self.password {
+            Borrowed(borrowed) => *borrowed,
+            Owned(owned) => owned.as_str(),
+        };
+
+        Password {
+            normalized: self.normalized,
+            password: Cow::Borrowed(password),
+        }
+    }
+
+    /// Returns the percent-decoded password.
+    ///
+    /// If the password contains no percent-encodings, the returned value borrows from the password.
+    /// Decoded bytes that are not valid UTF-8 are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Password;
+    ///
+    /// let password = Password::try_from("p%40ss%20word").unwrap();
+    /// assert_eq!(password.as_decoded(), "p@ss word");
+    /// ```
+    pub fn as_decoded(&self) -> Cow<'_, str> {
+        percent_decode(&self.password)
+    }
+
+    /// Returns a `str` representation of the password.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Password;
+    ///
+    /// let password = Password::try_from("password").unwrap();
+    /// assert_eq!(password, "password");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.password
+    }
+
+    /// Constructs a password from arbitrary unencoded text.
+    ///
+    /// Any character that is not allowed in a password, as well as `'%'` itself, is
+    /// percent-encoded, so the returned password always decodes back to the given text. The
+    /// resulting password is always normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Password;
+    ///
+    /// let password = Password::encode("100% p@ss");
+    /// assert_eq!(password.as_str(), "100%25%20p%40ss");
+    /// assert_eq!(password.as_decoded(), "100% p@ss");
+    /// ```
+    pub fn encode(value: &str) -> Password<'static> {
+        let mut password = String::with_capacity(value.len());
+        percent_encode(value.as_bytes(), &PASSWORD_ENCODE_CHAR_MAP, &mut password);
+
+        Password {
+            normalized: true,
+            password: Cow::from(password),
+        }
+    }
+
+    /// Constructs a password from the given string in a `const` context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid password. In a `const` or `static` item, this results
+    /// in a compilation error instead.
+    pub(crate) const fn from_static(value: &'static str) -> Password<'static> {
+        match check_user_info(value.as_bytes(), false) {
+            Ok(normalized) => Password {
+                normalized,
+                password: Cow::Borrowed(value),
+            },
+            Err(UserInfoError::Password(PasswordError::InvalidPercentEncoding)) => {
+                panic!("invalid password percent encoding")
+            }
+            Err(_) => panic!("invalid password character"),
+        }
+    }
+
+    /// Converts the [`Password`] into an owned copy.
+    ///
+    /// If you construct the authority from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the password will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Password<'static> {
+        Password {
+            normalized: self.normalized,
+            password: Cow::from(self.password.into_owned()),
+        }
+    }
+
+    /// Returns whether the password is normalized.
+    ///
+    /// A normalized password will have no bytes that are in the unreserved character set
+    /// percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// This function runs in constant-time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Password;
+    ///
+    /// let password = Password::try_from("password").unwrap();
+    /// assert!(password.is_normalized());
+    ///
+    /// let mut password = Password::try_from("%ff%ff").unwrap();
+    /// assert!(!password.is_normalized());
+    /// password.normalize();
+    /// assert!(password.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Normalizes the password such that it will have no bytes that are in the unreserved character
+    /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// If the password is already normalized, the function will return immediately. Otherwise, if
+    /// the password is not owned, this function will perform an allocation to clone it. The
+    /// normalization itself though, is done in-place with no extra memory allocations required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Password;
+    ///
+    /// let mut password = Password::try_from("password").unwrap();
+    /// password.normalize();
+    /// assert_eq!(password, "password");
+    ///
+    /// let mut password = Password::try_from("%ff%41").unwrap();
+    /// assert_eq!(password, "%ff%41");
+    /// password.normalize();
+    /// assert_eq!(password, "%FFA");
+    /// ```
+    pub fn normalize(&mut self) {
+        if !self.normalized {
+            // Unsafe: Passwords must be valid ASCII-US, so this is safe.
+            unsafe { normalize_string(self.password.to_mut(), true) };
+            self.normalized = true;
+        }
+    }
+}
+
+impl AsRef<[u8]> for Password<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.password.as_bytes()
+    }
+}
+
+impl AsRef<str> for Password<'_> {
+    fn as_ref(&self) -> &str {
+        &self.password
+    }
+}
+
+impl fmt::Debug for Password<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Password")
+            .field("normalized", &self.normalized)
+            .field("password", &REDACTED_PASSWORD)
+            .finish()
+    }
+}
+
+impl Deref for Password<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.password
+    }
+}
+
+impl Display for Password<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(&self.password)
+    }
+}
+
+impl Eq for Password<'_> {}
+
+impl<'password> From<Password<'password>> for String {
+    fn from(value: Password<'password>) -> String {
+        value.to_string()
+    }
+}
+
+impl Hash for Password<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        percent_encoded_hash(self.password.as_bytes(), state, true);
+    }
+}
+
+impl Ord for Password<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(self.password.as_bytes(), other.password.as_bytes(), true)
+    }
+}
+
+impl PartialEq for Password<'_> {
+    fn eq(&self, other: &Password) -> bool {
+        *self == *other.as_bytes()
+    }
+}
+
+impl PartialEq<[u8]> for Password<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        percent_encoded_equality(self.password.as_bytes(), other, true)
+    }
+}
+
+impl<'password> PartialEq<Password<'password>> for [u8] {
+    fn eq(&self, other: &Password<'password>) -> bool {
+        other == self
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for Password<'_> {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self == *other
+    }
+}
+
+impl<'password> PartialEq<Password<'password>> for &[u8] {
+    fn eq(&self, other: &Password<'password>) -> bool {
+        other == *self
+    }
+}
+
+impl PartialEq<str> for Password<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'password> PartialEq<Password<'password>> for str {
+    fn eq(&self, other: &Password<'password>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Password<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'password> PartialEq<Password<'password>> for &str {
+    fn eq(&self, other: &Password<'password>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl PartialOrd for Password<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'password> TryFrom<&'password [u8]> for Password<'password> {
+    type Error = PasswordError;
+
+    fn try_from(value: &'password [u8]) -> Result<Self, Self::Error> {
+        let normalized = check_user_info(value, false)?;
+
+        // Unsafe: The function above [`check_user_info`] ensures this is valid ASCII-US.
+        Ok(Password {
+            normalized,
+            password: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
+        })
+    }
+}
+
+impl<'password> TryFrom<&'password str> for Password<'password> {
+    type Error = PasswordError;
+
+    fn try_from(value: &'password str) -> Result<Self, Self::Error> {
+        Password::try_from(value.as_bytes())
+    }
+}
+
+/// A host that is a registered name (i.e. not an IP literal).
+///
+/// The registered name is case-insensitive meaning that `"example.com"` and `"ExAmPlE.CoM"` refer
+/// to the same registered name. Furthermore, percent-encoding plays no role in equality checking
+/// for characters in the unreserved character set meaning that `"example.com"` and
+/// `"ex%61mple.com"` are identical. Both of these attributes are reflected in the equality and hash
+/// functions.
+///
+/// However, be aware that just because percent-encoding plays no role in equality checking does not
+/// mean that the host is normalized. If the registered name needs to be normalized, use the
+/// [`RegisteredName::normalize`] function.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisteredName<'name> {
+    /// Whether the registered name is normalized.
+    normalized: bool,
+
+    /// The internal registered name source that is either owned or borrowed.
+    registered_name: Cow<'name, str>,
+}
+
+impl RegisteredName<'_> {
+    /// Returns a new registered name which is identical but has a lifetime tied to this registered
+    /// name.
+    pub fn as_borrowed(&self) -> RegisteredName<'_> {
+        use self::Cow::*;
+
+        let name = match &self.registered_name {
+            Borrowed(borrowed) => *borrowed,
+            Owned(owned) => owned.as_str(),
+        };
+
+        RegisteredName {
+            normalized: self.normalized,
+            registered_name: Cow::Borrowed(name),
+        }
+    }
+
+    /// Returns a `str` representation of the registered name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RegisteredName;
+    ///
+    /// let name = RegisteredName::try_from("example.com").unwrap();
+    /// assert_eq!(name.as_str(), "example.com");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.registered_name
+    }
+
+    /// Constructs a registered name from arbitrary unencoded text.
+    ///
+    /// Any character that is not allowed in a registered name, as well as `'%'` itself, is
+    /// percent-encoded. Uppercase characters are kept as is, so the resulting registered name may
+    /// still need to be normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::RegisteredName;
+    ///
+    /// let name = RegisteredName::encode("Bücher.de");
+    /// assert_eq!(name.as_str(), "B%C3%BCcher.de");
+    /// assert!(!name.is_normalized());
+    /// ```
+    pub fn encode(value: &str) -> RegisteredName<'static> {
+        let mut registered_name = String::with_capacity(value.len());
+        percent_encode(
+            value.as_bytes(),
+            &REGISTERED_NAME_ENCODE_CHAR_MAP,
+            &mut registered_name,
+        );
+        let (_, normalized) = check_ipv4_or_registered_name(registered_name.as_bytes());
+
+        RegisteredName {
+            normalized,
+            registered_name: Cow::from(registered_name),
+        }
+    }
+
+    /// Constructs a registered name from the given string in a `const` context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid registered name. In a `const` or `static` item, this
+    /// results in a compilation error instead.
+    pub(crate) const fn from_static(value: &'static str) -> RegisteredName<'static> {
+        match check_ipv4_or_registered_name(value.as_bytes()) {
+            (true, normalized) => RegisteredName {
+                normalized,
+                registered_name: Cow::Borrowed(value),
+            },
+            (false, _) => panic!("invalid registered name character"),
+        }
+    }
+
+    /// Converts the [`RegisteredName`] into an owned copy.
+    ///
+    /// If you construct the registered name from a source with a non-static lifetime, you may run
+    /// into lifetime problems due to the way the struct is designed. Calling this function will
+    /// ensure that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the registered name will just copy the
+    /// references, and thus the lifetime will remain the same.
+    pub fn into_owned(self) -> RegisteredName<'static> {
+        RegisteredName {
+            normalized: self.normalized,
+            registered_name: Cow::from(self.registered_name.into_owned()),
+        }
+    }
+
+    /// Returns whether the registered name is normalized.
+    ///
+    /// Registered names are considered normalized if all characters are lowercase, no bytes that
+    /// are in the unreserved character set are percent-encoded, and all alphabetical characters in
+    /// percent-encodings are uppercase.
+    ///
+    /// This function runs in constant-time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RegisteredName;
+    ///
+    /// let name = RegisteredName::try_from("example.com").unwrap();
+    /// assert!(name.is_normalized());
+    ///
+    /// let mut name = RegisteredName::try_from("EXAMPLE.COM").unwrap();
+    /// assert!(!name.is_normalized());
+    /// name.normalize();
+    /// assert!(name.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Normalizes the registered name such that all characters are lowercase, no bytes that are in
+    /// the unreserved character set are percent-encoded, and all alphabetical characters in
+    /// percent-encodings are uppercase.
+    ///
+    /// If the registered name is already normalized, the function will return immediately.
+    /// Otherwise, if the registered name is not owned, this function will perform an allocation to
+    /// clone it. The normalization itself though, is done in-place with no extra memory allocations
+    /// required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RegisteredName;
+    ///
+    /// let mut name = RegisteredName::try_from("example.com").unwrap();
+    /// name.normalize();
+    /// assert_eq!(name.to_string(), "example.com");
+    ///
+    /// let mut name = RegisteredName::try_from("%ff%41").unwrap();
+    /// assert_eq!(name.to_string(), "%ff%41");
+    /// name.normalize();
+    /// assert_eq!(name.to_string(), "%FFA");
+    /// ```
+    pub fn normalize(&mut self) {
+        if !self.normalized {
+            // Unsafe: Registered names must be valid ASCII-US, so this is safe.
+            unsafe { normalize_string(self.registered_name.to_mut(), false) };
+            self.normalized = true;
+        }
+    }
+
+    /// Validates that the registered name is a DNS hostname as defined in
+    /// [[RFC1123, Section 2.1](https://tools.ietf.org/html/rfc1123#section-2.1)].
+    ///
+    /// The registered name is percent-decoded and must then consist of labels separated by `'.'`,
+    /// where each label is between 1 and 63 characters long, contains only ASCII letters, digits
+    /// and hyphens, and does not start or end with a hyphen. The whole name must not exceed 253
+    /// characters, excluding an optional trailing `'.'`.
+    ///
+    /// This is much stricter than the registered name syntax of RFC3986 and is never checked while
+    /// parsing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{HostnameError, RegisteredName};
+    ///
+    /// let name = RegisteredName::try_from("www.example.com.").unwrap();
+    /// assert_eq!(name.validate_hostname(), Ok(()));
+    ///
+    /// let name = RegisteredName::try_from("www..example.com").unwrap();
+    /// assert_eq!(name.validate_hostname(), Err(HostnameError::EmptyLabel));
+    ///
+    /// let name = RegisteredName::try_from("my_host.example.com").unwrap();
+    /// assert_eq!(name.validate_hostname(), Err(HostnameError::InvalidCharacter));
+    ///
+    /// let name = RegisteredName::try_from("-example.com").unwrap();
+    /// assert_eq!(name.validate_hostname(), Err(HostnameError::LabelStartsWithHyphen));
+    /// ```
+    pub fn validate_hostname(&self) -> Result<(), HostnameError> {
+        let name = percent_decode(&self.registered_name);
+        let name = name.strip_suffix('.').unwrap_or(&name);
+
+        if name.len() > 253 {
+            return Err(HostnameError::TooLong);
+        }
+
+        for label in name.split('.') {
+            if label.is_empty() {
+                return Err(HostnameError::EmptyLabel);
+            }
+
+            if label.len() > 63 {
+                return Err(HostnameError::LabelTooLong);
+            }
+
+            if !label
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+            {
+                return Err(HostnameError::InvalidCharacter);
+            }
+
+            if label.starts_with('-') {
+                return Err(HostnameError::LabelStartsWithHyphen);
+            }
+
+            if label.ends_with('-') {
+                return Err(HostnameError::LabelEndsWithHyphen);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl AsRef<[u8]> for RegisteredName<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.registered_name.as_bytes()
+    }
+}
+
+impl AsRef<str> for RegisteredName<'_> {
+    fn as_ref(&self) -> &str {
+        &self.registered_name
+    }
+}
+
+impl Deref for RegisteredName<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.registered_name
+    }
+}
+
+impl Display for RegisteredName<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(&self.registered_name)
+    }
+}
+
+impl Eq for RegisteredName<'_> {}
+
+impl<'name> From<RegisteredName<'name>> for String {
+    fn from(value: RegisteredName<'name>) -> String {
+        value.to_string()
+    }
+}
+
+impl Hash for RegisteredName<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        percent_encoded_hash(self.registered_name.as_bytes(), state, false);
+    }
+}
+
+impl Ord for RegisteredName<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(
+            self.registered_name.as_bytes(),
+            other.registered_name.as_bytes(),
+            false,
+        )
+    }
+}
+
+impl PartialEq for RegisteredName<'_> {
+    fn eq(&self, other: &RegisteredName) -> bool {
+        *self == *other.as_bytes()
+    }
+}
+
+impl PartialEq<[u8]> for RegisteredName<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        percent_encoded_equality(self.registered_name.as_bytes(), other, false)
+    }
+}
+
+impl<'name> PartialEq<RegisteredName<'name>> for [u8] {
+    fn eq(&self, other: &RegisteredName<'name>) -> bool {
+        other == self
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for RegisteredName<'_> {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self == *other
+    }
+}
+
+impl<'name> PartialEq<RegisteredName<'name>> for &[u8] {
+    fn eq(&self, other: &RegisteredName<'name>) -> bool {
+        other == *self
+    }
+}
+
+impl PartialEq<str> for RegisteredName<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'name> PartialEq<RegisteredName<'name>> for str {
+    fn eq(&self, other: &RegisteredName<'name>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a str> for RegisteredName<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'name> PartialEq<RegisteredName<'name>> for &str {
+    fn eq(&self, other: &RegisteredName<'name>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl PartialOrd for RegisteredName<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'name> TryFrom<&'name [u8]> for RegisteredName<'name> {
+    type Error = RegisteredNameError;
+
+    fn try_from(value: &'name [u8]) -> Result<Self, Self::Error> {
+        match Host::try_from(value) {
+            Ok(Host::RegisteredName(name)) => Ok(name),
+            _ => Err(RegisteredNameError),
+        }
+    }
+}
+
+impl<'name> TryFrom<&'name str> for RegisteredName<'name> {
+    type Error = RegisteredNameError;
+
+    fn try_from(value: &'name str) -> Result<Self, Self::Error> {
+        RegisteredName::try_from(value.as_bytes())
+    }
+}
+
+/// The username component of the authority as defined in
+/// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+///
+/// The username is case-sensitive. Furthermore, percent-encoding plays no role in equality checking
+/// for characters in the unreserved character set meaning that `"username"` and `"usern%61me"` are
+/// identical. Both of these attributes are reflected in the equality and hash functions.
+///
+/// Be aware that just because percent-encoding plays no role in equality checking does not
+/// mean that the username is normalized. If the username needs to be normalized, use the
+/// [`Username::normalize`] function.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Username<'username> {
+    /// Whether the username is normalized.
+    normalized: bool,
+
+    /// The internal username source that is either owned or borrowed.
+    username: Cow<'username, str>,
+}
+
+impl Username<'_> {
+    /// Returns a new username which is identical but has a lifetime tied to this username.
+    pub fn as_borrowed(&self) -> Username<'_> {
+        use self::Cow::*;
+
+        let username = match &self.username {
+            Borrowed(borrowed) => *borrowed,
+            Owned(owned) => owned.as_str(),
+        };
+
+        Username {
+            normalized: self.normalized,
+            username: Cow::Borrowed(username),
+        }
+    }
+
+    /// Returns the percent-decoded username.
+    ///
+    /// If the username contains no percent-encodings, the returned value borrows from the username.
+    /// Decoded bytes that are not valid UTF-8 are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Username;
+    ///
+    /// let username = Username::try_from("user%40example.com").unwrap();
+    /// assert_eq!(username.as_decoded(), "user@example.com");
+    /// ```
+    pub fn as_decoded(&self) -> Cow<'_, str> {
+        percent_decode(&self.username)
+    }
+
+    /// Returns a `str` representation of the username.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Username;
+    ///
+    /// let username = Username::try_from("username").unwrap();
+    /// assert_eq!(username.as_str(), "username");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.username
+    }
+
+    /// Constructs a username from arbitrary unencoded text.
+    ///
+    /// Any character that is not allowed in a username, as well as `'%'` and `':'`, is
+    /// percent-encoded, so the returned username always decodes back to the given text. The
+    /// resulting username is always normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Username;
+    ///
+    /// let username = Username::encode("user:name@example.com");
+    /// assert_eq!(username, "user%3Aname%40example.com");
+    /// assert_eq!(username.as_decoded(), "user:name@example.com");
+    /// ```
+    pub fn encode(value: &str) -> Username<'static> {
+        let mut username = String::with_capacity(value.len());
+        percent_encode(value.as_bytes(), &USERNAME_ENCODE_CHAR_MAP, &mut username);
+
+        Username {
+            normalized: true,
+            username: Cow::from(username),
+        }
+    }
+
+    /// Constructs a username from the given string in a `const` context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid username. In a `const` or `static` item, this results
+    /// in a compilation error instead.
+    pub(crate) const fn from_static(value: &'static str) -> Username<'static> {
+        match check_user_info(value.as_bytes(), true) {
+            Ok(normalized) => Username {
+                normalized,
+                username: Cow::Borrowed(value),
+            },
+            Err(UserInfoError::Username(UsernameError::ContainsColon)) => {
+                panic!("username contains colon")
+            }
+            Err(UserInfoError::Username(UsernameError::InvalidPercentEncoding)) => {
+                panic!("invalid username percent encoding")
+            }
+            Err(_) => panic!("invalid username character"),
+        }
+    }
+
+    /// Converts the [`Username`] into an owned copy.
+    ///
+    /// If you construct the username from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the username will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Username<'static> {
+        Username {
+            normalized: self.normalized,
+            username: Cow::from(self.username.into_owned()),
+        }
+    }
+
+    /// Returns whether the username is normalized.
+    ///
+    /// A normalized username will have no bytes that are in the unreserved character set
+    /// percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// This function runs in constant-time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Username;
+    ///
+    /// let username = Username::try_from("username").unwrap();
+    /// assert!(username.is_normalized());
+    ///
+    /// let mut username = Username::try_from("%ff%ff").unwrap();
+    /// assert!(!username.is_normalized());
+    /// username.normalize();
+    /// assert!(username.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Normalizes the username such that it will have no bytes that are in the unreserved character
+    /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// If the username is already normalized, the function will return immediately. Otherwise, if
+    /// the username is not owned, this function will perform an allocation to clone it. The
+    /// normalization itself though, is done in-place with no extra memory allocations required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Username;
+    ///
+    /// let mut username = Username::try_from("username").unwrap();
+    /// username.normalize();
+    /// assert_eq!(username, "username");
+    ///
+    /// let mut username = Username::try_from("%ff%41").unwrap();
+    /// assert_eq!(username, "%ff%41");
+    /// username.normalize();
+    /// assert_eq!(username, "%FFA");
+    /// ```
+    pub fn normalize(&mut self) {
+        if !self.normalized {
+            // Unsafe: Usernames must be valid ASCII-US, so this is safe.
+            unsafe { normalize_string(self.username.to_mut(), true) };
+            self.normalized = true;
+        }
+    }
+}
+
+impl AsRef<[u8]> for Username<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.username.as_bytes()
+    }
+}
+
+impl AsRef<str> for Username<'_> {
+    fn as_ref(&self) -> &str {
+        &self.username
+    }
+}
+
+impl Deref for Username<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.username
+    }
+}
+
+impl Display for Username<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(&self.username)
+    }
+}
+
+impl<'username> Eq for Username<'username> {}
+
+impl<'username> From<Username<'username>> for String {
+    fn from(value: Username<'username>) -> String {
+        value.to_string()
+    }
+}
+
+impl Hash for Username<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        percent_encoded_hash(self.username.as_bytes(), state, true);
+    }
+}
+
+impl Ord for Username<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(self.username.as_bytes(), other.username.as_bytes(), true)
+    }
+}
+
+impl PartialEq for Username<'_> {
+    fn eq(&self, other: &Username) -> bool {
+        *self == *other.as_bytes()
+    }
+}
+
+impl PartialEq<[u8]> for Username<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        percent_encoded_equality(self.username.as_bytes(), other, true)
+    }
+}
+
+impl<'username> PartialEq<Username<'username>> for [u8] {
+    fn eq(&self, other: &Username<'username>) -> bool {
+        other == self
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for Username<'_> {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self == *other
+    }
+}
+
+impl<'username> PartialEq<Username<'username>> for &[u8] {
+    fn eq(&self, other: &Username<'username>) -> bool {
+        other == *self
+    }
+}
+
+impl PartialEq<str> for Username<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'username> PartialEq<Username<'username>> for str {
+    fn eq(&self, other: &Username<'username>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Username<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'username> PartialEq<Username<'username>> for &str {
+    fn eq(&self, other: &Username<'username>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl PartialOrd for Username<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'username> TryFrom<&'username [u8]> for Username<'username> {
+    type Error = UsernameError;
+
+    fn try_from(value: &'username [u8]) -> Result<Self, Self::Error> {
+        let normalized = check_user_info(value, true)?;
+
+        // Unsafe: The function above [`check_user_info`] ensure this is valid ASCII-US.
+        Ok(Username {
+            normalized,
+            username: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
+        })
+    }
+}
+
+impl<'username> TryFrom<&'username str> for Username<'username> {
+    type Error = UsernameError;
+
+    fn try_from(value: &'username str) -> Result<Self, Self::Error> {
+        Username::try_from(value.as_bytes())
+    }
+}
+
+/// An error representing an invalid authority.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum AuthorityError {
+    /// The host component of the authority was invalid.
+    Host(HostError),
+
+    /// A host was not specified when building the authority.
+    MissingHost,
+
+    /// The password component of the authority was invalid.
+    Password(PasswordError),
+
+    /// The port component of the authority was invalid.
+    Port(PortError),
+
+    /// The username component of the authority was invalid.
+    Username(UsernameError),
+}
+
+impl Display for AuthorityError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::AuthorityError::*;
+
+        match self {
+            Host(error) => error.fmt(formatter),
+            MissingHost => write!(formatter, "authority missing host"),
+            Password(error) => error.fmt(formatter),
+            Port(error) => error.fmt(formatter),
+            Username(error) => error.fmt(formatter),
+        }
+    }
+}
+
+impl Error for AuthorityError {}
+
+impl From<Infallible> for AuthorityError {
+    fn from(_: Infallible) -> Self {
+        AuthorityError::Host(HostError::InvalidIPv4OrRegisteredNameCharacter)
+    }
+}
+
+impl From<HostError> for AuthorityError {
+    fn from(value: HostError) -> Self {
+        AuthorityError::Host(value)
+    }
+}
+
+impl From<PasswordError> for AuthorityError {
+    fn from(value: PasswordError) -> Self {
+        AuthorityError::Password(value)
+    }
+}
+
+impl From<PortError> for AuthorityError {
+    fn from(value: PortError) -> Self {
+        AuthorityError::Port(value)
+    }
+}
+
+impl From<UserInfoError> for AuthorityError {
+    fn from(value: UserInfoError) -> Self {
+        use self::AuthorityError::*;
+
+        match value {
+            UserInfoError::Password(error) => Password(error),
+            UserInfoError::Username(error) => Username(error),
+        }
+    }
+}
+
+impl From<UsernameError> for AuthorityError {
+    fn from(value: UsernameError) -> Self {
+        AuthorityError::Username(value)
+    }
+}
+
+/// Options for the malformations tolerated by [`Authority::try_from_lenient`].
+///
+/// The default options tolerate nothing, so parsing is as strict as with [`Authority::try_from`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct AuthorityParseOptions {
+    /// Whether the user information may contain `'@'` characters.
+    ///
+    /// The last `'@'` separates the user information from the host, and any earlier ones are
+    /// percent-encoded as part of the user information.
+    pub allow_multiple_at_signs: bool,
+
+    /// Whether stray whitespace should be removed.
+    ///
+    /// Leading and trailing spaces and control characters are trimmed, and tabs and newlines are
+    /// removed wherever they occur.
+    pub trim_whitespace: bool,
+}
+
+/// An error representing an invalid host.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum HostError {
+    /// The address mechanism of a future IP literal is not supported.
+    ///
+    /// This is no longer returned when parsing, since future IP literals are parsed into
+    /// [`Host::IPvFuture`]. It is only returned by [`Authority::to_socket_addrs`] for such hosts,
+    /// which cannot be resolved.
+    AddressMechanismNotSupported,
+
+    /// An invalid character for an IPv4 address or registered name was used. Due to the ambiguity
+    /// of the grammar, it is not possible to say which. It is also possible that all the characters
+    /// were valid, but there was an invalid percent encoding (e.g. `"%ZZ"`).
+    InvalidIPv4OrRegisteredNameCharacter,
+
+    /// The syntax for an IPv6 literal was used (i.e. `"[...]"`), but it contained an invalid IPv6
+    /// character.
+    InvalidIPv6Character,
+
+    /// The syntax for an IPv6 literal was used (i.e. `"[...]"`) and all of the characters were
+    /// valid IPv6 characters. However, the format of the literal was invalid.
+    InvalidIPv6Format,
+
+    /// The syntax for a future IP literal was used (i.e. `"[v*...]"` where `"*"` is a hexadecimal
+    /// digit), but it contained an invalid character.
+    InvalidIPvFutureCharacter,
+
+    /// The syntax for a future IP literal was used (i.e. `"[v...]"`), but it did not consist of a
+    /// non-empty hexadecimal version followed by a `'.'` and a non-empty address.
+    InvalidIPvFutureFormat,
+
+    /// The IPv6 literal contained a `'%'`, but it was not followed by a valid zone identifier
+    /// (i.e. `"%25"` followed by at least one unreserved or percent-encoded character).
+    InvalidZoneIdentifier,
+
+    /// The host was converted to a socket address, but its IPv6 zone identifier is not a numeric
+    /// scope ID. Interface names are not resolved.
+    NonNumericZoneIdentifier,
+}
+
+impl Display for HostError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::HostError::*;
+
+        match self {
+            AddressMechanismNotSupported => {
+                write!(formatter, "host address mechanism not supported")
+            }
+            InvalidIPv4OrRegisteredNameCharacter => {
+                write!(formatter, "invalid host IPv4 or registered name character")
+            }
+            InvalidIPv6Character => write!(formatter, "invalid host IPv6 character"),
+            InvalidIPv6Format => write!(formatter, "invalid host IPv6 format"),
+            InvalidIPvFutureCharacter => write!(formatter, "invalid host IPvFuture character"),
+            InvalidIPvFutureFormat => write!(formatter, "invalid host IPvFuture format"),
+            InvalidZoneIdentifier => write!(formatter, "invalid host IPv6 zone identifier"),
+            NonNumericZoneIdentifier => {
+                write!(
+                    formatter,
+                    "host IPv6 zone identifier is not a numeric scope ID"
+                )
+            }
+        }
+    }
+}
+
+impl Error for HostError {}
+
+impl From<Infallible> for HostError {
+    fn from(_: Infallible) -> Self {
+        HostError::InvalidIPv4OrRegisteredNameCharacter
+    }
+}
+
+/// An error representing a registered name that is not a valid DNS hostname.
+///
+/// This error is not possible from parsing. It can only be returned from
+/// [`RegisteredName::validate_hostname`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum HostnameError {
+    /// The hostname was empty or contained two consecutive `'.'` characters.
+    EmptyLabel,
+
+    /// A label contained a character other than an ASCII letter, digit or hyphen.
+    InvalidCharacter,
+
+    /// A label ended with a hyphen.
+    LabelEndsWithHyphen,
+
+    /// A label started with a hyphen.
+    LabelStartsWithHyphen,
+
+    /// A label was longer than 63 characters.
+    LabelTooLong,
+
+    /// The hostname was longer than 253 characters.
+    TooLong,
+}
+
+impl Display for HostnameError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::HostnameError::*;
+
+        match self {
+            EmptyLabel => write!(formatter, "empty hostname label"),
+            InvalidCharacter => write!(formatter, "invalid hostname character"),
+            LabelEndsWithHyphen => write!(formatter, "hostname label ends with hyphen"),
+            LabelStartsWithHyphen => write!(formatter, "hostname label starts with hyphen"),
+            LabelTooLong => write!(formatter, "hostname label too long"),
+            TooLong => write!(formatter, "hostname too long"),
+        }
+    }
+}
+
+impl Error for HostnameError {}
+
+/// An error representing an invalid password component.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum PasswordError {
+    /// The password contained an invalid character.
+    InvalidCharacter,
+
+    /// The password contained an invalid percent encoding (e.g. `"%ZZ"`).
+    InvalidPercentEncoding,
+}
+
+impl Display for PasswordError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::PasswordError::*;
+
+        match self {
+            InvalidCharacter => write!(formatter, "invalid password character"),
+            InvalidPercentEncoding => write!(formatter, "invalid password percent encoding"),
+        }
+    }
+}
+
+impl Error for PasswordError {}
+
+impl From<Infallible> for PasswordError {
+    fn from(_: Infallible) -> Self {
+        PasswordError::InvalidCharacter
+    }
+}
+
+impl From<UserInfoError> for PasswordError {
+    fn from(value: UserInfoError) -> Self {
+        match value {
+            UserInfoError::Password(error) => error,
+            _ => panic!("unexpected user info error"),
+        }
+    }
+}
+
+/// An error representing an invalid port.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum PortError {
+    /// An invalid character was used in the port. Only decimal digits are allowed.
+    InvalidCharacter,
+
+    /// The port was a valid number, but it was too large to fit in a `u16`.
+    Overflow,
+}
+
+impl Display for PortError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::PortError::*;
+
+        match self {
+            InvalidCharacter => write!(formatter, "invalid port character"),
+            Overflow => write!(formatter, "port overflow"),
+        }
+    }
+}
+
+impl Error for PortError {}
+
+impl From<Infallible> for PortError {
+    fn from(_: Infallible) -> Self {
+        PortError::InvalidCharacter
+    }
+}
+
+/// An error representing an invalid registered name.
+///
+/// This implies that the registered name contained an invalid host character or had an invalid
+/// percent encoding. This error is not possible from parsing an authority. It can only be returned
+/// from directly parsing a registered name.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RegisteredNameError;
+
+impl Display for RegisteredNameError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "invalid registered name")
+    }
+}
+
+impl Error for RegisteredNameError {}
+
+/// An error representing an invalid user information component.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+enum UserInfoError {
+    /// The password component of the user information was invalid.
+    Password(PasswordError),
+
+    /// The username component of the user information was invalid.
+    Username(UsernameError),
+}
+
+impl Display for UserInfoError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::UserInfoError::*;
+
+        match self {
+            Password(error) => error.fmt(formatter),
+            Username(error) => error.fmt(formatter),
+        }
+    }
+}
+
+impl Error for UserInfoError {}
+
+impl From<Infallible> for UserInfoError {
+    fn from(_: Infallible) -> Self {
+        UserInfoError::Username(UsernameError::InvalidCharacter)
+    }
+}
+
+impl From<PasswordError> for UserInfoError {
+    fn from(value: PasswordError) -> Self {
+        UserInfoError::Password(value)
+    }
+}
+
+impl From<UsernameError> for UserInfoError {
+    fn from(value: UsernameError) -> Self {
+        UserInfoError::Username(value)
+    }
+}
+
+/// An error representing an invalid username component.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum UsernameError {
+    /// The username contained a `':'` which is only to be used as a delimiter between the username
+    /// and password. This variant can only happen when trying to directly parse a username from a
+    /// byte source.
+    ContainsColon,
+
+    /// The username contained an invalid character.
+    InvalidCharacter,
+
+    /// The username contained an invalid percent encoding (e.g. `"%ZZ"`).
+    InvalidPercentEncoding,
+}
+
+impl Display for UsernameError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::UsernameError::*;
+
+        match self {
+            ContainsColon => write!(formatter, "username contains a colon character"),
+            InvalidCharacter => write!(formatter, "invalid username character"),
+            InvalidPercentEncoding => write!(formatter, "invalid username percent encoding"),
+        }
+    }
+}
+
+impl Error for UsernameError {}
+
+impl From<Infallible> for UsernameError {
+    fn from(_: Infallible) -> Self {
+        UsernameError::InvalidCharacter
+    }
+}
+
+impl From<UserInfoError> for UsernameError {
+    fn from(value: UserInfoError) -> Self {
+        match value {
+            UserInfoError::Username(error) => error,
+            _ => panic!("unexpected user info error"),
+        }
+    }
+}
+
+/// Returns true if the byte string contains only valid IPv4 or registered name characters. This
+/// also ensures that percent encodings are valid.
+const fn check_ipv4_or_registered_name(value: &[u8]) -> (bool, bool) {
+    let mut index = 0;
+    let mut normalized = true;
+
+    while index < value.len() {
+        match IPV4_AND_REGISTERED_NAME_CHAR_MAP[value[index] as usize] {
+            0 => return (false, false),
+            b'%' => match get_percent_encoded_value_at(value, index) {
+                Ok((hex_value, uppercase)) => {
+                    if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
+                        normalized = false;
+                    }
+
+                    index += 2;
+                }
+                _ => return (false, false),
+            },
+            b'A'..=b'Z' => normalized = false,
+            _ => (),
+        }
+
+        index += 1;
+    }
+
+    (true, normalized)
+}
+
+/// Returns true if the byte string contains only valid IPv6 characters.
+fn check_ipv6(value: &[u8]) -> bool {
+    for &byte in value {
+        if !byte.is_ascii_hexdigit() && byte != b':' && byte != b'.' {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Returns true if the byte string contains only valid future IP literal characters. This also
+/// ensures that percent encodings are valid.
+const fn check_ipvfuture(value: &[u8]) -> bool {
+    let mut index = 0;
+
+    while index < value.len() {
+        if IPV_FUTURE_CHAR_MAP[value[index] as usize] == 0 {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+/// Returns true if the byte string is a valid zone identifier, i.e. it is not empty and consists of
+/// only unreserved characters and valid percent encodings.
+fn check_zone_identifier(value: &[u8]) -> bool {
+    if value.is_empty() {
+        return false;
+    }
+
+    let mut bytes = value.iter();
+
+    while let Some(&byte) = bytes.next() {
+        if byte == b'%' {
+            if get_percent_encoded_value(bytes.next().cloned(), bytes.next().cloned()).is_err() {
+                return false;
+            }
+        } else if UNRESERVED_CHAR_MAP[byte as usize] == 0 {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Returns true if the given valid zone identifier is normalized, i.e. it contains no
+/// percent-encoded unreserved characters and all percent-encodings are uppercase.
+fn check_zone_identifier_normalized(value: &[u8]) -> bool {
+    let mut bytes = value.iter();
+
+    while let Some(&byte) = bytes.next() {
+        if byte == b'%' {
+            match get_percent_encoded_value(bytes.next().cloned(), bytes.next().cloned()) {
+                Ok((hex_value, true)) if UNRESERVED_CHAR_MAP[hex_value as usize] == 0 => {}
+                _ => return false,
+            }
+        }
+    }
+
+    true
+}
+
+/// Checks if the user information component contains valid characters and percent encodings. If so,
+/// it will return an `Option<usize>` indicating the separator index for the username and password.
+const fn check_user_info(value: &[u8], is_username: bool) -> Result<bool, UserInfoError> {
+    let mut index = 0;
+    let mut normalized = true;
+
+    while index < value.len() {
+        match USER_INFO_CHAR_MAP[value[index] as usize] {
+            0 => {
+                return if is_username {
+                    Err(UserInfoError::Username(UsernameError::InvalidCharacter))
+                } else {
+                    Err(UserInfoError::Password(PasswordError::InvalidCharacter))
+                };
+            }
+            b'%' => match get_percent_encoded_value_at(value, index) {
+                Ok((hex_value, uppercase)) => {
+                    if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
+                        normalized = false;
+                    }
+
+                    index += 2;
+                }
+                Err(_) => {
+                    return if is_username {
+                        Err(UserInfoError::Username(
+                            UsernameError::InvalidPercentEncoding,
+                        ))
+                    } else {
+                        Err(UserInfoError::Password(
+                            PasswordError::InvalidPercentEncoding,
+                        ))
+                    };
+                }
+            },
+            b':' if is_username => {
+                return Err(UserInfoError::Username(UsernameError::ContainsColon))
+            }
+            _ => (),
+        }
+
+        index += 1;
+    }
+
+    Ok(normalized)
+}
+
+/// Returns whether the IPv4 address is not within any of the special-purpose ranges that are not
+/// globally reachable.
+fn is_global_ipv4(address: Ipv4Addr) -> bool {
+    let [first, second, third, fourth] = address.octets();
+
+    // The Port Control Protocol and TURN anycast addresses are the only globally reachable
+    // addresses of the IETF protocol assignments range.
+    if first == 192 && second == 0 && third == 0 && (fourth == 9 || fourth == 10) {
+        return true;
+    }
+
+    !(first == 0
+        || address.is_private()
+        || (first == 100 && second & 0xc0 == 64)
+        || address.is_loopback()
+        || address.is_link_local()
+        || (first == 192 && second == 0 && third == 0)
+        || address.is_documentation()
+        || (first == 198 && second & 0xfe == 18)
+        || first >= 240)
+}
+
+/// Returns whether the IPv6 address is not within any of the special-purpose ranges that are not
+/// globally reachable.
+fn is_global_ipv6(address: Ipv6Addr) -> bool {
+    let segments = address.segments();
+
+    !(address.is_unspecified()
+        || address.is_loopback()
+        || (segments[0] == 0x0100 && segments[1..4] == [0, 0, 0])
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8)
+        || segments[0] & 0xfe00 == 0xfc00
+        || segments[0] & 0xffc0 == 0xfe80)
+}
+
+/// Returns whether the special-use name is a link-local name.
+fn is_link_local_name(name: &str) -> bool {
+    name == "local" || name.ends_with(".local")
+}
+
+/// Returns whether the special-use name is a loopback name.
+fn is_loopback_name(name: &str) -> bool {
+    name == "localhost" || name.ends_with(".localhost")
+}
+
+/// Splits the registered name into labels normalized for matching against the Public Suffix List,
+/// ignoring a trailing `'.'`.
+#[cfg(feature = "psl")]
+fn psl_labels(name: &str) -> Vec<String> {
+    let name = name.strip_suffix('.').unwrap_or(name);
+
+    if name.is_empty() {
+        return Vec::new();
+    }
+
+    name.split('.').map(psl::normalize_label).collect()
+}
+
+/// Returns the registered name in the form it is compared against special-use names, that is
+/// percent-decoded, lowercased, and without a trailing `'.'`.
+fn special_use_name(name: &RegisteredName<'_>) -> String {
+    let mut name = percent_decode(name.as_str()).to_ascii_lowercase();
+
+    if name.ends_with('.') {
+        name.pop();
+    }
+
+    name
+}
+
+/// Converts IPv4-mapped IPv6 addresses to the IPv4 address they map to.
+fn unmap_ip_addr(address: IpAddr) -> IpAddr {
+    match address {
+        IpAddr::V6(ipv6) => match ipv6.segments() {
+            [0, 0, 0, 0, 0, 0xffff, high, low] => {
+                IpAddr::V4(Ipv4Addr::from((u32::from(high) << 16) | u32::from(low)))
+            }
+            _ => address,
+        },
+        address => address,
+    }
+}
+
+/// Parses the authority from the given byte string.
+pub(crate) fn parse_authority(
+    value: &[u8],
+) -> Result<(Authority<'_>, &[u8]), (AuthorityError, usize)> {
+    let mut at_index = None;
+    let mut last_colon_index = None;
+    let mut end_index = value.len();
+
+    for (index, &byte) in value.iter().enumerate() {
+        match byte {
+            b'@' if at_index.is_none() => {
+                at_index = Some(index);
+                last_colon_index = None;
+            }
+            b':' => last_colon_index = Some(index),
+            b']' => last_colon_index = None,
+            b'/' | b'?' | b'#' => {
+                end_index = index;
+                break;
+            }
+            _ => (),
+        }
+    }
+
+    let (value, rest) = value.split_at(end_index);
+    let (username, password, host_start_index) = match at_index {
+        Some(index) => {
+            let (username, password) = parse_user_info(&value[..index])
+                .map_err(|(error, position)| (error.into(), position))?;
+            (Some(username), password, index + 1)
+        }
+        None => (None, None, 0),
+    };
+
+    // Errors within the host are reported at the start of the host, since host parsing does not
+    // track positions.
+    let host_end_index = last_colon_index.unwrap_or(value.len());
+    let host = Host::try_from(&value[host_start_index..host_end_index])
+        .map_err(|error| (error.into(), host_start_index))?;
+
+    let port = match last_colon_index {
+        Some(index) => {
+            let port = &value[index + 1..];
+            parse_port(port).map_err(|error| {
+                let position = match error {
+                    PortError::InvalidCharacter => port
+                        .iter()
+                        .position(|byte| !byte.is_ascii_digit())
+                        .unwrap_or(0),
+                    _ => 0,
+                };
+                (error.into(), index + 1 + position)
+            })?
+        }
+        None => None,
+    };
+
+    let authority = Authority {
+        host,
+        port,
+        password,
+        username,
+    };
+
+    Ok((authority, rest))
+}
+
+/// Parses the port from the given byte string.
+///
+/// This function can be used in `const` contexts.
+///
+/// # Examples
+///
+/// ```
+/// use uriparse::authority::parse_port;
+///
+/// const PORT: u16 = match parse_port(b"5683") {
+///     Ok(Some(port)) => port,
+///     _ => panic!("invalid port"),
+/// };
+/// assert_eq!(PORT, 5683);
+/// ```
+pub const fn parse_port(value: &[u8]) -> Result<Option<u16>, PortError> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    let mut index = 0;
+    let mut port = 0u16;
+
+    while index < value.len() {
+        let byte = value[index];
+
+        if !byte.is_ascii_digit() {
+            return Err(PortError::InvalidCharacter);
+        }
+
+        port = match port.checked_mul(10) {
+            Some(port) => match port.checked_add((byte - b'0') as u16) {
+                Some(port) => port,
+                None => return Err(PortError::Overflow),
+            },
+            None => return Err(PortError::Overflow),
+        };
+        index += 1;
+    }
+
+    Ok(Some(port))
+}
+
+/// Parses the user information from the given byte string.
+fn parse_user_info(
+    value: &[u8],
+) -> Result<(Username<'_>, Option<Password<'_>>), (UserInfoError, usize)> {
+    let mut bytes = value.iter().enumerate();
+    let mut first_colon_index = None;
+    let mut password_normalized = true;
+    let mut username_normalized = true;
+
+    while let Some((index, &byte)) = bytes.next() {
+        match USER_INFO_CHAR_MAP[byte as usize] {
+            0 => {
+                return if first_colon_index.is_some() {
+                    Err((PasswordError::InvalidCharacter.into(), index))
+                } else {
+                    Err((UsernameError::InvalidCharacter.into(), index))
+                }
+            }
+            b'%' => match get_percent_encoded_value(
+                bytes.next().map(|(_, &byte)| byte),
+                bytes.next().map(|(_, &byte)| byte),
+            ) {
+                Ok((hex_value, uppercase)) => {
+                    if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
+                        if first_colon_index.is_some() {
+                            password_normalized = false;
+                        } else {
+                            username_normalized = false;
+                        }
+                    }
+                }
+                Err(_) => {
+                    return if first_colon_index.is_some() {
+                        Err((PasswordError::InvalidPercentEncoding.into(), index))
+                    } else {
+                        Err((UsernameError::InvalidPercentEncoding.into(), index))
+                    }
+                }
+            },
+            b':' if first_colon_index.is_none() => {
+                first_colon_index = Some(index);
+            }
+            _ => (),
+        }
+    }
+
+    // Unsafe: All characters are ASCII-US, as checked above.
+    Ok(match first_colon_index {
+        Some(index) => {
+            let username = Username {
+                normalized: username_normalized,
+                username: Cow::from(unsafe { str::from_utf8_unchecked(&value[..index]) }),
+            };
+            let password = Password {
+                normalized: password_normalized,
+                password: Cow::from(unsafe { str::from_utf8_unchecked(&value[index + 1..]) }),
+            };
+            (username, Some(password))
+        }
+        _ => {
+            let username = Username {
+                normalized: username_normalized,
+                username: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
+            };
+            (username, None)
+        }
+    })
+}
@@ -0,0 +1,390 @@
+//! Internationalized Domain Names
+//!
+//! See [[RFC5890](https://tools.ietf.org/html/rfc5890)] and
+//! [[RFC3492](https://tools.ietf.org/html/rfc3492)].
+//!
+//! Registered names containing non-ASCII characters are percent-encoded UTF-8 within a URI, e.g.
+//! `"b%C3%BCcher.de"`. This module converts such names to their ASCII compatible encoding, where
+//! each non-ASCII label is lowercased, Punycode-encoded, and prefixed with `"xn--"`, e.g.
+//! `"xn--bcher-kva.de"`, as well as back again. The mapping and validation tables of UTS #46 are
+//! not applied.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::utility::{get_percent_encoded_value, percent_encode, UNRESERVED_CHAR_MAP};
+
+/// The prefix of labels that are encoded using Punycode.
+const ACE_PREFIX: &str = "xn--";
+
+const BASE: u32 = 36;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 0x80;
+const SKEW: u32 = 38;
+const T_MAX: u32 = 26;
+const T_MIN: u32 = 1;
+
+/// Converts the percent-encoded registered name into its ASCII compatible encoding.
+///
+/// Labels that are already ASCII are left as is. The ASCII characters of all other labels must be
+/// letters, digits, or hyphens, as anything else could not be represented in the encoded label.
+pub(super) fn to_ascii(name: &str) -> Result<String, IdnaError> {
+    let mut output = String::with_capacity(name.len());
+
+    for (index, label) in name.split('.').enumerate() {
+        if index > 0 {
+            output.push('.');
+        }
+
+        let decoded_label = decode_utf8(label)?;
+
+        if decoded_label.is_ascii() {
+            output.push_str(label);
+        } else if !check_unicode_label(&decoded_label) {
+            return Err(IdnaError::InvalidLabelCharacter);
+        } else {
+            output.push_str(ACE_PREFIX);
+            output.push_str(&encode(&decoded_label.to_lowercase())?);
+        }
+    }
+
+    Ok(output)
+}
+
+/// Converts the labels of the registered name that are in their ASCII compatible encoding back to
+/// Unicode, percent-encoding the result.
+///
+/// Encoded labels are only accepted if they are the encoding [`to_ascii`] would produce for the
+/// decoded label, i.e. if the decoded label contains at least one non-ASCII character, only
+/// characters allowed by [`to_ascii`], and encodes back to the same Punycode.
+pub(super) fn to_unicode(name: &str) -> Result<String, IdnaError> {
+    let mut output = String::with_capacity(name.len());
+
+    for (index, label) in name.split('.').enumerate() {
+        if index > 0 {
+            output.push('.');
+        }
+
+        match label.get(..ACE_PREFIX.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(ACE_PREFIX) => {
+                let punycode = &label[ACE_PREFIX.len()..];
+                let decoded_label = decode(punycode)?;
+
+                if decoded_label.is_ascii()
+                    || !check_unicode_label(&decoded_label)
+                    || !encode(&decoded_label)?.eq_ignore_ascii_case(punycode)
+                {
+                    return Err(IdnaError::InvalidPunycode);
+                }
+
+                let label = decoded_label.to_lowercase();
+                percent_encode(label.as_bytes(), &UNRESERVED_CHAR_MAP, &mut output);
+            }
+            _ => output.push_str(label),
+        }
+    }
+
+    Ok(output)
+}
+
+/// Adapts the bias as defined in
+/// [[RFC3492, Section 6.1](https://tools.ietf.org/html/rfc3492#section-6.1)].
+fn adapt(delta: u32, number_of_points: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time { delta / DAMP } else { delta / 2 };
+    delta += delta / number_of_points;
+
+    let mut k = 0;
+
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+
+    k + (BASE - T_MIN + 1) * delta / (delta + SKEW)
+}
+
+/// Returns true if the characters of the non-ASCII label can be encoded using Punycode, i.e. all
+/// ASCII characters are letters, digits, or hyphens and there are no control characters.
+fn check_unicode_label(label: &str) -> bool {
+    label.chars().all(|character| {
+        if character.is_ascii() {
+            character.is_ascii_alphanumeric() || character == '-'
+        } else {
+            !character.is_control()
+        }
+    })
+}
+
+/// Decodes a Punycode string as defined in
+/// [[RFC3492, Section 6.2](https://tools.ietf.org/html/rfc3492#section-6.2)].
+fn decode(input: &str) -> Result<String, IdnaError> {
+    let (basic, extended) = match input.rfind('-') {
+        Some(index) => (&input[..index], &input[index + 1..]),
+        None => ("", input),
+    };
+
+    if !basic.is_ascii() {
+        return Err(IdnaError::InvalidPunycode);
+    }
+
+    let mut output: Vec<char> = basic.chars().collect();
+    let mut n = INITIAL_N;
+    let mut i: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut bytes = extended.bytes().peekable();
+
+    while bytes.peek().is_some() {
+        let old_i = i;
+        let mut weight: u32 = 1;
+        let mut k = BASE;
+
+        loop {
+            let digit = bytes
+                .next()
+                .and_then(decode_digit)
+                .ok_or(IdnaError::InvalidPunycode)?;
+            i = digit
+                .checked_mul(weight)
+                .and_then(|value| i.checked_add(value))
+                .ok_or(IdnaError::InvalidPunycode)?;
+
+            let threshold = threshold(k, bias);
+
+            if digit < threshold {
+                break;
+            }
+
+            weight = weight
+                .checked_mul(BASE - threshold)
+                .ok_or(IdnaError::InvalidPunycode)?;
+            k += BASE;
+        }
+
+        let length = output.len() as u32 + 1;
+        bias = adapt(i - old_i, length, old_i == 0);
+        n = n
+            .checked_add(i / length)
+            .ok_or(IdnaError::InvalidPunycode)?;
+        i %= length;
+
+        let character = char::from_u32(n).ok_or(IdnaError::InvalidPunycode)?;
+        output.insert(i as usize, character);
+        i += 1;
+    }
+
+    Ok(output.into_iter().collect())
+}
+
+/// Returns the value of the given Punycode digit.
+fn decode_digit(byte: u8) -> Option<u32> {
+    match byte {
+        b'a'..=b'z' => Some(u32::from(byte - b'a')),
+        b'A'..=b'Z' => Some(u32::from(byte - b'A')),
+        b'0'..=b'9' => Some(u32::from(byte - b'0') + 26),
+        _ => None,
+    }
+}
+
+/// Percent-decodes the validated registered name, requiring the result to be valid UTF-8.
+fn decode_utf8(name: &str) -> Result<String, IdnaError> {
+    let mut bytes = name.bytes();
+    let mut decoded = Vec::with_capacity(name.len());
+
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let (hex_value, _) = get_percent_encoded_value(bytes.next(), bytes.next())
+                .expect("registered names should only contain valid percent encodings");
+            decoded.push(hex_value);
+        } else {
+            decoded.push(byte);
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| IdnaError::InvalidUtf8)
+}
+
+/// Encodes a string using Punycode as defined in
+/// [[RFC3492, Section 6.3](https://tools.ietf.org/html/rfc3492#section-6.3)].
+fn encode(input: &str) -> Result<String, IdnaError> {
+    let input: Vec<u32> = input.chars().map(u32::from).collect();
+    let mut output: String = input
+        .iter()
+        .filter(|&&code_point| code_point < INITIAL_N)
+        .map(|&code_point| code_point as u8 as char)
+        .collect();
+    let basic_length = output.len() as u32;
+    let mut handled = basic_length;
+
+    if basic_length > 0 {
+        output.push('-');
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+
+    while (handled as usize) < input.len() {
+        let minimum = input
+            .iter()
+            .cloned()
+            .filter(|&code_point| code_point >= n)
+            .min()
+            .expect("there should be unhandled code points");
+        delta = (minimum - n)
+            .checked_mul(handled + 1)
+            .and_then(|value| delta.checked_add(value))
+            .ok_or(IdnaError::Overflow)?;
+        n = minimum;
+
+        for &code_point in &input {
+            if code_point < n {
+                delta = delta.checked_add(1).ok_or(IdnaError::Overflow)?;
+            }
+
+            if code_point == n {
+                let mut q = delta;
+                let mut k = BASE;
+
+                loop {
+                    let threshold = threshold(k, bias);
+
+                    if q < threshold {
+                        break;
+                    }
+
+                    output.push(encode_digit(
+                        threshold + (q - threshold) % (BASE - threshold),
+                    ));
+                    q = (q - threshold) / (BASE - threshold);
+                    k += BASE;
+                }
+
+                output.push(encode_digit(q));
+                bias = adapt(delta, handled + 1, handled == basic_length);
+                delta = 0;
+                handled += 1;
+            }
+        }
+
+        delta += 1;
+        n += 1;
+    }
+
+    Ok(output)
+}
+
+/// Returns the Punycode digit for the given value.
+fn encode_digit(value: u32) -> char {
+    match value {
+        0..=25 => (b'a' + value as u8) as char,
+        _ => (b'0' + (value - 26) as u8) as char,
+    }
+}
+
+/// Returns the threshold for the given position and bias.
+fn threshold(k: u32, bias: u32) -> u32 {
+    if k <= bias {
+        T_MIN
+    } else if k >= bias + T_MAX {
+        T_MAX
+    } else {
+        k - bias
+    }
+}
+
+/// An error representing a failed conversion of a registered name to or from its ASCII compatible
+/// encoding.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum IdnaError {
+    /// A label that is not ASCII contained an ASCII character other than a letter, digit, or
+    /// hyphen, or a control character.
+    InvalidLabelCharacter,
+
+    /// A label prefixed with `"xn--"` did not contain valid Punycode, or it was not the encoding of
+    /// a label that is not ASCII.
+    InvalidPunycode,
+
+    /// The percent-decoded registered name was not valid UTF-8.
+    InvalidUtf8,
+
+    /// A label was too long to be encoded using Punycode.
+    Overflow,
+}
+
+impl Display for IdnaError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::IdnaError::*;
+
+        match self {
+            InvalidLabelCharacter => write!(formatter, "invalid character in non-ASCII label"),
+            InvalidPunycode => write!(formatter, "invalid punycode"),
+            InvalidUtf8 => write!(formatter, "invalid UTF-8 in registered name"),
+            Overflow => write!(formatter, "punycode overflow"),
+        }
+    }
+}
+
+impl Error for IdnaError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_idna_punycode() {
+        fn test_case(value: &str, expected: &str) {
+            assert_eq!(encode(value).unwrap(), expected);
+            assert_eq!(decode(expected).unwrap(), value);
+        }
+
+        test_case("bücher", "bcher-kva");
+        test_case("münchen", "mnchen-3ya");
+        test_case("ü", "tda");
+        test_case("他们为什么不说中文", "ihqwcrb4cv8a8dqg056pqjye");
+        test_case(
+            "安室奈美恵-with-super-monkeys",
+            "-with-super-monkeys-pc58ag80a8qai00g7n9n",
+        );
+        test_case("abc", "abc-");
+
+        assert_eq!(decode("bcher-kv!"), Err(IdnaError::InvalidPunycode));
+        assert_eq!(decode("ü-abc"), Err(IdnaError::InvalidPunycode));
+        assert_eq!(decode("99999999999"), Err(IdnaError::InvalidPunycode));
+    }
+
+    #[test]
+    fn test_idna_to_ascii() {
+        assert_eq!(to_ascii("b%C3%BCcher.de").unwrap(), "xn--bcher-kva.de");
+        assert_eq!(to_ascii("B%C3%9CCHER.DE").unwrap(), "xn--bcher-kva.DE");
+        assert_eq!(to_ascii("ex%61mple.com").unwrap(), "ex%61mple.com");
+        assert_eq!(to_ascii("%FF.com"), Err(IdnaError::InvalidUtf8));
+        assert_eq!(
+            to_ascii("a%20%C3%BC.de"),
+            Err(IdnaError::InvalidLabelCharacter)
+        );
+        assert_eq!(
+            to_ascii("x%2F%C3%BC.de"),
+            Err(IdnaError::InvalidLabelCharacter)
+        );
+        assert_eq!(to_ascii("%C2%80.de"), Err(IdnaError::InvalidLabelCharacter));
+    }
+
+    #[test]
+    fn test_idna_to_unicode() {
+        assert_eq!(to_unicode("xn--bcher-kva.de").unwrap(), "b%C3%BCcher.de");
+        assert_eq!(to_unicode("XN--BCHER-KVA.de").unwrap(), "b%C3%BCcher.de");
+        assert_eq!(to_unicode("example.com").unwrap(), "example.com");
+        assert_eq!(to_unicode("xn--a!.com"), Err(IdnaError::InvalidPunycode));
+        assert_eq!(to_unicode("xn--.com"), Err(IdnaError::InvalidPunycode));
+        assert_eq!(to_unicode("xn---.com"), Err(IdnaError::InvalidPunycode));
+        assert_eq!(to_unicode("xn--2-.com"), Err(IdnaError::InvalidPunycode));
+        assert_eq!(to_unicode("xn--a.com"), Err(IdnaError::InvalidPunycode));
+        assert_eq!(to_unicode("xn--abc-.com"), Err(IdnaError::InvalidPunycode));
+        assert_eq!(
+            to_unicode("xn--bcher-kva-.com"),
+            Err(IdnaError::InvalidPunycode)
+        );
+    }
+}
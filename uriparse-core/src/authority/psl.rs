@@ -0,0 +1,151 @@
+//! Public Suffix List
+//!
+//! See [https://publicsuffix.org](https://publicsuffix.org).
+//!
+//! A snapshot of the list is embedded in the crate, so results do not depend on the system the
+//! crate runs on. It is updated by running `scripts/update-public-suffix-list.sh`. Each rule is on
+//! its own line and is either a normal rule (`"co.uk"`), a wildcard rule (`"*.ck"`), or an
+//! exception rule (`"!www.ck"`). Lines starting with `"//"` are comments. Names that match no rule
+//! fall under the implicit `"*"` rule, that is their top-level domain is their public suffix.
+
+use fnv::FnvBuildHasher;
+use lazy_static::lazy_static;
+use std::collections::HashSet;
+
+use super::idna;
+use crate::utility::percent_decode;
+
+/// The embedded snapshot of the public suffix list.
+const PUBLIC_SUFFIX_LIST: &str = include_str!("public_suffix_list.dat");
+
+lazy_static! {
+    /// The rules of the embedded public suffix list, parsed once.
+    static ref RULES: Rules = parse_rules(PUBLIC_SUFFIX_LIST);
+}
+
+/// The parsed rules of a public suffix list.
+#[derive(Debug, Default)]
+struct Rules {
+    /// Exception rules without the leading `'!'`.
+    exceptions: HashSet<String, FnvBuildHasher>,
+
+    /// Normal rules.
+    rules: HashSet<String, FnvBuildHasher>,
+
+    /// Wildcard rules without the leading `"*."`.
+    wildcards: HashSet<String, FnvBuildHasher>,
+}
+
+impl Rules {
+    /// Returns the number of trailing labels that form the public suffix of the given normalized
+    /// labels.
+    fn public_suffix_length(&self, labels: &[String]) -> usize {
+        for index in 0..labels.len() {
+            let length = labels.len() - index;
+            let suffix = labels[index..].join(".");
+
+            if self.exceptions.contains(&suffix) {
+                return length - 1;
+            }
+
+            if self.rules.contains(&suffix)
+                || (length > 1 && self.wildcards.contains(&labels[index + 1..].join(".")))
+            {
+                return length;
+            }
+        }
+
+        1
+    }
+}
+
+/// Normalizes a label of a registered name for comparison against the rules of the list.
+///
+/// The label is converted from its ASCII compatible encoding if necessary, percent-decoded and
+/// lowercased.
+pub(super) fn normalize_label(label: &str) -> String {
+    match idna::to_unicode(label) {
+        Ok(label) => percent_decode(&label).to_lowercase(),
+        Err(_) => percent_decode(label).to_lowercase(),
+    }
+}
+
+/// Returns the number of trailing labels that form the public suffix of the given normalized
+/// labels, according to the embedded public suffix list.
+pub(super) fn public_suffix_length(labels: &[String]) -> usize {
+    RULES.public_suffix_length(labels)
+}
+
+/// Parses the contents of a public suffix list.
+fn parse_rules(contents: &str) -> Rules {
+    let mut rules = Rules::default();
+
+    for line in contents.lines() {
+        let rule = match line.split_whitespace().next() {
+            Some(rule) if !rule.starts_with("//") => rule.to_lowercase(),
+            _ => continue,
+        };
+
+        if let Some(rule) = rule.strip_prefix('!') {
+            rules.exceptions.insert(rule.to_string());
+        } else if let Some(rule) = rule.strip_prefix("*.") {
+            rules.wildcards.insert(rule.to_string());
+        } else {
+            rules.rules.insert(rule);
+        }
+    }
+
+    rules
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn labels(name: &str) -> Vec<String> {
+        name.split('.').map(normalize_label).collect()
+    }
+
+    #[test]
+    fn test_normalize_label() {
+        assert_eq!(normalize_label("EXAMPLE"), "example");
+        assert_eq!(normalize_label("B%C3%9Ccher"), "bücher");
+        assert_eq!(normalize_label("xn--bcher-kva"), "bücher");
+        assert_eq!(normalize_label("XN--BCHER-KVA"), "bücher");
+    }
+
+    #[test]
+    fn test_public_suffix_length() {
+        let rules = parse_rules(
+            "// ===BEGIN ICANN DOMAINS===\n\
+             \n\
+             com\n\
+             uk\n\
+             co.uk\n\
+             *.ck\n\
+             !www.ck\n\
+             公司.cn\n\
+             // ===BEGIN PRIVATE DOMAINS===\n\
+             github.io\n",
+        );
+
+        assert_eq!(rules.public_suffix_length(&labels("example.com")), 1);
+        assert_eq!(rules.public_suffix_length(&labels("www.example.co.uk")), 2);
+        assert_eq!(rules.public_suffix_length(&labels("co.uk")), 2);
+        assert_eq!(rules.public_suffix_length(&labels("a.b.ck")), 2);
+        assert_eq!(rules.public_suffix_length(&labels("www.ck")), 1);
+        assert_eq!(rules.public_suffix_length(&labels("a.www.ck")), 1);
+        assert_eq!(rules.public_suffix_length(&labels("xn--55qx5d.cn")), 2);
+        assert_eq!(rules.public_suffix_length(&labels("user.GitHub.io")), 2);
+        assert_eq!(rules.public_suffix_length(&labels("example.unknown")), 1);
+    }
+
+    #[test]
+    fn test_public_suffix_list() {
+        assert!(RULES.rules.len() > 1000);
+        assert_eq!(public_suffix_length(&labels("www.example.co.uk")), 2);
+        assert_eq!(public_suffix_length(&labels("user.github.io")), 2);
+        assert_eq!(public_suffix_length(&labels("a.b.ck")), 2);
+        assert_eq!(public_suffix_length(&labels("www.ck")), 1);
+    }
+}
@@ -0,0 +1,114 @@
+//! Service Name Lookup
+//!
+//! Maps service names such as `"imap"` to their port using the services database of the system,
+//! which on Unix-like systems is found at `/etc/services`. Each line of the database has the form
+//! `name port/protocol [aliases...] [# comment]`.
+
+use fnv::FnvBuildHasher;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::fs;
+
+/// The location of the services database.
+#[cfg(windows)]
+const SERVICES_PATH: &str = r"C:\Windows\System32\drivers\etc\services";
+
+/// The location of the services database.
+#[cfg(not(windows))]
+const SERVICES_PATH: &str = "/etc/services";
+
+lazy_static! {
+    /// An immutable hashmap mapping service names and aliases to their ports, read once from the
+    /// services database.
+    static ref SERVICE_PORTS: HashMap<String, u16, FnvBuildHasher> = {
+        let contents = fs::read_to_string(SERVICES_PATH).unwrap_or_default();
+        parse_services(&contents)
+    };
+}
+
+/// Returns the port of the given service as listed in the services database of the system.
+///
+/// Service names are compared case-insensitively. If a service is listed for multiple protocols,
+/// the TCP port is preferred. [`None`] is returned if the service is not listed or the database
+/// cannot be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use uriparse::authority::port_for_service;
+///
+/// assert_eq!(port_for_service("imap"), Some(143));
+/// ```
+pub fn port_for_service(name: &str) -> Option<u16> {
+    SERVICE_PORTS.get(&name.to_ascii_lowercase()).cloned()
+}
+
+/// Parses the contents of a services database.
+fn parse_services(contents: &str) -> HashMap<String, u16, FnvBuildHasher> {
+    let mut tcp_ports = HashMap::default();
+    let mut other_ports: HashMap<String, u16, FnvBuildHasher> = HashMap::default();
+
+    for line in contents.lines() {
+        let line = match line.find('#') {
+            Some(index) => &line[..index],
+            None => line,
+        };
+        let mut fields = line.split_whitespace();
+
+        let (name, port) = match (fields.next(), fields.next()) {
+            (Some(name), Some(port)) => (name, port),
+            _ => continue,
+        };
+        let (port, protocol) = match port.split_once('/') {
+            Some((port, protocol)) => match port.parse() {
+                Ok(port) => (port, protocol),
+                Err(_) => continue,
+            },
+            None => continue,
+        };
+        let ports = if protocol.eq_ignore_ascii_case("tcp") {
+            &mut tcp_ports
+        } else {
+            &mut other_ports
+        };
+
+        for name in Some(name).into_iter().chain(fields) {
+            ports.entry(name.to_ascii_lowercase()).or_insert(port);
+        }
+    }
+
+    for (name, port) in other_ports {
+        tcp_ports.entry(name).or_insert(port);
+    }
+
+    tcp_ports
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_services() {
+        let services = parse_services(
+            "# Network services\n\
+             \n\
+             ftp             21/tcp\n\
+             domain          53/tcp                          # Domain Name Server\n\
+             domain          53/udp\n\
+             http            80/tcp          www             # WorldWideWeb HTTP\n\
+             snmp            161/udp\n\
+             invalid         port/tcp\n\
+             incomplete\n",
+        );
+
+        assert_eq!(services.get("ftp"), Some(&21));
+        assert_eq!(services.get("domain"), Some(&53));
+        assert_eq!(services.get("http"), Some(&80));
+        assert_eq!(services.get("www"), Some(&80));
+        assert_eq!(services.get("snmp"), Some(&161));
+        assert_eq!(services.get("invalid"), None);
+        assert_eq!(services.get("incomplete"), None);
+        assert_eq!(services.len(), 5);
+    }
+}
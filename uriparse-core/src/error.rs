@@ -0,0 +1,337 @@
+//! Errors
+//!
+//! Every type in this crate has its own error type describing why a value of that type is invalid.
+//! The [`Error`] type wraps all of them, so applications can use a single error type at their API
+//! boundaries without losing which component failed. The wrapped error is available through
+//! [`std::error::Error::source`].
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::absolute_uri::AbsoluteURIError;
+use crate::authority::{AuthorityError, HostError, PasswordError, PortError, UsernameError};
+use crate::fragment::FragmentError;
+use crate::path::PathError;
+use crate::query::QueryError;
+use crate::relative_reference::RelativeReferenceError;
+use crate::scheme::SchemeError;
+use crate::uri::URIError;
+use crate::uri_reference::{Component, ParseError, URIReferenceError};
+
+/// An error representing any invalid value of this crate.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use std::error::Error as _;
+///
+/// use uriparse::{Component, Error, URI};
+///
+/// fn parse(value: &str) -> Result<URI<'_>, Error> {
+///     Ok(URI::try_from(value)?)
+/// }
+///
+/// let error = parse("http://example.com/?%zz").unwrap_err();
+/// assert_eq!(error.component(), Component::Query);
+/// assert_eq!(error.to_string(), "invalid URI");
+/// assert_eq!(error.source().unwrap().to_string(), "invalid query percent encoding");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// An absolute URI was invalid.
+    AbsoluteURI(AbsoluteURIError),
+
+    /// An authority was invalid.
+    Authority(AuthorityError),
+
+    /// A fragment was invalid.
+    Fragment(FragmentError),
+
+    /// A host was invalid.
+    Host(HostError),
+
+    /// A password was invalid.
+    Password(PasswordError),
+
+    /// A path was invalid.
+    Path(PathError),
+
+    /// A port was invalid.
+    Port(PortError),
+
+    /// A query was invalid.
+    Query(QueryError),
+
+    /// A relative reference was invalid.
+    RelativeReference(RelativeReferenceError),
+
+    /// A scheme was invalid.
+    Scheme(SchemeError),
+
+    /// A URI was invalid.
+    URI(URIError),
+
+    /// A URI reference was invalid.
+    URIReference(URIReferenceError),
+
+    /// A username was invalid.
+    Username(UsernameError),
+}
+
+impl Error {
+    /// Returns the component that was invalid.
+    ///
+    /// Errors of the host, password, port and username are all part of the authority component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Component, Error, RelativeReference};
+    ///
+    /// let error = Error::from(RelativeReference::try_from("http://example.com").unwrap_err());
+    /// assert_eq!(error.component(), Component::Scheme);
+    /// ```
+    pub fn component(&self) -> Component {
+        match self {
+            Error::AbsoluteURI(AbsoluteURIError::HasFragment) => Component::Fragment,
+            Error::AbsoluteURI(AbsoluteURIError::URI(error)) | Error::URI(error) => {
+                uri_error_component(error)
+            }
+            Error::Authority(_)
+            | Error::Host(_)
+            | Error::Password(_)
+            | Error::Port(_)
+            | Error::Username(_) => Component::Authority,
+            Error::Fragment(_) => Component::Fragment,
+            Error::Path(_) => Component::Path,
+            Error::Query(_) => Component::Query,
+            Error::RelativeReference(error) => relative_reference_error_component(error),
+            Error::Scheme(_) => Component::Scheme,
+            Error::URIReference(error) => uri_reference_error_component(error),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::Error::*;
+
+        match self {
+            AbsoluteURI(_) => write!(formatter, "invalid absolute URI"),
+            Authority(_) => write!(formatter, "invalid authority"),
+            Fragment(_) => write!(formatter, "invalid fragment"),
+            Host(_) => write!(formatter, "invalid host"),
+            Password(_) => write!(formatter, "invalid password"),
+            Path(_) => write!(formatter, "invalid path"),
+            Port(_) => write!(formatter, "invalid port"),
+            Query(_) => write!(formatter, "invalid query"),
+            RelativeReference(_) => write!(formatter, "invalid relative reference"),
+            Scheme(_) => write!(formatter, "invalid scheme"),
+            URI(_) => write!(formatter, "invalid URI"),
+            URIReference(_) => write!(formatter, "invalid URI reference"),
+            Username(_) => write!(formatter, "invalid username"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        use self::Error::*;
+
+        Some(match self {
+            AbsoluteURI(error) => error,
+            Authority(error) => error,
+            Fragment(error) => error,
+            Host(error) => error,
+            Password(error) => error,
+            Path(error) => error,
+            Port(error) => error,
+            Query(error) => error,
+            RelativeReference(error) => error,
+            Scheme(error) => error,
+            URI(error) => error,
+            URIReference(error) => error,
+            Username(error) => error,
+        })
+    }
+}
+
+impl From<AbsoluteURIError> for Error {
+    fn from(value: AbsoluteURIError) -> Self {
+        Error::AbsoluteURI(value)
+    }
+}
+
+impl From<AuthorityError> for Error {
+    fn from(value: AuthorityError) -> Self {
+        Error::Authority(value)
+    }
+}
+
+impl From<FragmentError> for Error {
+    fn from(value: FragmentError) -> Self {
+        Error::Fragment(value)
+    }
+}
+
+impl From<HostError> for Error {
+    fn from(value: HostError) -> Self {
+        Error::Host(value)
+    }
+}
+
+impl<TError> From<ParseError<TError>> for Error
+where
+    Error: From<TError>,
+{
+    fn from(value: ParseError<TError>) -> Self {
+        value.into_error().into()
+    }
+}
+
+impl From<PasswordError> for Error {
+    fn from(value: PasswordError) -> Self {
+        Error::Password(value)
+    }
+}
+
+impl From<PathError> for Error {
+    fn from(value: PathError) -> Self {
+        Error::Path(value)
+    }
+}
+
+impl From<PortError> for Error {
+    fn from(value: PortError) -> Self {
+        Error::Port(value)
+    }
+}
+
+impl From<QueryError> for Error {
+    fn from(value: QueryError) -> Self {
+        Error::Query(value)
+    }
+}
+
+impl From<RelativeReferenceError> for Error {
+    fn from(value: RelativeReferenceError) -> Self {
+        Error::RelativeReference(value)
+    }
+}
+
+impl From<SchemeError> for Error {
+    fn from(value: SchemeError) -> Self {
+        Error::Scheme(value)
+    }
+}
+
+impl From<URIError> for Error {
+    fn from(value: URIError) -> Self {
+        Error::URI(value)
+    }
+}
+
+impl From<URIReferenceError> for Error {
+    fn from(value: URIReferenceError) -> Self {
+        Error::URIReference(value)
+    }
+}
+
+impl From<UsernameError> for Error {
+    fn from(value: UsernameError) -> Self {
+        Error::Username(value)
+    }
+}
+
+fn relative_reference_error_component(error: &RelativeReferenceError) -> Component {
+    use self::RelativeReferenceError::*;
+
+    match error {
+        Authority(_) => Component::Authority,
+        Fragment(_) => Component::Fragment,
+        NotRelativeReference => Component::Scheme,
+        Query(_) => Component::Query,
+        AbsolutePathStartsWithTwoSlashes
+        | MissingPath
+        | Path(_)
+        | SchemelessPathStartsWithColonSegment => Component::Path,
+    }
+}
+
+fn uri_error_component(error: &URIError) -> Component {
+    use self::URIError::*;
+
+    match error {
+        Authority(_) => Component::Authority,
+        Fragment(_) => Component::Fragment,
+        AbsolutePathStartsWithTwoSlashes | MissingPath | Path(_) => Component::Path,
+        Query(_) => Component::Query,
+        MissingScheme | NotURI | Scheme(_) => Component::Scheme,
+    }
+}
+
+fn uri_reference_error_component(error: &URIReferenceError) -> Component {
+    use self::URIReferenceError::*;
+
+    match error {
+        Authority(_) => Component::Authority,
+        Fragment(_) => Component::Fragment,
+        AbsolutePathStartsWithTwoSlashes
+        | MissingPath
+        | Path(_)
+        | SchemelessPathStartsWithColonSegment => Component::Path,
+        Query(_) => Component::Query,
+        Scheme(_) => Component::Scheme,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+    use std::error::Error as _;
+
+    use super::*;
+    use crate::absolute_uri::AbsoluteURI;
+    use crate::authority::Authority;
+    use crate::uri::URI;
+    use crate::uri_reference::URIReference;
+
+    #[test]
+    fn test_error_component() {
+        fn test_case(error: Error, component: Component) {
+            assert_eq!(error.component(), component, "{:?}", error);
+            assert!(error.source().is_some());
+        }
+
+        test_case(
+            URI::try_from("http://exa mple.com/").unwrap_err().into(),
+            Component::Authority,
+        );
+        test_case(
+            Authority::try_from("example.com:x").unwrap_err().into(),
+            Component::Authority,
+        );
+        test_case(
+            URIReference::try_from("1a:b").unwrap_err().into(),
+            Component::Path,
+        );
+        test_case(
+            URI::try_from("/path").unwrap_err().into(),
+            Component::Scheme,
+        );
+        test_case(
+            AbsoluteURI::try_from("http://example.com/#a")
+                .unwrap_err()
+                .into(),
+            Component::Fragment,
+        );
+        test_case(
+            URIReference::parse("#a b").unwrap_err().into(),
+            Component::Fragment,
+        );
+    }
+}
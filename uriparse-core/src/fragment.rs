@@ -0,0 +1,790 @@
+//! Fragment Component
+//!
+//! See [[RFC3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5)].
+
+pub mod json_pointer;
+#[cfg(feature = "media-fragments")]
+pub mod media;
+pub mod text;
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::convert::{Infallible, TryFrom};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::{self, FromStr};
+
+use self::json_pointer::JsonPointerError;
+use self::text::{TextDirective, FRAGMENT_DIRECTIVE_DELIMITER};
+use crate::utility::{
+    get_percent_encoded_value_at, normalize_string, percent_decode, percent_decoded_equality,
+    percent_encode, percent_encoded_cmp, percent_encoded_equality, percent_encoded_hash,
+    UNRESERVED_CHAR_MAP,
+};
+
+/// A map of byte characters that determines if a character is a valid fragment character.
+#[rustfmt::skip]
+const FRAGMENT_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$', b'%', b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.', b'/', // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0, b'=',    0, b'?', // 3
+ b'@', b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A map of byte characters that can be left unencoded when encoding arbitrary text as a fragment.
+///
+/// This is the same as [`FRAGMENT_CHAR_MAP`] except that `'%'` is excluded so that it is always
+/// percent-encoded.
+#[rustfmt::skip]
+const FRAGMENT_ENCODE_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$',    0, b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.', b'/', // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0, b'=',    0, b'?', // 3
+ b'@', b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// The fragment component as defined in
+/// [[RFC3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5)].
+///
+/// The fragment is case-sensitive. Furthermore, percent-encoding plays no role in equality checking
+/// for characters in the unreserved character set meaning that `"fragment"` and `"fr%61gment"` are
+/// identical. Both of these attributes are reflected in the equality and hash functions.
+///
+/// However, be aware that just because percent-encoding plays no role in equality checking does not
+/// mean that the fragment is normalized. If the fragment needs to be normalized, use the
+/// [`Fragment::normalize`] function.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fragment<'fragment> {
+    /// The internal fragment source that is either owned or borrowed.
+    fragment: Cow<'fragment, str>,
+
+    /// Whether the fragment is normalized.
+    normalized: bool,
+}
+
+impl Fragment<'_> {
+    /// Returns a new fragment which is identical but has a lifetime tied to this fragment.
+    pub fn as_borrowed(&self) -> Fragment<'_> {
+        use self::Cow::*;
+
+        let fragment = match &self.fragment {
+            Borrowed(borrowed) => *borrowed,
+            Owned(owned) => owned.as_str(),
+        };
+
+        Fragment {
+            fragment: Cow::Borrowed(fragment),
+            normalized: self.normalized,
+        }
+    }
+
+    /// Returns the percent-decoded fragment.
+    ///
+    /// If the fragment contains no percent-encodings, the returned value borrows from the fragment.
+    /// Decoded bytes that are not valid UTF-8 are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("section").unwrap();
+    /// assert!(matches!(fragment.as_decoded(), Cow::Borrowed("section")));
+    ///
+    /// let fragment = Fragment::try_from("Getting%20Started").unwrap();
+    /// assert_eq!(fragment.as_decoded(), "Getting Started");
+    /// ```
+    pub fn as_decoded(&self) -> Cow<'_, str> {
+        percent_decode(&self.fragment)
+    }
+
+    /// Returns a `str` representation of the fragment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("fragment").unwrap();
+    /// assert_eq!(fragment.as_str(), "fragment");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.fragment
+    }
+
+    /// Constructs a fragment from arbitrary unencoded text.
+    ///
+    /// Any character that is not allowed in a fragment, as well as `%` itself, is percent-encoded,
+    /// so the returned fragment always decodes back to the given text. The resulting fragment is
+    /// always normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::encode("Caf\u{e9} & 100% #1");
+    /// assert_eq!(fragment, "Caf%C3%A9%20&%20100%25%20%231");
+    /// assert_eq!(fragment.as_decoded(), "Caf\u{e9} & 100% #1");
+    /// ```
+    pub fn encode(value: &str) -> Fragment<'static> {
+        let mut fragment = String::with_capacity(value.len());
+        percent_encode(value.as_bytes(), &FRAGMENT_ENCODE_CHAR_MAP, &mut fragment);
+
+        Fragment {
+            fragment: Cow::from(fragment),
+            normalized: true,
+        }
+    }
+
+    /// Returns whether the percent-decoded fragment is equal to the given unencoded string.
+    ///
+    /// Unlike comparing against [`Fragment::as_decoded`], this does not allocate, and
+    /// percent-encoded bytes that are not valid UTF-8 never compare equal to a replacement
+    /// character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("Getting%20Started").unwrap();
+    /// assert!(fragment.eq_decoded("Getting Started"));
+    /// assert!(!fragment.eq_decoded("Getting%20Started"));
+    /// ```
+    pub fn eq_decoded(&self, other: &str) -> bool {
+        percent_decoded_equality(self.fragment.as_bytes(), other.as_bytes())
+    }
+
+    /// Constructs a fragment from the given JSON pointer.
+    ///
+    /// The JSON pointer must already have its reference tokens escaped (i.e. `~` as `~0` and `/`
+    /// as `~1`). It is then percent-encoded as required for fragments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::from_json_pointer("/definitions/a b/~1").unwrap();
+    /// assert_eq!(fragment, "/definitions/a%20b/~1");
+    /// assert!(Fragment::from_json_pointer("definitions").is_err());
+    /// ```
+    pub fn from_json_pointer(pointer: &str) -> Result<Fragment<'static>, JsonPointerError> {
+        let fragment = json_pointer::encode(pointer)?;
+
+        Ok(Fragment {
+            fragment: Cow::from(fragment),
+            normalized: true,
+        })
+    }
+
+    /// Constructs a fragment from the JSON pointer with the given unescaped reference tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::from_json_pointer_tokens(&["paths", "/users/{id}", "get"]);
+    /// assert_eq!(fragment, "/paths/~1users~1%7Bid%7D/get");
+    /// ```
+    pub fn from_json_pointer_tokens<I, T>(tokens: I) -> Fragment<'static>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut pointer = String::new();
+
+        for token in tokens {
+            json_pointer::push_reference_token(&mut pointer, token.as_ref());
+        }
+
+        Fragment::from_json_pointer(&pointer).expect("escaped tokens should form a valid pointer")
+    }
+
+    /// Constructs a fragment from the given string in a `const` context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid fragment. In a `const` or `static` item, this results in
+    /// a compilation error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Fragment;
+    ///
+    /// const FRAGMENT: Fragment<'static> = Fragment::from_static("section-1");
+    /// assert_eq!(FRAGMENT, "section-1");
+    /// ```
+    pub const fn from_static(value: &'static str) -> Fragment<'static> {
+        match scan_fragment(value.as_bytes()) {
+            Ok(normalized) => Fragment {
+                fragment: Cow::Borrowed(value),
+                normalized,
+            },
+            Err((FragmentError::InvalidCharacter, _)) => panic!("invalid fragment character"),
+            Err((FragmentError::InvalidPercentEncoding, _)) => {
+                panic!("invalid fragment percent encoding")
+            }
+        }
+    }
+
+    /// Converts the [`Fragment`] into an owned copy.
+    ///
+    /// If you construct the fragment from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the fragment will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Fragment<'static> {
+        Fragment {
+            fragment: Cow::from(self.fragment.into_owned()),
+            normalized: self.normalized,
+        }
+    }
+
+    /// Returns the unescaped reference tokens of the JSON pointer represented by the fragment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("/paths/~1users~1%7Bid%7D/get").unwrap();
+    /// assert_eq!(
+    ///     fragment.json_pointer_tokens().unwrap(),
+    ///     vec!["paths", "/users/{id}", "get"]
+    /// );
+    /// ```
+    pub fn json_pointer_tokens(&self) -> Result<Vec<String>, JsonPointerError> {
+        let pointer = json_pointer::decode(&self.fragment)?;
+        Ok(json_pointer::reference_tokens(&pointer))
+    }
+
+    /// Returns whether the fragment is normalized.
+    ///
+    /// A normalized fragment will have no bytes that are in the unreserved character set
+    /// percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// This function runs in constant-time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("fragment").unwrap();
+    /// assert!(fragment.is_normalized());
+    ///
+    /// let mut fragment = Fragment::try_from("%ff%ff").unwrap();
+    /// assert!(!fragment.is_normalized());
+    /// fragment.normalize();
+    /// assert!(fragment.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Normalizes the fragment such that it will have no bytes that are in the unreserved character
+    /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// If the fragment is already normalized, the function will return immediately. Otherwise, if
+    /// the fragment is not owned, this function will perform an allocation to clone it. The
+    /// normalization itself though, is done in-place with no extra memory allocations required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let mut fragment = Fragment::try_from("fragment").unwrap();
+    /// fragment.normalize();
+    /// assert_eq!(fragment, "fragment");
+    ///
+    /// let mut fragment = Fragment::try_from("%ff%41").unwrap();
+    /// assert_eq!(fragment, "%ff%41");
+    /// fragment.normalize();
+    /// assert_eq!(fragment, "%FFA");
+    /// ```
+    pub fn normalize(&mut self) {
+        if !self.normalized {
+            // Unsafe: Fragments must be valid ASCII-US, so this is safe.
+            unsafe { normalize_string(self.fragment.to_mut(), true) };
+            self.normalized = true;
+        }
+    }
+
+    /// Replaces the fragment directives of the fragment with the given text directives.
+    ///
+    /// The part of the fragment before the fragment directive delimiter `:~:` is kept as is, and
+    /// any existing fragment directives are removed. If there are no text directives, the delimiter
+    /// is removed as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Fragment, TextDirective};
+    ///
+    /// let mut fragment = Fragment::try_from("section").unwrap();
+    /// let directive = TextDirective::new("quick brown").unwrap().with_end("fox");
+    /// fragment.set_text_directives(&[directive]);
+    /// assert_eq!(fragment, "section:~:text=quick%20brown,fox");
+    ///
+    /// fragment.set_text_directives(&[]);
+    /// assert_eq!(fragment, "section");
+    /// ```
+    pub fn set_text_directives<'directive, I>(&mut self, directives: I)
+    where
+        I: IntoIterator<Item = &'directive TextDirective>,
+    {
+        let fragment = match self.fragment.find(FRAGMENT_DIRECTIVE_DELIMITER) {
+            Some(index) => &self.fragment[..index],
+            None => &self.fragment,
+        };
+
+        *self = text::build_fragment(fragment, directives);
+    }
+
+    /// Returns the JSON pointer represented by the fragment.
+    ///
+    /// The fragment is percent-decoded, but the reference tokens of the JSON pointer are left
+    /// escaped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("/definitions/a%20b/~1").unwrap();
+    /// assert_eq!(fragment.to_json_pointer().unwrap(), "/definitions/a b/~1");
+    /// assert!(Fragment::try_from("section").unwrap().to_json_pointer().is_err());
+    /// ```
+    pub fn to_json_pointer(&self) -> Result<Cow<'_, str>, JsonPointerError> {
+        json_pointer::decode(&self.fragment)
+    }
+
+    /// Returns the text directives of the fragment.
+    ///
+    /// Text directives are the `text=` directives that appear after the fragment directive
+    /// delimiter `:~:`. Invalid text directives are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from(":~:text=an-,example,-text&text=other").unwrap();
+    /// let directives = fragment.text_directives();
+    /// assert_eq!(directives.len(), 2);
+    /// assert_eq!(directives[0].prefix(), Some("an"));
+    /// assert_eq!(directives[0].start(), "example");
+    /// assert_eq!(directives[0].suffix(), Some("text"));
+    /// assert_eq!(directives[1].start(), "other");
+    /// ```
+    pub fn text_directives(&self) -> Vec<TextDirective> {
+        let directives = match self.fragment.find(FRAGMENT_DIRECTIVE_DELIMITER) {
+            Some(index) => &self.fragment[index + FRAGMENT_DIRECTIVE_DELIMITER.len()..],
+            None => return Vec::new(),
+        };
+
+        directives
+            .split('&')
+            .filter_map(|directive| directive.strip_prefix("text="))
+            .filter_map(TextDirective::parse)
+            .collect()
+    }
+}
+
+impl AsRef<[u8]> for Fragment<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.fragment.as_bytes()
+    }
+}
+
+impl AsRef<str> for Fragment<'_> {
+    fn as_ref(&self) -> &str {
+        &self.fragment
+    }
+}
+
+impl Deref for Fragment<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.fragment
+    }
+}
+
+impl Display for Fragment<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(&self.fragment)
+    }
+}
+
+impl Eq for Fragment<'_> {}
+
+impl<'fragment> From<Fragment<'fragment>> for String {
+    fn from(value: Fragment<'fragment>) -> Self {
+        value.to_string()
+    }
+}
+
+impl FromStr for Fragment<'static> {
+    type Err = FragmentError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Fragment::try_from(value).map(Fragment::into_owned)
+    }
+}
+
+impl Hash for Fragment<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        percent_encoded_hash(self.fragment.as_bytes(), state, true);
+    }
+}
+
+impl Ord for Fragment<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(self.fragment.as_bytes(), other.fragment.as_bytes(), true)
+    }
+}
+
+impl PartialEq for Fragment<'_> {
+    fn eq(&self, other: &Fragment) -> bool {
+        *self == *other.as_bytes()
+    }
+}
+
+impl PartialEq<[u8]> for Fragment<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        percent_encoded_equality(self.fragment.as_bytes(), other, true)
+    }
+}
+
+impl<'fragment> PartialEq<Fragment<'fragment>> for [u8] {
+    fn eq(&self, other: &Fragment<'fragment>) -> bool {
+        other == self
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for Fragment<'_> {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self == *other
+    }
+}
+
+impl<'fragment> PartialEq<Fragment<'fragment>> for &[u8] {
+    fn eq(&self, other: &Fragment<'fragment>) -> bool {
+        other == *self
+    }
+}
+
+impl PartialEq<str> for Fragment<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'fragment> PartialEq<Fragment<'fragment>> for str {
+    fn eq(&self, other: &Fragment<'fragment>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Fragment<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'fragment> PartialEq<Fragment<'fragment>> for &str {
+    fn eq(&self, other: &Fragment<'fragment>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl PartialOrd for Fragment<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'fragment> TryFrom<&'fragment [u8]> for Fragment<'fragment> {
+    type Error = FragmentError;
+
+    fn try_from(value: &'fragment [u8]) -> Result<Self, Self::Error> {
+        parse_fragment(value).map_err(|(error, _)| error)
+    }
+}
+
+impl<'fragment> TryFrom<&'fragment str> for Fragment<'fragment> {
+    type Error = FragmentError;
+
+    fn try_from(value: &'fragment str) -> Result<Self, Self::Error> {
+        Fragment::try_from(value.as_bytes())
+    }
+}
+
+/// An error representing an invalid fragment.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum FragmentError {
+    /// The fragment contained an invalid character.
+    InvalidCharacter,
+
+    /// The fragment contained an invalid percent encoding (e.g. `"%ZZ"`).
+    InvalidPercentEncoding,
+}
+
+impl Display for FragmentError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::FragmentError::*;
+
+        match self {
+            InvalidCharacter => write!(formatter, "invalid fragment character"),
+            InvalidPercentEncoding => write!(formatter, "invalid fragment percent encoding"),
+        }
+    }
+}
+
+impl Error for FragmentError {}
+
+impl From<Infallible> for FragmentError {
+    fn from(_: Infallible) -> Self {
+        FragmentError::InvalidCharacter
+    }
+}
+
+/// Parses the fragment from the given byte string.
+///
+/// On failure, the byte index of the error within the given byte string is returned alongside it.
+pub(crate) fn parse_fragment(value: &[u8]) -> Result<Fragment<'_>, (FragmentError, usize)> {
+    let normalized = scan_fragment(value)?;
+
+    // Unsafe: The scan above makes sure the byte string is valid ASCII-US.
+    Ok(Fragment {
+        fragment: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
+        normalized,
+    })
+}
+
+/// Validates the fragment in the given byte string and returns whether it is normalized.
+const fn scan_fragment(value: &[u8]) -> Result<bool, (FragmentError, usize)> {
+    let mut index = 0;
+    let mut normalized = true;
+
+    while index < value.len() {
+        match FRAGMENT_CHAR_MAP[value[index] as usize] {
+            0 => return Err((FragmentError::InvalidCharacter, index)),
+            b'%' => match get_percent_encoded_value_at(value, index) {
+                Ok((hex_value, uppercase)) => {
+                    if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
+                        normalized = false;
+                    }
+
+                    index += 3;
+                }
+                Err(_) => return Err((FragmentError::InvalidPercentEncoding, index)),
+            },
+            _ => index += 1,
+        }
+    }
+
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fragment_encode() {
+        fn test_case(value: &str, expected: &str) {
+            let fragment = Fragment::encode(value);
+            assert_eq!(fragment, expected);
+            assert_eq!(fragment.as_decoded(), value);
+            assert!(fragment.is_normalized());
+            assert_eq!(Fragment::try_from(fragment.as_str()).unwrap(), fragment);
+        }
+
+        test_case("", "");
+        test_case("section-1", "section-1");
+        test_case("a/b?c", "a/b?c");
+        test_case(" #%", "%20%23%25");
+        test_case("[\u{fc}]", "%5B%C3%BC%5D");
+    }
+
+    #[test]
+    fn test_fragment_from_static() {
+        fn test_case(value: &'static str) {
+            let fragment = Fragment::from_static(value);
+            let expected = Fragment::try_from(value).unwrap();
+            assert_eq!(fragment, expected);
+            assert_eq!(
+                fragment.is_normalized(),
+                expected.is_normalized(),
+                "{}",
+                value
+            );
+        }
+
+        test_case("");
+        test_case("section-1");
+        test_case("a/b?c");
+        test_case("%41");
+        test_case("%c3%a9");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid fragment percent encoding")]
+    fn test_fragment_from_static_invalid() {
+        Fragment::from_static("%4");
+    }
+
+    #[test]
+    fn test_fragment_json_pointer() {
+        fn test_case(tokens: &[&str], pointer: &str, fragment: &str) {
+            let from_tokens = Fragment::from_json_pointer_tokens(tokens);
+            assert_eq!(from_tokens.as_str(), fragment);
+            assert_eq!(
+                Fragment::from_json_pointer(pointer).unwrap().as_str(),
+                fragment
+            );
+
+            let parsed = Fragment::try_from(fragment).unwrap();
+            assert_eq!(parsed.to_json_pointer().unwrap(), pointer);
+            assert_eq!(parsed.json_pointer_tokens().unwrap(), tokens);
+        }
+
+        test_case(&[], "", "");
+        test_case(&[""], "/", "/");
+        test_case(&["foo", "0"], "/foo/0", "/foo/0");
+        test_case(&["a/b"], "/a~1b", "/a~1b");
+        test_case(&["m~n"], "/m~0n", "/m~0n");
+        test_case(&["c%d"], "/c%d", "/c%25d");
+        test_case(&[" ", "\"", "\u{e9}"], "/ /\"/\u{e9}", "/%20/%22/%C3%A9");
+
+        let fragment = Fragment::try_from("/a~2").unwrap();
+        assert_eq!(
+            fragment.json_pointer_tokens(),
+            Err(JsonPointerError::InvalidEscape)
+        );
+        let fragment = Fragment::try_from("%2Fa").unwrap();
+        assert_eq!(fragment.json_pointer_tokens().unwrap(), vec!["a"]);
+    }
+
+    #[test]
+    fn test_fragment_normalize() {
+        fn test_case(value: &str, expected: &str) {
+            let mut fragment = Fragment::try_from(value).unwrap();
+            fragment.normalize();
+            assert_eq!(fragment, expected);
+        }
+
+        test_case("", "");
+        test_case("%ff", "%FF");
+        test_case("%41", "A");
+    }
+
+    #[test]
+    fn test_fragment_text_directives() {
+        let fragment = Fragment::try_from("section").unwrap();
+        assert!(fragment.text_directives().is_empty());
+
+        let fragment = Fragment::try_from("a:~:b=1&text=x&text=&text=y,z").unwrap();
+        let directives = fragment.text_directives();
+        assert_eq!(
+            directives,
+            vec![
+                TextDirective::new("x").unwrap(),
+                TextDirective::new("y").unwrap().with_end("z")
+            ]
+        );
+
+        let mut fragment = Fragment::try_from("a:~:text=old").unwrap();
+        fragment.set_text_directives(&[
+            TextDirective::new("a-b").unwrap(),
+            TextDirective::new("c").unwrap(),
+        ]);
+        assert_eq!(fragment, "a:~:text=a%2Db&text=c");
+        assert_eq!(fragment.as_str(), "a:~:text=a%2Db&text=c");
+        assert!(!fragment.is_normalized());
+        assert_eq!(fragment.text_directives()[0].start(), "a-b");
+
+        fragment.set_text_directives(&[]);
+        assert_eq!(fragment.as_str(), "a");
+
+        let mut fragment = Fragment::try_from(":~:text=old").unwrap();
+        fragment.set_text_directives(&[]);
+        assert_eq!(fragment.as_str(), "");
+    }
+
+    #[test]
+    fn test_fragment_parse() {
+        use self::FragmentError::*;
+
+        assert_eq!(Fragment::try_from("").unwrap(), "");
+        assert_eq!(Fragment::try_from("fragment").unwrap(), "fragment");
+        assert_eq!(Fragment::try_from("fRaGmEnT").unwrap(), "fRaGmEnT");
+        assert_eq!(Fragment::try_from("%ff%ff%ff%41").unwrap(), "%ff%ff%ff%41");
+
+        assert_eq!(Fragment::try_from(" "), Err(InvalidCharacter));
+        assert_eq!(Fragment::try_from("%"), Err(InvalidPercentEncoding));
+        assert_eq!(Fragment::try_from("%f"), Err(InvalidPercentEncoding));
+        assert_eq!(Fragment::try_from("%zz"), Err(InvalidPercentEncoding));
+    }
+}
@@ -0,0 +1,131 @@
+//! JSON Pointers
+//!
+//! See [[RFC6901, Section 6](https://tools.ietf.org/html/rfc6901#section-6)].
+//!
+//! A JSON pointer such as `"/definitions/a~1b"` can be represented as a fragment, in which case it
+//! is additionally percent-encoded. This module handles both the `~` escaping of the pointer's
+//! reference tokens and the percent-encoding of the fragment.
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use super::FRAGMENT_ENCODE_CHAR_MAP;
+use crate::utility::{percent_decode, percent_encode};
+
+/// Percent-decodes the fragment and validates that it is a JSON pointer.
+pub(super) fn decode(fragment: &str) -> Result<Cow<'_, str>, JsonPointerError> {
+    let pointer = percent_decode(fragment);
+    validate(&pointer)?;
+    Ok(pointer)
+}
+
+/// Percent-encodes the validated JSON pointer so that it can be used as a fragment.
+pub(super) fn encode(pointer: &str) -> Result<String, JsonPointerError> {
+    validate(pointer)?;
+
+    let mut fragment = String::with_capacity(pointer.len());
+    percent_encode(pointer.as_bytes(), &FRAGMENT_ENCODE_CHAR_MAP, &mut fragment);
+    Ok(fragment)
+}
+
+/// Escapes the reference token and appends it to the JSON pointer.
+pub(super) fn push_reference_token(pointer: &mut String, token: &str) {
+    pointer.push('/');
+
+    for character in token.chars() {
+        match character {
+            '~' => pointer.push_str("~0"),
+            '/' => pointer.push_str("~1"),
+            _ => pointer.push(character),
+        }
+    }
+}
+
+/// Splits the validated JSON pointer into its unescaped reference tokens.
+pub(super) fn reference_tokens(pointer: &str) -> Vec<String> {
+    if pointer.is_empty() {
+        return Vec::new();
+    }
+
+    pointer[1..]
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+/// Validates that the given string is a JSON pointer.
+fn validate(pointer: &str) -> Result<(), JsonPointerError> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return Err(JsonPointerError::MissingLeadingSlash);
+    }
+
+    let mut characters = pointer.chars();
+
+    while let Some(character) = characters.next() {
+        if character == '~' {
+            match characters.next() {
+                Some('0') | Some('1') => (),
+                _ => return Err(JsonPointerError::InvalidEscape),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// An error representing an invalid JSON pointer.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum JsonPointerError {
+    /// The JSON pointer contained a `~` that was not followed by `0` or `1`.
+    InvalidEscape,
+
+    /// The JSON pointer was not empty and did not start with a `/`.
+    MissingLeadingSlash,
+}
+
+impl Display for JsonPointerError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::JsonPointerError::*;
+
+        match self {
+            InvalidEscape => write!(formatter, "invalid JSON pointer escape"),
+            MissingLeadingSlash => write!(formatter, "JSON pointer missing leading slash"),
+        }
+    }
+}
+
+impl Error for JsonPointerError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_json_pointer_reference_tokens() {
+        let tokens: Vec<String> = Vec::new();
+        assert_eq!(reference_tokens(""), tokens);
+        assert_eq!(reference_tokens("/"), vec![""]);
+        assert_eq!(reference_tokens("/a/0/"), vec!["a", "0", ""]);
+        assert_eq!(reference_tokens("/a~1b/m~0n/~01"), vec!["a/b", "m~n", "~1"]);
+
+        let mut pointer = String::new();
+        push_reference_token(&mut pointer, "a/b");
+        push_reference_token(&mut pointer, "~1");
+        assert_eq!(pointer, "/a~1b/~01");
+        assert_eq!(reference_tokens(&pointer), vec!["a/b", "~1"]);
+    }
+
+    #[test]
+    fn test_json_pointer_validate() {
+        use self::JsonPointerError::*;
+
+        assert_eq!(validate(""), Ok(()));
+        assert_eq!(validate("/"), Ok(()));
+        assert_eq!(validate("/a~0~1"), Ok(()));
+        assert_eq!(validate("a"), Err(MissingLeadingSlash));
+        assert_eq!(validate("/~"), Err(InvalidEscape));
+        assert_eq!(validate("/~2"), Err(InvalidEscape));
+    }
+}
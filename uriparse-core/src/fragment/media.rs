@@ -0,0 +1,441 @@
+//! Media Fragments
+//!
+//! See [[Media Fragments URI 1.0](https://www.w3.org/TR/media-frags/)].
+//!
+//! This module parses the temporal (`t`), spatial (`xywh`), track (`track`), and id (`id`)
+//! dimensions out of a fragment such as `"t=10,20&xywh=160,120,320,240"`. As required by the
+//! specification, unknown dimensions and dimensions with invalid values are ignored, and if a
+//! dimension other than `track` appears multiple times, only its last valid occurrence is used.
+//!
+//! Only the normal play time (NPT) format of the temporal dimension is supported. Temporal
+//! dimensions using the SMPTE or wall-clock formats are ignored.
+
+use std::time::Duration;
+
+use super::Fragment;
+use crate::utility::percent_decode;
+
+/// The dimensions of a media fragment parsed from a [`Fragment`].
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct MediaFragment {
+    /// The name of the selected media fragment, if any.
+    id: Option<String>,
+
+    /// The selected spatial region, if any.
+    space: Option<SpatialSelector>,
+
+    /// The selected temporal interval, if any.
+    time: Option<TemporalSelector>,
+
+    /// The names of the selected tracks.
+    tracks: Vec<String>,
+}
+
+impl MediaFragment {
+    /// Returns the name of the selected media fragment, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::fragment::media::MediaFragment;
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("id=Chapter%202").unwrap();
+    /// let media = MediaFragment::from_fragment(&fragment);
+    /// assert_eq!(media.id(), Some("Chapter 2"));
+    /// ```
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Parses the media fragment dimensions out of the given fragment.
+    ///
+    /// This never fails, since unknown or invalid dimensions are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use std::time::Duration;
+    ///
+    /// use uriparse::fragment::media::{MediaFragment, SpatialUnit};
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("t=10,20&xywh=percent:25,25,50,50").unwrap();
+    /// let media = MediaFragment::from_fragment(&fragment);
+    ///
+    /// let time = media.time().unwrap();
+    /// assert_eq!(time.start(), Duration::from_secs(10));
+    /// assert_eq!(time.end(), Some(Duration::from_secs(20)));
+    ///
+    /// let space = media.space().unwrap();
+    /// assert_eq!(space.unit(), SpatialUnit::Percent);
+    /// assert_eq!((space.x(), space.y(), space.width(), space.height()), (25, 25, 50, 50));
+    /// ```
+    pub fn from_fragment(fragment: &Fragment) -> MediaFragment {
+        let mut media = MediaFragment::default();
+
+        for pair in fragment.as_str().split('&') {
+            let (name, value) = match pair.find('=') {
+                Some(index) => (&pair[..index], &pair[index + 1..]),
+                None => continue,
+            };
+            let name = percent_decode(name);
+            let value = percent_decode(value);
+
+            match &*name {
+                "id" => media.id = Some(value.into_owned()),
+                "t" => {
+                    if let Some(time) = TemporalSelector::parse(&value) {
+                        media.time = Some(time);
+                    }
+                }
+                "track" => media.tracks.push(value.into_owned()),
+                "xywh" => {
+                    if let Some(space) = SpatialSelector::parse(&value) {
+                        media.space = Some(space);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        media
+    }
+
+    /// Returns whether no dimensions were selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::fragment::media::MediaFragment;
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("section-2").unwrap();
+    /// assert!(MediaFragment::from_fragment(&fragment).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.space.is_none() && self.time.is_none() && self.tracks.is_empty()
+    }
+
+    /// Returns the selected spatial region, if any.
+    pub fn space(&self) -> Option<&SpatialSelector> {
+        self.space.as_ref()
+    }
+
+    /// Returns the selected temporal interval, if any.
+    pub fn time(&self) -> Option<&TemporalSelector> {
+        self.time.as_ref()
+    }
+
+    /// Returns the names of the selected tracks in the order they appear.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::fragment::media::MediaFragment;
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("track=audio&track=sub%20titles").unwrap();
+    /// let media = MediaFragment::from_fragment(&fragment);
+    /// assert_eq!(media.tracks(), &["audio", "sub titles"]);
+    /// ```
+    pub fn tracks(&self) -> &[String] {
+        &self.tracks
+    }
+}
+
+/// A spatial region selected by the `xywh` dimension.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SpatialSelector {
+    /// The height of the region.
+    height: u32,
+
+    /// The unit of the region's values.
+    unit: SpatialUnit,
+
+    /// The width of the region.
+    width: u32,
+
+    /// The horizontal offset of the region from the left edge.
+    x: u32,
+
+    /// The vertical offset of the region from the top edge.
+    y: u32,
+}
+
+impl SpatialSelector {
+    /// Returns the height of the region.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Parses the value of an `xywh` dimension.
+    fn parse(value: &str) -> Option<SpatialSelector> {
+        let (unit, value) = if let Some(value) = value.strip_prefix("pixel:") {
+            (SpatialUnit::Pixel, value)
+        } else if let Some(value) = value.strip_prefix("percent:") {
+            (SpatialUnit::Percent, value)
+        } else {
+            (SpatialUnit::Pixel, value)
+        };
+
+        let mut values = value.split(',').map(parse_digits);
+        let x = values.next()??;
+        let y = values.next()??;
+        let width = values.next()??;
+        let height = values.next()??;
+
+        if values.next().is_some() || width == 0 || height == 0 {
+            return None;
+        }
+
+        if unit == SpatialUnit::Percent
+            && (x.checked_add(width)? > 100 || y.checked_add(height)? > 100)
+        {
+            return None;
+        }
+
+        Some(SpatialSelector {
+            height,
+            unit,
+            width,
+            x,
+            y,
+        })
+    }
+
+    /// Returns the unit of the region's values.
+    pub fn unit(&self) -> SpatialUnit {
+        self.unit
+    }
+
+    /// Returns the width of the region.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the horizontal offset of the region from the left edge.
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    /// Returns the vertical offset of the region from the top edge.
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+}
+
+/// The unit of the values of a [`SpatialSelector`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SpatialUnit {
+    /// The values are percentages of the media's dimensions.
+    Percent,
+
+    /// The values are in pixels. This is the default if no unit is given.
+    Pixel,
+}
+
+/// A temporal interval selected by the `t` dimension using the normal play time format.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TemporalSelector {
+    /// The end of the interval, if any.
+    end: Option<Duration>,
+
+    /// The start of the interval.
+    start: Duration,
+}
+
+impl TemporalSelector {
+    /// Returns the end of the interval.
+    ///
+    /// If this is [`None`], the interval extends to the end of the media.
+    pub fn end(&self) -> Option<Duration> {
+        self.end
+    }
+
+    /// Parses the value of a `t` dimension.
+    fn parse(value: &str) -> Option<TemporalSelector> {
+        let value = value.strip_prefix("npt:").unwrap_or(value);
+
+        let (start, end) = match value.find(',') {
+            Some(index) => (&value[..index], Some(&value[index + 1..])),
+            None => (value, None),
+        };
+
+        let start = match start {
+            "" if end.is_some() => Duration::from_secs(0),
+            _ => parse_npt_time(start)?,
+        };
+        let end = match end {
+            Some(end) => Some(parse_npt_time(end)?),
+            None => None,
+        };
+
+        match end {
+            Some(end) if end <= start => None,
+            _ => Some(TemporalSelector { end, start }),
+        }
+    }
+
+    /// Returns the start of the interval.
+    pub fn start(&self) -> Duration {
+        self.start
+    }
+}
+
+/// Parses a non-empty string of ASCII digits.
+fn parse_digits(value: &str) -> Option<u32> {
+    if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        None
+    } else {
+        value.parse().ok()
+    }
+}
+
+/// Parses a normal play time in one of the `"ss"`, `"mm:ss"`, or `"hh:mm:ss"` forms with an
+/// optional fraction of seconds.
+fn parse_npt_time(value: &str) -> Option<Duration> {
+    let (value, fraction) = match value.find('.') {
+        Some(index) => (&value[..index], &value[index + 1..]),
+        None => (value, ""),
+    };
+
+    if !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let nanoseconds = fraction
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(9)
+        .fold(0, |nanoseconds, digit| {
+            nanoseconds * 10 + u32::from(digit - b'0')
+        });
+
+    let parts: Vec<&str> = value.split(':').collect();
+    let seconds = match parts.as_slice() {
+        [seconds] => u64::from(parse_digits(seconds)?),
+        [minutes, seconds] => parse_minutes_and_seconds(minutes, seconds)?,
+        [hours, minutes, seconds] => u64::from(parse_digits(hours)?)
+            .checked_mul(3600)?
+            .checked_add(parse_minutes_and_seconds(minutes, seconds)?)?,
+        _ => return None,
+    };
+
+    Some(Duration::new(seconds, nanoseconds))
+}
+
+/// Parses the two-digit minutes and seconds of a normal play time into seconds.
+fn parse_minutes_and_seconds(minutes: &str, seconds: &str) -> Option<u64> {
+    if minutes.len() != 2 || seconds.len() != 2 {
+        return None;
+    }
+
+    let minutes = parse_digits(minutes)?;
+    let seconds = parse_digits(seconds)?;
+
+    if minutes > 59 || seconds > 59 {
+        None
+    } else {
+        Some(u64::from(minutes * 60 + seconds))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    fn parse(value: &str) -> MediaFragment {
+        MediaFragment::from_fragment(&Fragment::try_from(value).unwrap())
+    }
+
+    #[test]
+    fn test_media_fragment_parse() {
+        let media = parse("t=npt:10.5,1:02:03&xywh=160,120,320,240&track=a&id=x&track=b&other=1");
+        let time = media.time().unwrap();
+        assert_eq!(time.start(), Duration::from_millis(10_500));
+        assert_eq!(time.end(), Some(Duration::from_secs(3723)));
+        let space = media.space().unwrap();
+        assert_eq!(space.unit(), SpatialUnit::Pixel);
+        assert_eq!(
+            (space.x(), space.y(), space.width(), space.height()),
+            (160, 120, 320, 240)
+        );
+        assert_eq!(media.tracks(), &["a", "b"]);
+        assert_eq!(media.id(), Some("x"));
+
+        assert!(parse("").is_empty());
+        assert!(parse("t").is_empty());
+        assert!(parse("unknown=1").is_empty());
+    }
+
+    #[test]
+    fn test_media_fragment_parse_last_valid() {
+        let media = parse("t=10&t=invalid&xywh=1,2,3,4&xywh=pixel:5,6,7,8&id=a&id=b");
+        assert_eq!(media.time().unwrap().start(), Duration::from_secs(10));
+        assert_eq!(media.space().unwrap().x(), 5);
+        assert_eq!(media.id(), Some("b"));
+    }
+
+    #[test]
+    fn test_spatial_selector_parse() {
+        fn test_case(value: &str, expected: Option<(SpatialUnit, u32, u32, u32, u32)>) {
+            let space = SpatialSelector::parse(value).map(|space| {
+                (
+                    space.unit(),
+                    space.x(),
+                    space.y(),
+                    space.width(),
+                    space.height(),
+                )
+            });
+            assert_eq!(space, expected);
+        }
+
+        use self::SpatialUnit::*;
+
+        test_case("1,2,3,4", Some((Pixel, 1, 2, 3, 4)));
+        test_case("pixel:1,2,3,4", Some((Pixel, 1, 2, 3, 4)));
+        test_case("percent:0,0,100,100", Some((Percent, 0, 0, 100, 100)));
+        test_case("percent:50,0,51,10", None);
+        test_case("1,2,3", None);
+        test_case("1,2,3,4,5", None);
+        test_case("1,2,0,4", None);
+        test_case("-1,2,3,4", None);
+        test_case("em:1,2,3,4", None);
+    }
+
+    #[test]
+    fn test_temporal_selector_parse() {
+        fn test_case(value: &str, expected: Option<(Duration, Option<Duration>)>) {
+            let time = TemporalSelector::parse(value).map(|time| (time.start(), time.end()));
+            assert_eq!(time, expected);
+        }
+
+        let seconds = Duration::from_secs;
+
+        test_case("10", Some((seconds(10), None)));
+        test_case("10,20", Some((seconds(10), Some(seconds(20)))));
+        test_case(",20", Some((seconds(0), Some(seconds(20)))));
+        test_case("npt:01:30", Some((seconds(90), None)));
+        test_case("0:00:01.25", Some((Duration::from_millis(1250), None)));
+        test_case("5.", Some((seconds(5), None)));
+        test_case("20,10", None);
+        test_case("10,10", None);
+        test_case("", None);
+        test_case(",", None);
+        test_case("1:5", None);
+        test_case("00:60", None);
+        test_case("smpte:0:00:01", None);
+        test_case("1.2.3", None);
+    }
+}
@@ -0,0 +1,324 @@
+//! Text Fragments
+//!
+//! See [[URL Fragment Text Directives](https://wicg.github.io/scroll-to-text-fragment/)].
+//!
+//! A text fragment is a fragment directive of the form `text=[prefix-,]start[,end][,-suffix]`
+//! that appears after the fragment directive delimiter `:~:`, e.g.
+//! `"section:~:text=quick%20brown,fox"`. Multiple directives are separated by `&`.
+
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+
+use super::Fragment;
+use crate::utility::{percent_decode, percent_encode};
+
+/// The delimiter that separates the fragment from the fragment directives.
+pub(super) const FRAGMENT_DIRECTIVE_DELIMITER: &str = ":~:";
+
+/// A map of byte characters that can be left unencoded in the components of a text directive.
+///
+/// This is the same as the fragment characters except that `'%'`, `'&'`, `','`, and `'-'` are
+/// excluded since they are either the percent-encoding character or separators within directives.
+#[rustfmt::skip]
+const TEXT_DIRECTIVE_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$',    0,    0,b'\'', b'(', b')', b'*', b'+',    0,    0, b'.', b'/', // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0, b'=',    0, b'?', // 3
+ b'@', b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A text directive as found in a text fragment.
+///
+/// All of the components are stored percent-decoded. The [`Display`] implementation writes the
+/// directive in its percent-encoded form, e.g. `"text=prefix-,start,end,-suffix"`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TextDirective {
+    /// The text that ends the match, if the match is a range.
+    end: Option<String>,
+
+    /// The text that must immediately precede the match, if any.
+    prefix: Option<String>,
+
+    /// The text that starts the match, or the entire match if there is no end.
+    start: String,
+
+    /// The text that must immediately follow the match, if any.
+    suffix: Option<String>,
+}
+
+impl TextDirective {
+    /// Returns the text that ends the match, if the match is a range.
+    pub fn end(&self) -> Option<&str> {
+        self.end.as_deref()
+    }
+
+    /// Constructs a new text directive that matches the given text.
+    ///
+    /// [`None`] is returned if the text is empty, since such a directive could not be parsed back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::TextDirective;
+    ///
+    /// let directive = TextDirective::new("an example").unwrap().with_suffix("text");
+    /// assert_eq!(directive.to_string(), "text=an%20example,-text");
+    /// assert!(TextDirective::new("").is_none());
+    /// ```
+    pub fn new<T>(start: T) -> Option<Self>
+    where
+        T: Into<String>,
+    {
+        let start = start.into();
+
+        if start.is_empty() {
+            return None;
+        }
+
+        Some(TextDirective {
+            end: None,
+            prefix: None,
+            start,
+            suffix: None,
+        })
+    }
+
+    /// Parses the value of a `text=` directive, returning [`None`] if it is invalid.
+    pub(super) fn parse(value: &str) -> Option<TextDirective> {
+        let mut components: Vec<&str> = value.split(',').collect();
+
+        let prefix = match components.first() {
+            Some(first) if first.ends_with('-') => {
+                let prefix = &first[..first.len() - 1];
+                components.remove(0);
+                Some(prefix)
+            }
+            _ => None,
+        };
+        let suffix = match components.last() {
+            Some(last) if last.starts_with('-') => {
+                let suffix = &last[1..];
+                components.pop();
+                Some(suffix)
+            }
+            _ => None,
+        };
+
+        let (start, end) = match components.as_slice() {
+            [start] => (*start, None),
+            [start, end] => (*start, Some(*end)),
+            _ => return None,
+        };
+
+        let decode = |component: &str| {
+            if component.is_empty() {
+                None
+            } else {
+                Some(percent_decode(component).into_owned())
+            }
+        };
+
+        Some(TextDirective {
+            end: match end {
+                Some(end) => Some(decode(end)?),
+                None => None,
+            },
+            prefix: match prefix {
+                Some(prefix) => Some(decode(prefix)?),
+                None => None,
+            },
+            start: decode(start)?,
+            suffix: match suffix {
+                Some(suffix) => Some(decode(suffix)?),
+                None => None,
+            },
+        })
+    }
+
+    /// Returns the text that must immediately precede the match, if any.
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// Returns the text that starts the match, or the entire match if there is no end.
+    pub fn start(&self) -> &str {
+        &self.start
+    }
+
+    /// Returns the text that must immediately follow the match, if any.
+    pub fn suffix(&self) -> Option<&str> {
+        self.suffix.as_deref()
+    }
+
+    /// Consumes the directive and returns one that matches the range from its start to the given
+    /// text. An empty text removes the end instead, since it could not be parsed back.
+    pub fn with_end<T>(mut self, end: T) -> Self
+    where
+        T: Into<String>,
+    {
+        let end = end.into();
+        self.end = Some(end).filter(|end| !end.is_empty());
+        self
+    }
+
+    /// Consumes the directive and returns one whose match must be immediately preceded by the given
+    /// text. An empty text removes the prefix instead, since it could not be parsed back.
+    pub fn with_prefix<T>(mut self, prefix: T) -> Self
+    where
+        T: Into<String>,
+    {
+        let prefix = prefix.into();
+        self.prefix = Some(prefix).filter(|prefix| !prefix.is_empty());
+        self
+    }
+
+    /// Consumes the directive and returns one whose match must be immediately followed by the given
+    /// text. An empty text removes the suffix instead, since it could not be parsed back.
+    pub fn with_suffix<T>(mut self, suffix: T) -> Self
+    where
+        T: Into<String>,
+    {
+        let suffix = suffix.into();
+        self.suffix = Some(suffix).filter(|suffix| !suffix.is_empty());
+        self
+    }
+}
+
+impl Display for TextDirective {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        fn encode(value: &str) -> String {
+            let mut encoded = String::with_capacity(value.len());
+            percent_encode(value.as_bytes(), &TEXT_DIRECTIVE_CHAR_MAP, &mut encoded);
+            encoded
+        }
+
+        formatter.write_str("text=")?;
+
+        if let Some(prefix) = &self.prefix {
+            write!(formatter, "{}-,", encode(prefix))?;
+        }
+
+        formatter.write_str(&encode(&self.start))?;
+
+        if let Some(end) = &self.end {
+            write!(formatter, ",{}", encode(end))?;
+        }
+
+        if let Some(suffix) = &self.suffix {
+            write!(formatter, ",-{}", encode(suffix))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Constructs a fragment consisting of the given fragment followed by the given text directives.
+///
+/// If there are no directives, the fragment directive delimiter is omitted as well.
+pub(super) fn build_fragment<'directive, I>(fragment: &str, directives: I) -> Fragment<'static>
+where
+    I: IntoIterator<Item = &'directive TextDirective>,
+{
+    let mut value = String::from(fragment);
+
+    for (index, directive) in directives.into_iter().enumerate() {
+        if index == 0 {
+            value.push_str(FRAGMENT_DIRECTIVE_DELIMITER);
+        } else {
+            value.push('&');
+        }
+
+        value.push_str(&directive.to_string());
+    }
+
+    Fragment::try_from(value.as_str())
+        .expect("text directives should always form a valid fragment")
+        .into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_text_directive_display() {
+        fn test_case(directive: TextDirective, expected: &str) {
+            assert_eq!(directive.to_string(), expected);
+            let value = &expected["text=".len()..];
+            assert_eq!(TextDirective::parse(value), Some(directive));
+        }
+
+        test_case(TextDirective::new("word").unwrap(), "text=word");
+        test_case(
+            TextDirective::new("a-b, c&d").unwrap().with_end("%"),
+            "text=a%2Db%2C%20c%26d,%25",
+        );
+        test_case(
+            TextDirective::new("start")
+                .unwrap()
+                .with_prefix("pre")
+                .with_end("end")
+                .with_suffix("suf"),
+            "text=pre-,start,end,-suf",
+        );
+    }
+
+    #[test]
+    fn test_text_directive_new() {
+        assert_eq!(TextDirective::new(""), None);
+
+        let directive = TextDirective::new("a").unwrap();
+        assert_eq!(
+            directive
+                .clone()
+                .with_prefix("")
+                .with_end("")
+                .with_suffix(""),
+            directive
+        );
+    }
+
+    #[test]
+    fn test_text_directive_parse() {
+        fn test_case(value: &str, expected: Option<TextDirective>) {
+            assert_eq!(TextDirective::parse(value), expected);
+        }
+
+        let start = || TextDirective::new("start").unwrap();
+
+        test_case("start", Some(start()));
+        test_case("start,end", Some(start().with_end("end")));
+        test_case("pre-,start", Some(start().with_prefix("pre")));
+        test_case("start,-suf", Some(start().with_suffix("suf")));
+        test_case(
+            "pre-,start,end,-suf",
+            Some(
+                start()
+                    .with_prefix("pre")
+                    .with_end("end")
+                    .with_suffix("suf"),
+            ),
+        );
+        test_case("a%20b%2C", TextDirective::new("a b,"));
+        test_case("", None);
+        test_case("pre-", None);
+        test_case("-suf", None);
+        test_case("pre-,-suf", None);
+        test_case("a,b,c", None);
+        test_case("a,", None);
+        test_case("-,a", None);
+    }
+}
@@ -0,0 +1,54 @@
+mod utility;
+
+#[doc(hidden)]
+pub mod __private;
+pub mod absolute_uri;
+pub mod authority;
+pub mod error;
+pub mod fragment;
+pub mod normalized_uri;
+pub mod origin;
+pub mod path;
+pub mod query;
+pub mod relative_reference;
+pub mod scheme;
+pub mod typed_uri_builder;
+pub mod uri;
+pub mod uri_reference;
+#[cfg(feature = "whatwg")]
+pub mod whatwg;
+
+pub use self::absolute_uri::{AbsoluteURI, AbsoluteURIError};
+#[cfg(feature = "idna")]
+pub use self::authority::idna::IdnaError;
+pub use self::authority::{
+    Authority, AuthorityBuilder, AuthorityError, AuthorityParseOptions, Host, HostError,
+    HostnameError, IPvFuture, Password, PasswordError, PortError, RegisteredName,
+    RegisteredNameError, Username, UsernameError,
+};
+pub use self::error::Error;
+pub use self::fragment::text::TextDirective;
+pub use self::fragment::{Fragment, FragmentError};
+pub use self::normalized_uri::NormalizedURI;
+pub use self::origin::Origin;
+pub use self::path::{
+    Ancestors, MatrixParameters, Path, PathBufError, PathBufOptions, PathBuilder, PathError,
+    PathNormalizeOptions, Segment,
+};
+#[cfg(feature = "serde-query")]
+pub use self::query::QueryDeserializeError;
+pub use self::query::{
+    Query, QueryError, QueryLimits, QueryNormalizationPolicy, QueryPairSeparator,
+};
+pub use self::relative_reference::{
+    RelativeReference, RelativeReferenceBuilder, RelativeReferenceError,
+};
+pub use self::scheme::{Scheme, SchemeError, SchemeStatus, UnregisteredScheme};
+pub use self::typed_uri_builder::{HasAuthority, NoAuthority, TypedURIBuilder};
+pub use self::uri::{Equivalence, Normalizer, URIBuilder, URIError, URI};
+pub use self::uri_reference::{
+    Component, DisplayOptions, LenientFixes, ParseError, URIReference, URIReferenceBuilder,
+    URIReferenceError,
+};
+#[cfg(feature = "whatwg")]
+pub use self::whatwg::{Divergence, WhatwgError};
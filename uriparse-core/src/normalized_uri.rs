@@ -0,0 +1,165 @@
+//! Normalized URIs
+//!
+//! See [[RFC3986, Section 6](https://tools.ietf.org/html/rfc3986#section-6)].
+//!
+//! The [`Eq`] and [`Hash`] implementations of [`URI`] compare components, which already ignores
+//! differences such as the case of percent-encodings, but still distinguishes URIs that only differ
+//! in e.g. the case of the host, dot segments, or an explicit default port. A [`NormalizedURI`]
+//! applies scheme-based normalization when it is constructed, so that equivalent URIs are equal and
+//! hash the same, which makes it usable as a key for deduplicating URIs.
+
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+
+use crate::uri::{URIError, URI};
+
+/// A URI that is compared and hashed after scheme-based normalization.
+///
+/// Two normalized URIs are equal exactly if their URIs are equivalent according to
+/// [`Equivalence::SchemeBased`](crate::Equivalence::SchemeBased), i.e. if they are identical after
+/// [`URI::normalize_scheme_based`]. This includes the case of the scheme and the host,
+/// percent-encodings of unreserved characters, dot segments in the path, and default ports. The set
+/// of normalizations may be extended in future versions, but only with normalizations that the RFC
+/// considers safe, so URIs that are equal now will remain equal.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use std::convert::TryFrom;
+///
+/// use uriparse::NormalizedURI;
+///
+/// let mut set = HashSet::new();
+/// set.insert(NormalizedURI::try_from("http://example.com/a/b").unwrap());
+/// assert!(!set.insert(NormalizedURI::try_from("HTTP://Example.com:80/a/./%62").unwrap()));
+/// assert!(set.insert(NormalizedURI::try_from("https://example.com/a/b").unwrap()));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct NormalizedURI<'uri> {
+    /// The URI, which is always normalized.
+    uri: URI<'uri>,
+}
+
+impl<'uri> NormalizedURI<'uri> {
+    /// Returns the normalized [`URI`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::NormalizedURI;
+    ///
+    /// let uri = NormalizedURI::try_from("HTTP://EXAMPLE.com:80/%7e").unwrap();
+    /// assert_eq!(uri.as_uri().to_string(), "http://example.com/~");
+    /// ```
+    pub fn as_uri(&self) -> &URI<'uri> {
+        &self.uri
+    }
+
+    /// Converts the [`NormalizedURI`] into an owned copy.
+    ///
+    /// If you construct the normalized URI from a source with a non-static lifetime, you may run
+    /// into lifetime problems due to the way the struct is designed. Calling this function will
+    /// ensure that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the normalized URI will just copy the
+    /// references, and thus the lifetime will remain the same.
+    pub fn into_owned(self) -> NormalizedURI<'static> {
+        NormalizedURI {
+            uri: self.uri.into_owned(),
+        }
+    }
+}
+
+impl Display for NormalizedURI<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        self.uri.fmt(formatter)
+    }
+}
+
+impl<'uri> From<NormalizedURI<'uri>> for String {
+    fn from(value: NormalizedURI<'uri>) -> Self {
+        String::from(value.uri)
+    }
+}
+
+impl<'uri> From<NormalizedURI<'uri>> for URI<'uri> {
+    fn from(value: NormalizedURI<'uri>) -> Self {
+        value.uri
+    }
+}
+
+impl<'uri> From<URI<'uri>> for NormalizedURI<'uri> {
+    fn from(mut value: URI<'uri>) -> Self {
+        value.normalize_scheme_based();
+        NormalizedURI { uri: value }
+    }
+}
+
+impl<'uri> TryFrom<&'uri [u8]> for NormalizedURI<'uri> {
+    type Error = URIError;
+
+    fn try_from(value: &'uri [u8]) -> Result<Self, Self::Error> {
+        URI::try_from(value).map(NormalizedURI::from)
+    }
+}
+
+impl<'uri> TryFrom<&'uri str> for NormalizedURI<'uri> {
+    type Error = URIError;
+
+    fn try_from(value: &'uri str) -> Result<Self, Self::Error> {
+        NormalizedURI::try_from(value.as_bytes())
+    }
+}
+
+impl TryFrom<String> for NormalizedURI<'static> {
+    type Error = URIError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        NormalizedURI::try_from(value.as_str()).map(NormalizedURI::into_owned)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    use super::*;
+    use crate::uri::Equivalence;
+
+    #[test]
+    fn test_normalized_uri_eq_and_hash() {
+        fn hash(uri: &NormalizedURI<'_>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            uri.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        fn test_case(left: &str, right: &str) {
+            let equivalent = URI::try_from(left)
+                .unwrap()
+                .equivalent(&URI::try_from(right).unwrap(), Equivalence::SchemeBased);
+            let left = NormalizedURI::try_from(left).unwrap();
+            let right = NormalizedURI::try_from(right).unwrap();
+            assert_eq!(left == right, equivalent, "{} {}", left, right);
+
+            if equivalent {
+                assert_eq!(hash(&left), hash(&right), "{} {}", left, right);
+            }
+        }
+
+        test_case("http://example.com/", "HTTP://EXAMPLE.COM:80");
+        test_case("http://example.com/~", "http://example.com/%7E");
+        test_case("http://example.com/%C3%BC", "http://example.com/%c3%bc");
+        test_case("http://example.com/a/../b", "http://example.com/b");
+        test_case("http://user@example.com/", "http://%75ser@example.com/");
+        test_case("file:///etc", "file://localhost/etc");
+        test_case("http://example.com/", "https://example.com/");
+        test_case("http://example.com/a", "http://example.com/A");
+        test_case("http://example.com/?a", "http://example.com/?A");
+        test_case("http://example.com:8080/", "http://example.com/");
+    }
+}
@@ -0,0 +1,3430 @@
+//! Path Component
+//!
+//! See [[RFC3986, Section 3.3](https://tools.ietf.org/html/rfc3986#section-3.3)].
+
+pub mod pattern;
+mod segment_list;
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::convert::{Infallible, TryFrom};
+use std::error::Error;
+use std::ffi::{OsStr, OsString};
+use std::fmt::{self, Display, Formatter, Write};
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::ops::Deref;
+use std::path::{self as std_path, Component, PathBuf, Prefix};
+use std::str::{self, FromStr};
+
+use self::segment_list::SegmentList;
+use crate::utility::{
+    get_percent_encoded_value, get_percent_encoded_value_at, normalize_string, percent_decode,
+    percent_decode_bytes, percent_encode, percent_encoded_cmp, percent_encoded_equality,
+    percent_encoded_hash, UNRESERVED_CHAR_MAP,
+};
+
+/// A map of byte characters that determines if a character is a valid path character.
+#[rustfmt::skip]
+const PATH_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$', b'%', b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0, b'=',    0,    0, // 3
+ b'@', b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A map of byte characters that can be left unencoded in the name and the parameters of a segment
+/// with matrix parameters.
+///
+/// This is the same as the segment characters except that `';'` and `'='` are excluded as well.
+#[rustfmt::skip]
+const MATRIX_ENCODE_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$',    0, b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':',    0,    0,    0,    0,    0, // 3
+ b'@', b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A map of byte characters that can be left unencoded in a segment.
+///
+/// This is the same as the path characters except that `'%'` is excluded.
+#[rustfmt::skip]
+const SEGMENT_ENCODE_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$',    0, b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0, b'=',    0,    0, // 3
+ b'@', b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// The path component as defined in
+/// [[RFC3986, Section 3.3](https://tools.ietf.org/html/rfc3986#section-3.3)].
+///
+/// A path is composed of a sequence of segments. It is also either absolute or relative, where an
+/// absolute path starts with a `'/'`. A URI with an authority *always* has an absolute path
+/// regardless of whether the path was empty (i.e. "http://example.com" has a single empty
+/// path segment and is absolute).
+///
+/// Each segment in the path is case-sensitive. Furthermore, percent-encoding plays no role in
+/// equality checking for characters in the unreserved character set meaning that `"segment"` and
+/// `"s%65gment"` are identical. Both of these attributes are reflected in the equality and hash
+/// functions.
+///
+/// Paths are ordered by comparing their segments one after another as with [`Segment`], so a path
+/// is ordered right before the paths it is a prefix of (e.g. `"/a"` < `"/a/b"` < `"/a-b"`). Like
+/// equality checking, ordering does not take into account whether a path is absolute.
+///
+/// However, be aware that just because percent-encoding plays no role in equality checking does not
+/// mean that either the path or a given segment is normalized. If the path or a segment needs to be
+/// normalized, use either the [`Path::normalize`] or [`Segment::normalize`] functions,
+/// respectively.
+#[derive(Clone, Debug, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path<'path> {
+    /// whether the path is absolute. Specifically, a path is absolute if it starts with a
+    /// `'/'`.
+    absolute: bool,
+
+    /// The total number of double dot segments in the path.
+    double_dot_segment_count: u16,
+
+    /// The number of double dot segments consecutive from the beginning of the path.
+    leading_double_dot_segment_count: u16,
+
+    /// The sequence of segments that compose the path.
+    segments: SegmentList<'path>,
+
+    /// The total number of single dot segments in the path.
+    single_dot_segment_count: u16,
+
+    /// The total number of unnormalized segments in the path.
+    unnormalized_count: u16,
+}
+
+impl<'path> Path<'path> {
+    /// Returns an iterator over this path and its ancestors.
+    ///
+    /// The iterator starts with this path and yields each path after removing its last segment,
+    /// ending with the root path `"/"` for absolute paths and the empty path otherwise. Since
+    /// paths with and without a trailing slash identify different resources, a trailing empty
+    /// segment is removed like any other segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/a/b/c").unwrap();
+    /// let ancestors: Vec<_> = path.ancestors().collect();
+    /// assert_eq!(ancestors, vec!["/a/b/c", "/a/b", "/a", "/"]);
+    ///
+    /// let path = Path::try_from("a/b/").unwrap();
+    /// let ancestors: Vec<_> = path.ancestors().collect();
+    /// assert_eq!(ancestors, vec!["a/b/", "a/b", "a", ""]);
+    /// ```
+    pub fn ancestors(&self) -> Ancestors<'_> {
+        Ancestors {
+            next: Some(self.to_borrowed()),
+        }
+    }
+
+    /// Constructs a default builder for a path.
+    ///
+    /// This provides an alternative means of constructing a path besides parsing, from segments
+    /// given as arbitrary unencoded text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::builder()
+    ///     .with_absolute(true)
+    ///     .with_segments(vec!["users", "jane doe", "a/b"])
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(path, "/users/jane%20doe/a%2Fb");
+    /// ```
+    pub fn builder() -> PathBuilder {
+        PathBuilder::default()
+    }
+
+    /// Clears all segments from the path leaving a single empty segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/my/path").unwrap();
+    /// assert_eq!(path, "/my/path");
+    /// path.clear();
+    /// assert_eq!(path, "/");
+    /// ```
+    pub fn clear(&mut self) {
+        self.segments.clear();
+        self.segments.push(Segment::empty());
+    }
+
+    /// Returns an iterator over the percent-decoded bytes of each segment of the path.
+    ///
+    /// Unlike [`Path::decoded_segments`], this works for segments that do not decode to valid
+    /// UTF-8. See [`Segment::as_decoded_bytes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/files/%FF").unwrap();
+    /// let segments: Vec<_> = path.decoded_segment_bytes().collect();
+    /// assert_eq!(segments, [&b"files"[..], &b"\xff"[..]]);
+    /// ```
+    pub fn decoded_segment_bytes(&self) -> impl Iterator<Item = Cow<'_, [u8]>> {
+        self.segments.iter().map(Segment::as_decoded_bytes)
+    }
+
+    /// Returns an iterator over the percent-decoded segments of the path.
+    ///
+    /// Since each segment is decoded on its own, a percent-encoded `'/'` (i.e. `"%2F"`) is part of
+    /// the decoded segment it occurs in and does not split it. See [`Segment::as_decoded`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/users/jane%20doe/a%2Fb").unwrap();
+    /// let segments: Vec<_> = path.decoded_segments().collect();
+    /// assert_eq!(segments, ["users", "jane doe", "a/b"]);
+    /// ```
+    pub fn decoded_segments(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        self.segments.iter().map(Segment::as_decoded)
+    }
+
+    /// Constructs a path from arbitrary unencoded text.
+    ///
+    /// The text is split into segments on `'/'`, and each segment is percent-encoded as with
+    /// [`Segment::encode`], so the path decodes back to the given text. A leading `'/'` makes the
+    /// path absolute. Use [`Path::from_segments`] if the segments themselves may contain `'/'`.
+    ///
+    /// An error is returned if the path would have too many segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::encode("/my files/100%?.txt").unwrap();
+    /// assert_eq!(path, "/my%20files/100%25%3F.txt");
+    /// assert_eq!(path.segments().len(), 2);
+    /// ```
+    pub fn encode(value: &str) -> Result<Path<'static>, PathError> {
+        match value.strip_prefix('/') {
+            Some(value) => Path::from_segments(value.split('/'), true),
+            None => Path::from_segments(value.split('/'), false),
+        }
+    }
+
+    /// Ensures that the path ends with a `'/'` by pushing an empty segment if necessary.
+    ///
+    /// The empty relative path is left unchanged since it has no segment to end with a `'/'`.
+    ///
+    /// An error is returned if the path already has the maximum number of segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/docs").unwrap();
+    /// path.ensure_trailing_slash().unwrap();
+    /// assert_eq!(path, "/docs/");
+    /// path.ensure_trailing_slash().unwrap();
+    /// assert_eq!(path, "/docs/");
+    /// ```
+    pub fn ensure_trailing_slash(&mut self) -> Result<(), PathError> {
+        if self.has_trailing_slash() || (!self.absolute && self.is_empty_path()) {
+            return Ok(());
+        }
+
+        self.push(Segment::empty())
+    }
+
+    /// Returns the extension of the file name of the path, see [`Path::file_name`].
+    ///
+    /// The extension is the part of the percent-decoded file name after its last `'.'`. A file
+    /// name without a `'.'` or whose only `'.'` is its first character (e.g. `".htaccess"`) has no
+    /// extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/assets/app.min.js").unwrap();
+    /// assert_eq!(path.extension().unwrap(), "js");
+    ///
+    /// let path = Path::try_from("/assets/archive%2Etar").unwrap();
+    /// assert_eq!(path.extension().unwrap(), "tar");
+    ///
+    /// let path = Path::try_from("/.htaccess").unwrap();
+    /// assert!(path.extension().is_none());
+    /// ```
+    pub fn extension(&self) -> Option<Cow<'_, str>> {
+        let file_name = self.file_name()?;
+        let index = split_extension(&file_name)?;
+
+        Some(match file_name {
+            Cow::Borrowed(file_name) => Cow::Borrowed(&file_name[index + 1..]),
+            Cow::Owned(file_name) => Cow::Owned(file_name[index + 1..].to_string()),
+        })
+    }
+
+    /// Returns the percent-decoded file name of the path, i.e. its last non-empty segment.
+    ///
+    /// A trailing `'/'` is ignored, so the file name of `"/docs/"` is `"docs"`. [`None`] is
+    /// returned if the path has no non-empty segments or if the last one is a dot segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/docs/my%20file.txt").unwrap();
+    /// assert_eq!(path.file_name().unwrap(), "my file.txt");
+    ///
+    /// let path = Path::try_from("/docs/").unwrap();
+    /// assert_eq!(path.file_name().unwrap(), "docs");
+    ///
+    /// let path = Path::try_from("/docs/..").unwrap();
+    /// assert!(path.file_name().is_none());
+    /// ```
+    pub fn file_name(&self) -> Option<Cow<'_, str>> {
+        self.file_name_index()
+            .map(|index| self.segments[index].as_decoded())
+    }
+
+    /// Returns the index of the segment containing the file name of the path.
+    fn file_name_index(&self) -> Option<usize> {
+        let index = self
+            .segments
+            .iter()
+            .rposition(|segment| !segment.is_empty())?;
+
+        if self.segments[index].is_dot_segment() {
+            None
+        } else {
+            Some(index)
+        }
+    }
+
+    /// Constructs a path from segments given as arbitrary unencoded text.
+    ///
+    /// Each segment is percent-encoded as with [`Segment::encode`], so characters such as `'/'`,
+    /// `'?'`, and `'#'` as well as non-ASCII characters cannot change the structure of the path.
+    /// However, `"."` and `".."` are still dot segments. This is a shorthand for using
+    /// [`PathBuilder`], and errors if the path would have too many segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::from_segments(vec!["files", "a/b?c#d", "grüße"], true).unwrap();
+    /// assert_eq!(path, "/files/a%2Fb%3Fc%23d/gr%C3%BC%C3%9Fe");
+    /// assert_eq!(path.segments().len(), 3);
+    ///
+    /// let path = Path::from_segments(Vec::<&str>::new(), false).unwrap();
+    /// assert_eq!(path, "");
+    /// ```
+    pub fn from_segments<TSegments, TSegment>(
+        segments: TSegments,
+        absolute: bool,
+    ) -> Result<Path<'static>, PathError>
+    where
+        TSegments: IntoIterator<Item = TSegment>,
+        TSegment: AsRef<str>,
+    {
+        PathBuilder::new()
+            .with_absolute(absolute)
+            .with_segments(segments)
+            .build()
+    }
+
+    /// Constructs a path from the given segments in a `const` context, borrowing them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no segments, or more than the maximum number of segments. In a `const`
+    /// or `static` item, this results in a compilation error instead.
+    pub(crate) const fn from_static_segments(
+        absolute: bool,
+        segments: &'static [Segment<'static>],
+    ) -> Path<'static> {
+        if segments.is_empty() {
+            panic!("path has no segments");
+        }
+
+        if segments.len() > u16::MAX as usize {
+            panic!("path exceeds maximum length");
+        }
+
+        let mut double_dot_segment_count = 0;
+        let mut index = 0;
+        let mut leading_double_dot_segment_count = 0;
+        let mut single_dot_segment_count = 0;
+        let mut unnormalized_count = 0;
+
+        while index < segments.len() {
+            let segment = &segments[index];
+
+            match segment.as_str().as_bytes() {
+                b"." => single_dot_segment_count += 1,
+                b".." => {
+                    if leading_double_dot_segment_count == index as u16 {
+                        leading_double_dot_segment_count += 1;
+                    }
+
+                    double_dot_segment_count += 1;
+                }
+                _ => (),
+            }
+
+            if !segment.normalized {
+                unnormalized_count += 1;
+            }
+
+            index += 1;
+        }
+
+        Path {
+            absolute,
+            double_dot_segment_count,
+            leading_double_dot_segment_count,
+            segments: SegmentList::Borrowed(segments),
+            single_dot_segment_count,
+            unnormalized_count,
+        }
+    }
+
+    /// Constructs a path from a path of the standard library, e.g. for use in a `file:` URI.
+    ///
+    /// The path is split on the separators of the platform and each component is percent-encoded
+    /// as done by [`Segment::encode`]. An absolute path results in an absolute path, and a
+    /// trailing separator results in a trailing empty segment. On Windows, a drive letter prefix
+    /// becomes a leading segment (`C:\dir` becomes `"/C:/dir"`) and a UNC prefix becomes a
+    /// leading empty segment followed by the server and share (`\\server\share\dir` becomes
+    /// `"//server/share/dir"`), which is the UNC form of
+    /// [[RFC8089, Appendix E.3](https://tools.ietf.org/html/rfc8089#appendix-E.3)].
+    ///
+    /// An error is returned if the path has a Windows prefix other than a drive letter or UNC
+    /// prefix, if the path has too many segments, or, on platforms other than Unix, if it is not
+    /// valid UTF-8. This is the inverse of [`Path::to_file_path`] for absolute paths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Path;
+    ///
+    /// # #[cfg(unix)]
+    /// # {
+    /// let path = Path::from_std_path("/home/jane/my file.txt".as_ref()).unwrap();
+    /// assert_eq!(path, "/home/jane/my%20file.txt");
+    ///
+    /// let path = Path::from_std_path("docs/100%/".as_ref()).unwrap();
+    /// assert_eq!(path, "docs/100%25/");
+    /// # }
+    /// ```
+    pub fn from_std_path(path: &std_path::Path) -> Result<Path<'static>, PathBufError> {
+        let mut uri_path = Path::try_from("").unwrap();
+        let mut has_segments = false;
+
+        for component in path.components() {
+            let segment = match component {
+                Component::Prefix(prefix) => {
+                    uri_path.set_absolute(true);
+
+                    match prefix.kind() {
+                        Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => {
+                            Segment::encode(&format!("{}:", letter as char))
+                        }
+                        Prefix::UNC(server, share) | Prefix::VerbatimUNC(server, share) => {
+                            uri_path
+                                .push(Segment::empty())
+                                .map_err(|_| PathBufError::ExceededMaximumLength)?;
+                            uri_path
+                                .push(encode_os_str(server)?)
+                                .map_err(|_| PathBufError::ExceededMaximumLength)?;
+                            encode_os_str(share)?
+                        }
+                        _ => return Err(PathBufError::UnsupportedPrefix),
+                    }
+                }
+                Component::RootDir => {
+                    uri_path.set_absolute(true);
+                    continue;
+                }
+                Component::CurDir => Segment::try_from(".").unwrap(),
+                Component::ParentDir => Segment::try_from("..").unwrap(),
+                Component::Normal(name) => encode_os_str(name)?,
+            };
+
+            has_segments = true;
+            uri_path
+                .push(segment)
+                .map_err(|_| PathBufError::ExceededMaximumLength)?;
+        }
+
+        let ends_with_separator = matches!(
+            path.to_string_lossy().chars().last(),
+            Some(character) if std_path::is_separator(character)
+        );
+
+        if has_segments && ends_with_separator {
+            uri_path
+                .push(Segment::empty())
+                .map_err(|_| PathBufError::ExceededMaximumLength)?;
+        }
+
+        Ok(uri_path)
+    }
+
+    /// Returns whether the path ends with a `'/'`, i.e. whether its last segment is empty.
+    ///
+    /// The absolute path `"/"` ends with a `'/'` while the empty relative path does not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// assert!(Path::try_from("/docs/").unwrap().has_trailing_slash());
+    /// assert!(Path::try_from("/").unwrap().has_trailing_slash());
+    /// assert!(!Path::try_from("/docs").unwrap().has_trailing_slash());
+    /// assert!(!Path::try_from("").unwrap().has_trailing_slash());
+    /// ```
+    pub fn has_trailing_slash(&self) -> bool {
+        if self.is_empty_path() {
+            self.absolute
+        } else {
+            self.segments[self.segments.len() - 1].is_empty()
+        }
+    }
+
+    /// Converts the [`Path`] into an owned copy.
+    ///
+    /// If you construct the path from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the path will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Path<'static> {
+        let segments = self.segments.map(Segment::into_owned);
+
+        Path {
+            absolute: self.absolute,
+            double_dot_segment_count: self.double_dot_segment_count,
+            leading_double_dot_segment_count: self.leading_double_dot_segment_count,
+            segments,
+            single_dot_segment_count: self.single_dot_segment_count,
+            unnormalized_count: self.unnormalized_count,
+        }
+    }
+
+    /// Returns whether the path is absolute (i.e. it starts with a `'/'`).
+    ///
+    /// Any path following an [`Authority`] will *always* be parsed to be absolute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/my/path").unwrap();
+    /// assert_eq!(path.is_absolute(), true);
+    /// ```
+    pub const fn is_absolute(&self) -> bool {
+        self.absolute
+    }
+
+    /// Returns whether the path consists of just one empty segment, i.e. is `""` or `"/"`.
+    pub(crate) fn is_empty_path(&self) -> bool {
+        self.segments.len() == 1 && self.segments[0].is_empty()
+    }
+
+    /// Returns whether the path is normalized either as or as not a reference.
+    ///
+    /// See [`Path::normalize`] for a full description of what path normalization entails.
+    ///
+    /// Although this function does not operate in constant-time in general, it will be
+    /// constant-time in the vast majority of cases.
+    ///
+    ///
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/my/path").unwrap();
+    /// assert!(path.is_normalized(false));
+    ///
+    /// let path = Path::try_from("/my/p%61th").unwrap();
+    /// assert!(!path.is_normalized(false));
+    ///
+    /// let path = Path::try_from("..").unwrap();
+    /// assert!(path.is_normalized(true));
+    ///
+    /// let path = Path::try_from("../.././.").unwrap();
+    /// assert!(!path.is_normalized(true));
+    /// ```
+    pub fn is_normalized(&self, as_reference: bool) -> bool {
+        if self.unnormalized_count != 0 {
+            return false;
+        }
+
+        if self.absolute || !as_reference {
+            self.single_dot_segment_count == 0 && self.double_dot_segment_count == 0
+        } else {
+            (self.single_dot_segment_count == 0
+                || (self.single_dot_segment_count == 1
+                    && self.segments[0].is_single_dot_segment()
+                    && self.segments.len() > 1
+                    && self.segments[1].contains(':')))
+                && self.double_dot_segment_count == self.leading_double_dot_segment_count
+        }
+    }
+
+    /// Returns whether the path is relative (i.e. it does not start with a `'/'`).
+    ///
+    /// Any path following an [`Authority`] will *always* be parsed to be absolute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("my/path").unwrap();
+    /// assert_eq!(path.is_relative(), true);
+    /// ```
+    pub fn is_relative(&self) -> bool {
+        !self.absolute
+    }
+
+    /// Returns whether the path is rootless, i.e. not absolute and not empty.
+    ///
+    /// This corresponds to the `path-rootless` rule of
+    /// [[RFC3986, Section 3.3](https://tools.ietf.org/html/rfc3986#section-3.3)]. The empty path
+    /// is neither absolute nor rootless.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("my/path").unwrap();
+    /// assert!(path.is_rootless());
+    ///
+    /// let path = Path::try_from("/my/path").unwrap();
+    /// assert!(!path.is_rootless());
+    ///
+    /// let path = Path::try_from("").unwrap();
+    /// assert!(!path.is_rootless());
+    /// assert!(path.is_relative());
+    /// ```
+    pub fn is_rootless(&self) -> bool {
+        !self.absolute && !self.is_empty_path()
+    }
+
+    /// Parses the given path and joins it onto this path, see [`Path::join_path`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Path, PathError};
+    ///
+    /// let path = Path::try_from("/static/css").unwrap();
+    /// assert_eq!(path.join("themes/../main.css").unwrap(), "/static/css/main.css");
+    /// assert_eq!(path.join("sub dir"), Err(PathError::InvalidCharacter));
+    /// ```
+    pub fn join(&self, path: &'path str) -> Result<Path<'path>, PathError> {
+        self.join_path(&Path::try_from(path)?)
+    }
+
+    /// Appends the segments of the given path to this path and removes dot segments from the
+    /// result.
+    ///
+    /// Unlike [`Path::merge`], the last segment of this path is kept, only a trailing empty segment
+    /// is replaced. If the given path is absolute, it replaces this path entirely. This is
+    /// analogous to joining paths of the standard library.
+    ///
+    /// An error is returned if the joined path would have too many segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/a/b").unwrap();
+    /// let other = Path::try_from("c/d").unwrap();
+    /// assert_eq!(path.join_path(&other).unwrap(), "/a/b/c/d");
+    ///
+    /// let path = Path::try_from("/a/b/").unwrap();
+    /// let other = Path::try_from("../c/").unwrap();
+    /// assert_eq!(path.join_path(&other).unwrap(), "/a/c/");
+    ///
+    /// let other = Path::try_from("/c").unwrap();
+    /// assert_eq!(path.join_path(&other).unwrap(), "/c");
+    /// ```
+    pub fn join_path(&self, path: &Path<'path>) -> Result<Path<'path>, PathError> {
+        let mut joined = if path.absolute {
+            path.clone()
+        } else {
+            let mut joined = self.clone();
+            joined.strip_trailing_slash();
+
+            for segment in path.segments() {
+                joined.push(segment.clone())?;
+            }
+
+            joined
+        };
+
+        joined.remove_dot_segments();
+        Ok(joined)
+    }
+
+    /// Returns a relative path that resolves to this path against the given base path.
+    ///
+    /// This is the inverse of reference resolution: merging the returned path with the base path
+    /// (see [`Path::merge`]) and removing dot segments gives this path without dot segments. The
+    /// returned path uses `".."` segments to leave the directories of the base path that are not
+    /// shared with this path, and starts with a `"."` segment whenever it would otherwise be empty
+    /// or start with a segment that is empty or contains a `':'`.
+    ///
+    /// [`None`] is returned if either path is not absolute or if the relative path would have too
+    /// many segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/blog/2020/post.html").unwrap();
+    /// let base = Path::try_from("/blog/tags/rust.html").unwrap();
+    /// let mut relative = path.make_relative(&base).unwrap();
+    /// assert_eq!(relative, "../2020/post.html");
+    ///
+    /// let mut resolved = base.merge(&relative, true).unwrap();
+    /// resolved.remove_dot_segments();
+    /// assert_eq!(resolved, path);
+    ///
+    /// let path = Path::try_from("/blog/").unwrap();
+    /// assert_eq!(path.make_relative(&base).unwrap(), "../");
+    ///
+    /// let path = Path::try_from("relative").unwrap();
+    /// assert!(path.make_relative(&base).is_none());
+    /// ```
+    pub fn make_relative(&self, base: &Path<'_>) -> Option<Path<'path>> {
+        if !self.absolute || !base.absolute {
+            return None;
+        }
+
+        let mut target = self.clone();
+        target.remove_dot_segments();
+        let mut base = base.to_borrowed();
+        base.remove_dot_segments();
+
+        let base_directory = &base.segments[..base.segments.len() - 1];
+        let target_directory = &target.segments[..target.segments.len() - 1];
+        let common_length = base_directory
+            .iter()
+            .zip(target_directory)
+            .take_while(|(base_segment, target_segment)| base_segment == target_segment)
+            .count();
+
+        let mut segments = Vec::new();
+
+        for _ in common_length..base_directory.len() {
+            segments.push(Segment::try_from("..").unwrap());
+        }
+
+        segments.extend(
+            target.segments[common_length..]
+                .iter_mut()
+                .map(|segment| mem::replace(segment, Segment::empty())),
+        );
+
+        if segments[0].is_empty() || segments[0].contains(':') {
+            segments.insert(0, Segment::try_from(".").unwrap());
+        }
+
+        let mut path = Path::try_from("").unwrap();
+
+        for segment in segments {
+            path.push(segment).ok()?;
+        }
+
+        Some(path)
+    }
+
+    /// Merges the given relative reference path with this base path as described in
+    /// [[RFC3986, Section 5.2.3](https://tools.ietf.org/html/rfc3986#section-5.2.3)].
+    ///
+    /// If the base has an authority (as given by `base_has_authority`) and this path is empty, the
+    /// result is the reference path made absolute. Otherwise, the last segment of this path is
+    /// replaced by the segments of the reference path. The reference path is treated as relative
+    /// even if it is absolute, and dot segments are not removed, see
+    /// [`Path::remove_dot_segments`].
+    ///
+    /// An error is returned if the merged path would have too many segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let base = Path::try_from("/b/c/d;p").unwrap();
+    /// let reference = Path::try_from("../g").unwrap();
+    /// let mut path = base.merge(&reference, true).unwrap();
+    /// assert_eq!(path, "/b/c/../g");
+    /// path.remove_dot_segments();
+    /// assert_eq!(path, "/b/g");
+    ///
+    /// let base = Path::try_from("").unwrap();
+    /// let reference = Path::try_from("g").unwrap();
+    /// assert_eq!(base.merge(&reference, true).unwrap(), "/g");
+    /// assert_eq!(base.merge(&reference, false).unwrap(), "g");
+    /// ```
+    pub fn merge(
+        &self,
+        reference: &Path<'path>,
+        base_has_authority: bool,
+    ) -> Result<Path<'path>, PathError> {
+        if base_has_authority && self.segments.len() == 1 && self.segments[0].is_empty() {
+            let mut path = reference.clone();
+            path.set_absolute(true);
+            return Ok(path);
+        }
+
+        let mut path = self.clone();
+        path.pop();
+
+        for segment in reference.segments() {
+            path.push(segment.clone())?;
+        }
+
+        Ok(path)
+    }
+
+    /// Creates a path with no segments on it.
+    ///
+    /// This is only used to avoid allocations for temporary paths. Any path created using this
+    /// function is **not** valid!
+    pub(crate) unsafe fn new_with_no_segments(absolute: bool) -> Path<'static> {
+        Path {
+            absolute,
+            double_dot_segment_count: 0,
+            leading_double_dot_segment_count: 0,
+            segments: SegmentList::new(),
+            single_dot_segment_count: 0,
+            unnormalized_count: 0,
+        }
+    }
+
+    /// Normalizes the path and all of its segments.
+    ///
+    /// There are two components to path normalization, the normalization of each segment
+    /// individually and the removal of unnecessary dot segments. It is also guaranteed that whether
+    /// the path is absolute will not change as a result of normalization.
+    ///
+    /// The normalization of each segment will proceed according to [`Segment::normalize`].
+    ///
+    /// If the path is absolute (i.e., it starts with a `'/'`), then `as_reference` will be set to
+    /// `false` regardless of its set value.
+    ///
+    /// If `as_reference` is `false`, then all dot segments will be removed as they would be if you
+    /// had called [`Path::remove_dot_segments`]. Otherwise, when a dot segment is removed is
+    /// dependent on whether it's `"."` or `".."` and its location in the path.
+    ///
+    /// In general, `"."` dot segments are always removed except for when it is at the beginning of
+    /// the path and is followed by a segment containing a `':'`, e.g. `"./a:b"` stays the same.
+    ///
+    /// For `".."` dot segments, they are kept whenever they are at the beginning of the path and
+    /// removed whenever they are not, e.g. `"a/../.."` normalizes to `".."`.
+    pub fn normalize(&mut self, as_reference: bool) {
+        if self.is_normalized(as_reference) {
+            return;
+        }
+
+        self.unnormalized_count = 0;
+
+        if self.absolute || !as_reference {
+            self.remove_dot_segments_helper(true);
+            return;
+        }
+
+        let mut double_dot_segment_count = 0;
+        let mut last_dot_segment = None;
+        let mut new_length = 0;
+
+        for i in 0..self.segments.len() {
+            let segment = &self.segments[i];
+
+            if segment.is_single_dot_segment()
+                && (new_length > 0
+                    || i == self.segments.len() - 1
+                    || !self.segments[i + 1].as_str().contains(':'))
+            {
+                continue;
+            }
+
+            if segment.is_double_dot_segment() {
+                match last_dot_segment {
+                    None if new_length == 0 => (),
+                    Some(index) if index == new_length - 1 => (),
+                    _ => {
+                        if new_length == 2
+                            && self.segments[0].is_single_dot_segment()
+                            && (i == self.segments.len() - 1
+                                || !self.segments[i + 1].as_str().contains(':'))
+                        {
+                            new_length -= 1
+                        }
+
+                        new_length -= 1;
+
+                        continue;
+                    }
+                }
+
+                double_dot_segment_count += 1;
+                last_dot_segment = Some(new_length);
+            }
+
+            self.segments.swap(i, new_length);
+            self.segments[new_length].normalize();
+            new_length += 1;
+        }
+
+        if new_length == 0 {
+            self.segments[0] = Segment::empty();
+            new_length = 1;
+        }
+
+        self.double_dot_segment_count = double_dot_segment_count;
+        self.leading_double_dot_segment_count = double_dot_segment_count;
+        self.single_dot_segment_count = if self.segments[0].is_single_dot_segment() {
+            1
+        } else {
+            0
+        };
+
+        self.segments.truncate(new_length);
+    }
+
+    /// Normalizes the path and all of its segments as with [`Path::normalize`], applying the given
+    /// options beforehand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Path, PathNormalizeOptions};
+    ///
+    /// let options = PathNormalizeOptions {
+    ///     collapse_empty_segments: true,
+    /// };
+    ///
+    /// let mut path = Path::try_from("//a//b/./c//").unwrap();
+    /// path.normalize_with_options(false, &options);
+    /// assert_eq!(path, "/a/b/c/");
+    ///
+    /// let mut path = Path::try_from("/a//b").unwrap();
+    /// path.normalize_with_options(false, &PathNormalizeOptions::default());
+    /// assert_eq!(path, "/a//b");
+    /// ```
+    pub fn normalize_with_options(&mut self, as_reference: bool, options: &PathNormalizeOptions) {
+        if options.collapse_empty_segments {
+            self.remove_empty_segments();
+        }
+
+        self.normalize(as_reference);
+    }
+
+    /// Pops the last segment off of the path.
+    ///
+    /// If the path only contains one segment, then that segment will become empty.
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/my/path").unwrap();
+    /// path.pop();
+    /// assert_eq!(path, "/my");
+    /// path.pop();
+    /// assert_eq!(path, "/");
+    /// ```
+    pub fn pop(&mut self) {
+        let segment = self.segments.pop().unwrap();
+
+        if segment.is_single_dot_segment() {
+            self.single_dot_segment_count = self.single_dot_segment_count.saturating_sub(1);
+        }
+
+        if segment.is_double_dot_segment() {
+            self.double_dot_segment_count = self.double_dot_segment_count.saturating_sub(1);
+
+            if self.double_dot_segment_count < self.leading_double_dot_segment_count {
+                self.leading_double_dot_segment_count -= 1;
+            }
+        }
+
+        if !segment.is_normalized() {
+            self.unnormalized_count = self.unnormalized_count.saturating_sub(1);
+        }
+
+        if self.segments.is_empty() {
+            self.segments.push(Segment::empty());
+        }
+    }
+
+    /// Returns the number of segments of this path that are matched by the given prefix, or
+    /// [`None`] if the path does not start with the prefix.
+    fn prefix_length(&self, prefix: &Path<'_>) -> Option<usize> {
+        if self.absolute != prefix.absolute {
+            return None;
+        }
+
+        let mut prefix_segments = &prefix.segments[..];
+
+        if let Some((last, rest)) = prefix_segments.split_last() {
+            if last.is_empty() {
+                prefix_segments = rest;
+            }
+        }
+
+        if self.segments.len() < prefix_segments.len()
+            || self
+                .segments
+                .iter()
+                .zip(prefix_segments)
+                .any(|(segment, prefix_segment)| segment != prefix_segment)
+        {
+            return None;
+        }
+
+        Some(prefix_segments.len())
+    }
+
+    /// Pushes a segment onto the path.
+    ///
+    /// If the conversion to a [`Segment`] fails, an [`InvalidPath`] will be returned.
+    ///
+    /// The behavior of this function is different if the current path is just one empty segment. In
+    /// this case, the pushed segment will replace that empty segment unless the pushed segment is
+    /// itself empty.
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/my/path").unwrap();
+    /// path.push("test");
+    /// assert_eq!(path, "/my/path/test");
+    ///
+    /// let mut path = Path::try_from("/").unwrap();
+    /// path.push("test");
+    /// assert_eq!(path, "/test");
+    ///
+    /// let mut path = Path::try_from("/").unwrap();
+    /// path.push("");
+    /// assert_eq!(path, "//");
+    /// ```
+    pub fn push<TSegment, TSegmentError>(&mut self, segment: TSegment) -> Result<(), PathError>
+    where
+        Segment<'path>: TryFrom<TSegment, Error = TSegmentError>,
+        PathError: From<TSegmentError>,
+    {
+        if self.segments.len() as u16 == u16::MAX {
+            return Err(PathError::ExceededMaximumLength);
+        }
+
+        let segment = Segment::try_from(segment)?;
+
+        if segment.is_single_dot_segment() {
+            self.single_dot_segment_count += 1;
+        }
+
+        if segment.is_double_dot_segment() {
+            if self.segments.len() as u16 == self.double_dot_segment_count {
+                self.leading_double_dot_segment_count += 1;
+            }
+
+            self.double_dot_segment_count += 1;
+        }
+
+        if !segment.is_normalized() {
+            self.unnormalized_count += 1;
+        }
+
+        if !segment.is_empty() && self.segments.len() == 1 && self.segments[0].as_str().is_empty() {
+            self.segments[0] = segment;
+        } else {
+            self.segments.push(segment);
+        }
+
+        Ok(())
+    }
+
+    /// Pushes a segment given as arbitrary unencoded text onto the path.
+    ///
+    /// The segment is percent-encoded as done by [`Segment::encode`], so it may contain any
+    /// character including `'/'`. Otherwise, this behaves exactly like [`Path::push`].
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/files").unwrap();
+    /// path.push_encoded("a/b c.txt").unwrap();
+    /// assert_eq!(path, "/files/a%2Fb%20c.txt");
+    /// assert_eq!(path.segments().len(), 2);
+    /// ```
+    pub fn push_encoded(&mut self, segment: &str) -> Result<(), PathError> {
+        let segment: Segment<'path> = Segment::encode(segment);
+        self.push(segment)
+    }
+
+    /// Removes all dot segments from the path according to the algorithm described in
+    /// [[RFC3986, Section 5.2.4](https://tools.ietf.org/html/rfc3986#section-5.2.4)].
+    ///
+    /// This function will perform no memory allocations during removal of dot segments.
+    ///
+    /// If the path currently has no dot segments, then this function is a no-op.
+    ///
+    /// This is the same algorithm that is used when resolving references with
+    /// [`URI::resolve`](crate::URI::resolve). Segments consisting of percent-encoded dots (e.g.
+    /// `"%2E%2E"`) are left as is, use [`Path::normalize`] to decode and remove them as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/a/b/c/./../../g").unwrap();
+    /// path.remove_dot_segments();
+    /// assert_eq!(path, "/a/g");
+    ///
+    /// let mut path = Path::try_from("mid/content=5/../6").unwrap();
+    /// path.remove_dot_segments();
+    /// assert_eq!(path, "mid/6");
+    ///
+    /// let mut path = Path::try_from("/a/b/%2E%2E/c").unwrap();
+    /// path.remove_dot_segments();
+    /// assert_eq!(path.to_string(), "/a/b/%2E%2E/c");
+    /// path.normalize(false);
+    /// assert_eq!(path, "/a/c");
+    /// ```
+    pub fn remove_dot_segments(&mut self) {
+        if self.single_dot_segment_count == 0 && self.double_dot_segment_count == 0 {
+            return;
+        }
+
+        self.remove_dot_segments_helper(false);
+    }
+
+    /// Helper function that removes all dot segments with optional segment normalization.
+    fn remove_dot_segments_helper(&mut self, normalize_segments: bool) {
+        let mut input_absolute = self.absolute;
+        let mut new_length = 0;
+
+        for i in 0..self.segments.len() {
+            let segment = &self.segments[i];
+
+            if input_absolute {
+                if segment.is_single_dot_segment() {
+                    continue;
+                } else if segment.is_double_dot_segment() {
+                    if new_length > 0 {
+                        new_length -= 1;
+                    } else {
+                        self.absolute = false;
+                    }
+
+                    continue;
+                }
+
+                if new_length == 0 {
+                    self.absolute = true;
+                }
+            } else if segment.is_single_dot_segment() || segment.is_double_dot_segment() {
+                continue;
+            }
+
+            self.segments.swap(i, new_length);
+
+            if normalize_segments {
+                self.segments[new_length].normalize();
+            }
+
+            new_length += 1;
+
+            input_absolute = i < self.segments.len() - 1;
+        }
+
+        if input_absolute {
+            if new_length == 0 {
+                self.absolute = true;
+            } else {
+                self.segments[new_length] = Segment::empty();
+                new_length += 1;
+            }
+        }
+
+        if new_length == 0 {
+            self.segments[0] = Segment::empty();
+            new_length = 1;
+        }
+
+        self.double_dot_segment_count = 0;
+        self.leading_double_dot_segment_count = 0;
+        self.single_dot_segment_count = 0;
+        self.segments.truncate(new_length);
+    }
+
+    /// Removes all empty segments except for the last one, collapsing duplicate slashes.
+    ///
+    /// Empty segments are neither dot segments nor unnormalized, so only the number of leading
+    /// double dot segments can change.
+    fn remove_empty_segments(&mut self) {
+        let last_index = self.segments.len() - 1;
+        let mut new_length = 0;
+
+        for i in 0..self.segments.len() {
+            if i < last_index && self.segments[i].is_empty() {
+                continue;
+            }
+
+            self.segments.swap(i, new_length);
+            new_length += 1;
+        }
+
+        self.segments.truncate(new_length);
+        self.leading_double_dot_segment_count = self
+            .segments
+            .iter()
+            .take_while(|segment| segment.is_double_dot_segment())
+            .count() as u16;
+    }
+
+    /// Returns the segments of the path.
+    ///
+    /// If you require mutability, use [`Path::segments_mut`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/my/path").unwrap();
+    /// assert_eq!(path.segments()[1], "path");
+    /// ```
+    pub const fn segments(&self) -> &[Segment<'path>] {
+        self.segments.as_slice()
+    }
+
+    /// Returns the segments of the path mutably.
+    ///
+    /// Due to the required restriction that there must be at least one segment in a path, this
+    /// mutability only applies to the segments themselves, not the container.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Path, Segment};
+    ///
+    /// let mut path = Path::try_from("/my/path").unwrap();
+    /// let mut segments = path.segments_mut();
+    /// segments[1] = Segment::try_from("test").unwrap();
+    ///
+    /// assert_eq!(path, "/my/test");
+    /// ```
+    pub fn segments_mut(&mut self) -> &mut [Segment<'path>] {
+        &mut self.segments
+    }
+
+    /// Sets whether the path is absolute (i.e. it starts with a `'/'`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/my/path").unwrap();
+    /// path.set_absolute(false);
+    /// assert_eq!(path, "my/path");
+    /// ```
+    pub fn set_absolute(&mut self, absolute: bool) {
+        self.absolute = absolute;
+    }
+
+    /// Sets the extension of the file name of the path, see [`Path::extension`].
+    ///
+    /// Any existing extension is replaced, and an empty extension removes it. The file name is
+    /// percent-encoded again as done by [`Segment::encode`]. Returns `false` and leaves the path
+    /// unchanged if the path has no file name (see [`Path::file_name`]) or if the new file name
+    /// would be a dot segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/docs/report%20final.md").unwrap();
+    /// assert!(path.set_extension("html"));
+    /// assert_eq!(path, "/docs/report%20final.html");
+    /// assert!(path.set_extension(""));
+    /// assert_eq!(path, "/docs/report%20final");
+    ///
+    /// let mut path = Path::try_from("/").unwrap();
+    /// assert!(!path.set_extension("html"));
+    /// ```
+    pub fn set_extension(&mut self, extension: &str) -> bool {
+        let index = match self.file_name_index() {
+            Some(index) => index,
+            None => return false,
+        };
+        let file_name = self.segments[index].as_decoded();
+        let stem = match split_extension(&file_name) {
+            Some(extension_index) => &file_name[..extension_index],
+            None => &file_name,
+        };
+        let segment = if extension.is_empty() {
+            Segment::encode(stem)
+        } else {
+            Segment::encode(&format!("{}.{}", stem, extension))
+        };
+
+        if segment.is_dot_segment() {
+            return false;
+        }
+
+        if !self.segments[index].is_normalized() {
+            self.unnormalized_count -= 1;
+        }
+
+        self.segments[index] = segment;
+        true
+    }
+
+    /// Returns whether the path starts with the given prefix, comparing whole segments.
+    ///
+    /// Segments are compared with percent-encoding aware equality, so `"/%61/b"` starts with
+    /// `"/a"` but `"/a%2Fb"` does not. Both paths must be either absolute or relative. A trailing
+    /// `'/'` of the prefix is ignored, so `"/a"` and `"/a/b"` both start with `"/a/"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let prefix = Path::try_from("/api").unwrap();
+    /// assert!(Path::try_from("/api/users").unwrap().starts_with(&prefix));
+    /// assert!(Path::try_from("/api").unwrap().starts_with(&prefix));
+    /// assert!(!Path::try_from("/apiary").unwrap().starts_with(&prefix));
+    /// assert!(!Path::try_from("/api%2Fusers").unwrap().starts_with(&prefix));
+    /// assert!(!Path::try_from("api/users").unwrap().starts_with(&prefix));
+    /// ```
+    pub fn starts_with(&self, prefix: &Path<'_>) -> bool {
+        self.prefix_length(prefix).is_some()
+    }
+
+    /// Returns the remainder of the path after the given prefix as a relative path, or [`None`] if
+    /// the path does not start with the prefix (see [`Path::starts_with`]).
+    ///
+    /// Joining a non-empty remainder onto the prefix with [`Path::join_path`] results in the path
+    /// again, with dot segments removed. So a remainder starting with an empty segment is prefixed
+    /// with a `"."` segment, as it would otherwise be absolute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let prefix = Path::try_from("/api/").unwrap();
+    /// let path = Path::try_from("/api/users/42/").unwrap();
+    /// assert_eq!(path.strip_prefix(&prefix).unwrap(), "users/42/");
+    ///
+    /// let path = Path::try_from("/api").unwrap();
+    /// assert_eq!(path.strip_prefix(&prefix).unwrap(), "");
+    ///
+    /// let path = Path::try_from("/api//users").unwrap();
+    /// assert_eq!(path.strip_prefix(&prefix).unwrap(), ".//users");
+    ///
+    /// let path = Path::try_from("/apiary").unwrap();
+    /// assert!(path.strip_prefix(&prefix).is_none());
+    /// ```
+    pub fn strip_prefix(&self, prefix: &Path<'_>) -> Option<Path<'path>> {
+        let length = self.prefix_length(prefix)?;
+        let remainder = &self.segments[length..];
+
+        if remainder.is_empty() {
+            return Some(Path::try_from("").unwrap());
+        }
+
+        // Unsafe: We are creating a path without segments, but at least one is pushed below.
+        let mut path = unsafe { Path::new_with_no_segments(false) };
+
+        if remainder.len() > 1 && remainder[0].is_empty() {
+            path.push(Segment::try_from(".").unwrap()).unwrap();
+        }
+
+        for segment in remainder {
+            path.push(segment.clone()).unwrap();
+        }
+
+        Some(path)
+    }
+
+    /// Removes the trailing `'/'` of the path, if any, by popping its empty last segment.
+    ///
+    /// The absolute path `"/"` is left unchanged since its `'/'` is not a trailing one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/docs/").unwrap();
+    /// path.strip_trailing_slash();
+    /// assert_eq!(path, "/docs");
+    /// path.strip_trailing_slash();
+    /// assert_eq!(path, "/docs");
+    ///
+    /// let mut path = Path::try_from("/").unwrap();
+    /// path.strip_trailing_slash();
+    /// assert_eq!(path, "/");
+    /// ```
+    pub fn strip_trailing_slash(&mut self) {
+        if self.has_trailing_slash() && !self.is_empty_path() {
+            self.pop();
+        }
+    }
+
+    /// Returns a copy of this path that is absolute.
+    ///
+    /// The empty path becomes the root path `"/"`. Note that an absolute path starting with an
+    /// empty segment (e.g. `"//a"`) can only be used if the URI has an authority.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("my/path").unwrap();
+    /// assert_eq!(path.to_absolute(), "/my/path");
+    ///
+    /// let path = Path::try_from("").unwrap();
+    /// assert_eq!(path.to_absolute(), "/");
+    /// ```
+    pub fn to_absolute(&self) -> Path<'path> {
+        let mut path = self.clone();
+        path.absolute = true;
+        path
+    }
+
+    /// Returns a new path which is identical but has a lifetime tied to this path.
+    ///
+    /// This function will perform a memory allocation.
+    pub fn to_borrowed(&self) -> Path<'_> {
+        let segments = self.segments.iter().map(Segment::as_borrowed).collect();
+
+        Path {
+            absolute: self.absolute,
+            double_dot_segment_count: self.double_dot_segment_count,
+            leading_double_dot_segment_count: self.leading_double_dot_segment_count,
+            segments,
+            single_dot_segment_count: self.single_dot_segment_count,
+            unnormalized_count: self.unnormalized_count,
+        }
+    }
+
+    /// Converts the path of a `file:` URI into a [`PathBuf`].
+    ///
+    /// This is the same as [`Path::to_path_buf_with_options`] with
+    /// [`PathBufOptions::file_uri`] set, so the path must be absolute and Windows drive letters
+    /// and UNC paths are handled on Windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/home/jane/my%20file.txt").unwrap();
+    /// let path_buf = path.to_file_path().unwrap();
+    ///
+    /// #[cfg(unix)]
+    /// assert_eq!(path_buf, std::path::Path::new("/home/jane/my file.txt"));
+    ///
+    /// let path = Path::try_from("relative").unwrap();
+    /// assert!(path.to_file_path().is_err());
+    /// ```
+    pub fn to_file_path(&self) -> Result<PathBuf, PathBufError> {
+        self.to_path_buf_with_options(&PathBufOptions {
+            file_uri: true,
+            ..PathBufOptions::default()
+        })
+    }
+
+    /// Converts the path into a [`PathBuf`].
+    ///
+    /// This is the same as [`Path::to_path_buf_with_options`] with the default options, so segments
+    /// containing percent-encoded separators are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::path::{Path, PathBufError};
+    ///
+    /// let path = Path::try_from("docs/%C3%BCber.md").unwrap();
+    /// let path_buf = path.to_path_buf().unwrap();
+    /// assert_eq!(path_buf.iter().collect::<Vec<_>>(), ["docs", "über.md"]);
+    ///
+    /// let path = Path::try_from("docs/..%2F..%2Fetc").unwrap();
+    /// assert_eq!(path.to_path_buf(), Err(PathBufError::EncodedSeparator));
+    /// ```
+    pub fn to_path_buf(&self) -> Result<PathBuf, PathBufError> {
+        self.to_path_buf_with_options(&PathBufOptions::default())
+    }
+
+    /// Converts the path into a [`PathBuf`] using the given options.
+    ///
+    /// Each segment is percent-decoded and the segments are joined using the separator of the
+    /// platform. An absolute path results in an absolute [`PathBuf`]. Dot segments are kept as
+    /// is, use [`Path::remove_dot_segments`] beforehand to resolve them.
+    ///
+    /// An error is returned if a segment contains a percent-encoded separator (unless allowed by
+    /// the options) or NUL character, or, on platforms other than Unix, if it does not decode to
+    /// valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::path::{Path, PathBufOptions};
+    ///
+    /// let options = PathBufOptions {
+    ///     allow_encoded_separators: true,
+    ///     ..PathBufOptions::default()
+    /// };
+    ///
+    /// let path = Path::try_from("a%2Fb/c").unwrap();
+    /// let path_buf = path.to_path_buf_with_options(&options).unwrap();
+    /// assert_eq!(path_buf.iter().collect::<Vec<_>>(), ["a", "b", "c"]);
+    /// ```
+    pub fn to_path_buf_with_options(
+        &self,
+        options: &PathBufOptions,
+    ) -> Result<PathBuf, PathBufError> {
+        let bytes = self.to_path_bytes(options, cfg!(windows))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStringExt;
+            Ok(PathBuf::from(OsString::from_vec(bytes)))
+        }
+
+        #[cfg(not(unix))]
+        {
+            let path = String::from_utf8(bytes).map_err(|_| PathBufError::InvalidUtf8)?;
+            Ok(PathBuf::from(OsString::from(path)))
+        }
+    }
+
+    /// Helper function that converts the path into the bytes of a platform path, using Windows
+    /// conventions if `windows` is true.
+    fn to_path_bytes(
+        &self,
+        options: &PathBufOptions,
+        windows: bool,
+    ) -> Result<Vec<u8>, PathBufError> {
+        let separator = if windows { b'\\' } else { b'/' };
+        let mut segments = Vec::with_capacity(self.segments.len());
+
+        for segment in self.segments.iter() {
+            let mut segment = segment.as_decoded_bytes().into_owned();
+
+            if segment.contains(&0) {
+                return Err(PathBufError::NulCharacter);
+            }
+
+            for byte in &mut segment {
+                if *byte == b'/' || (windows && *byte == b'\\') {
+                    if !options.allow_encoded_separators {
+                        return Err(PathBufError::EncodedSeparator);
+                    }
+
+                    *byte = separator;
+                }
+            }
+
+            segments.push(segment);
+        }
+
+        let mut bytes = Vec::new();
+        let mut segments = &segments[..];
+
+        if options.file_uri {
+            if !self.absolute {
+                return Err(PathBufError::NotAbsolute);
+            }
+
+            let is_drive_letter = |segment: &[u8]| {
+                segment.len() == 2
+                    && segment[0].is_ascii_alphabetic()
+                    && (segment[1] == b':' || segment[1] == b'|')
+            };
+
+            if windows && segments.len() > 1 && segments[0].is_empty() && !segments[1].is_empty() {
+                // UNC path, e.g. "//server/share/file".
+
+                bytes.extend_from_slice(b"\\\\");
+                segments = &segments[1..];
+            } else if windows && is_drive_letter(&segments[0]) {
+                // Drive letter, e.g. "/C:/file".
+
+                bytes.push(segments[0][0]);
+                bytes.extend_from_slice(b":\\");
+                segments = &segments[1..];
+            } else {
+                bytes.push(separator);
+            }
+        } else if self.absolute {
+            bytes.push(separator);
+        }
+
+        for (index, segment) in segments.iter().enumerate() {
+            if index > 0 {
+                bytes.push(separator);
+            }
+
+            bytes.extend_from_slice(segment);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Returns a copy of this path that is not absolute.
+    ///
+    /// Leading empty segments are removed, since a path that is not absolute cannot start with an
+    /// empty segment unless it is the only one, e.g. `"//a"` becomes `"a"`. The root path `"/"`
+    /// becomes the empty path, which is neither absolute nor rootless.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/my/path").unwrap();
+    /// assert_eq!(path.to_rootless(), "my/path");
+    ///
+    /// let path = Path::try_from("//my/path").unwrap();
+    /// assert_eq!(path.to_rootless(), "my/path");
+    ///
+    /// let path = Path::try_from("/").unwrap();
+    /// assert_eq!(path.to_rootless(), "");
+    /// ```
+    pub fn to_rootless(&self) -> Path<'path> {
+        let mut path = self.clone();
+        path.absolute = false;
+
+        let empty_segment_count = path
+            .segments
+            .iter()
+            .take(path.segments.len() - 1)
+            .take_while(|segment| segment.is_empty())
+            .count();
+
+        if empty_segment_count > 0 {
+            let length = path.segments.len() - empty_segment_count;
+            path.segments.rotate_left(empty_segment_count);
+            path.segments.truncate(length);
+            path.leading_double_dot_segment_count = path
+                .segments
+                .iter()
+                .take_while(|segment| segment.is_double_dot_segment())
+                .count() as u16;
+        }
+
+        path
+    }
+}
+
+impl Display for Path<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if self.absolute {
+            formatter.write_char('/')?;
+        }
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            formatter.write_str(segment.as_str())?;
+
+            if index < self.segments.len() - 1 {
+                formatter.write_char('/')?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Hash for Path<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.segments.hash(state)
+    }
+}
+
+impl<'path> From<Path<'path>> for String {
+    fn from(value: Path<'path>) -> Self {
+        value.to_string()
+    }
+}
+
+impl FromStr for Path<'static> {
+    type Err = PathError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Path::try_from(value).map(Path::into_owned)
+    }
+}
+
+impl Ord for Path<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.segments.cmp(&other.segments)
+    }
+}
+
+impl PartialEq for Path<'_> {
+    fn eq(&self, other: &Path) -> bool {
+        self.segments == other.segments
+    }
+}
+
+impl PartialEq<[u8]> for Path<'_> {
+    fn eq(&self, mut other: &[u8]) -> bool {
+        if self.absolute {
+            match other.first() {
+                Some(&byte) => {
+                    if byte != b'/' {
+                        return false;
+                    }
+                }
+                None => return false,
+            }
+
+            other = &other[1..];
+        }
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            let len = segment.as_str().len();
+
+            if other.len() < len || &other[..len] != segment {
+                return false;
+            }
+
+            other = &other[len..];
+
+            if index < self.segments.len() - 1 {
+                match other.first() {
+                    Some(&byte) => {
+                        if byte != b'/' {
+                            return false;
+                        }
+                    }
+                    None => return false,
+                }
+
+                other = &other[1..];
+            }
+        }
+
+        true
+    }
+}
+
+impl<'path> PartialEq<Path<'path>> for [u8] {
+    fn eq(&self, other: &Path<'path>) -> bool {
+        other == self
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for Path<'_> {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self == *other
+    }
+}
+
+impl<'path> PartialEq<Path<'path>> for &[u8] {
+    fn eq(&self, other: &Path<'path>) -> bool {
+        other == *self
+    }
+}
+
+impl PartialEq<str> for Path<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'path> PartialEq<Path<'path>> for str {
+    fn eq(&self, other: &Path<'path>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Path<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'path> PartialEq<Path<'path>> for &str {
+    fn eq(&self, other: &Path<'path>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl PartialOrd for Path<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'path> TryFrom<&'path [u8]> for Path<'path> {
+    type Error = PathError;
+
+    fn try_from(value: &'path [u8]) -> Result<Self, Self::Error> {
+        let (path, rest) = parse_path(value).map_err(|(error, _)| error)?;
+
+        if rest.is_empty() {
+            Ok(path)
+        } else {
+            Err(PathError::InvalidCharacter)
+        }
+    }
+}
+
+impl<'path> TryFrom<&'path str> for Path<'path> {
+    type Error = PathError;
+
+    fn try_from(value: &'path str) -> Result<Self, Self::Error> {
+        Path::try_from(value.as_bytes())
+    }
+}
+
+/// A builder type for [`Path`].
+///
+/// Segments are given as arbitrary unencoded text and are percent-encoded as done by
+/// [`Segment::encode`]. By default, the path is rootless (i.e. relative) and has no segments,
+/// which builds the empty path.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PathBuilder {
+    /// Whether the path is absolute, i.e. starts with a `'/'`.
+    absolute: bool,
+
+    /// The encoded segments of the path.
+    segments: Vec<Segment<'static>>,
+}
+
+impl PathBuilder {
+    /// Sets whether the path is absolute, i.e. starts with a `'/'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::PathBuilder;
+    ///
+    /// let mut builder = PathBuilder::new();
+    /// builder.absolute(true);
+    /// assert_eq!(builder.build().unwrap(), "/");
+    /// ```
+    pub fn absolute(&mut self, absolute: bool) -> &mut Self {
+        self.absolute = absolute;
+        self
+    }
+
+    /// Consumes the builder and tries to build a [`Path`].
+    ///
+    /// This function will error if the path would have too many segments. The built path has
+    /// exactly the given segments, e.g. the segments `["", "a"]` build the absolute path `"//a"`.
+    /// Without any segments, the path consists of a single empty segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::PathBuilder;
+    ///
+    /// let mut builder = PathBuilder::new();
+    /// builder.segment("a").segment("").segment("b");
+    /// assert_eq!(builder.build().unwrap(), "a//b");
+    ///
+    /// let mut builder = PathBuilder::new();
+    /// builder.absolute(true).segments(&["", "a"]);
+    /// assert_eq!(builder.build().unwrap(), "//a");
+    /// ```
+    pub fn build(self) -> Result<Path<'static>, PathError> {
+        // Pushing the segments would merge a leading empty segment with the single empty segment
+        // of an empty path, so the path is parsed instead. Encoded segments never contain a
+        // `'/'`, so this results in exactly the given segments.
+        let mut path = String::new();
+
+        if self.absolute {
+            path.push('/');
+        }
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            if index > 0 {
+                path.push('/');
+            }
+
+            path.push_str(segment.as_str());
+        }
+
+        Path::try_from(path.as_str()).map(Path::into_owned)
+    }
+
+    /// Constructs a new builder with nothing set.
+    pub fn new() -> Self {
+        PathBuilder::default()
+    }
+
+    /// Appends a segment given as arbitrary unencoded text to the path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::PathBuilder;
+    ///
+    /// let mut builder = PathBuilder::new();
+    /// builder.segment("100%").segment("a?b");
+    /// assert_eq!(builder.build().unwrap(), "100%25/a%3Fb");
+    /// ```
+    pub fn segment(&mut self, segment: &str) -> &mut Self {
+        self.segments.push(Segment::encode(segment));
+        self
+    }
+
+    /// Appends segments given as arbitrary unencoded text to the path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::PathBuilder;
+    ///
+    /// let mut builder = PathBuilder::new();
+    /// builder.absolute(true).segments(&["api", "v1"]);
+    /// assert_eq!(builder.build().unwrap(), "/api/v1");
+    /// ```
+    pub fn segments<TSegments, TSegment>(&mut self, segments: TSegments) -> &mut Self
+    where
+        TSegments: IntoIterator<Item = TSegment>,
+        TSegment: AsRef<str>,
+    {
+        for segment in segments {
+            self.segment(segment.as_ref());
+        }
+
+        self
+    }
+
+    /// Consumes the builder and sets whether the path is absolute, i.e. starts with a `'/'`.
+    pub fn with_absolute(mut self, absolute: bool) -> Self {
+        self.absolute(absolute);
+        self
+    }
+
+    /// Consumes the builder and appends a segment given as arbitrary unencoded text to the path.
+    pub fn with_segment(mut self, segment: &str) -> Self {
+        self.segment(segment);
+        self
+    }
+
+    /// Consumes the builder and appends segments given as arbitrary unencoded text to the path.
+    pub fn with_segments<TSegments, TSegment>(mut self, segments: TSegments) -> Self
+    where
+        TSegments: IntoIterator<Item = TSegment>,
+        TSegment: AsRef<str>,
+    {
+        self.segments(segments);
+        self
+    }
+}
+
+/// A segment of a path.
+///
+/// Segments are separated from other segments with the `'/'` delimiter.
+///
+/// Segments are compared and ordered by their bytes as they would be after normalization, i.e.
+/// with percent-encoded unreserved characters decoded and all other percent-encodings using
+/// uppercase hexadecimal digits. Other percent-encoded characters are not decoded, as they are not
+/// equivalent to the characters themselves, so for example `"%3A"` is ordered before `"-"`,
+/// unlike `":"`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment<'segment> {
+    /// Whether the segment is normalized.
+    normalized: bool,
+
+    /// The internal segment source that is either owned or borrowed.
+    segment: Cow<'segment, str>,
+}
+
+impl Segment<'_> {
+    /// Returns a new segment which is identical but has as lifetime tied to this segment.
+    pub fn as_borrowed(&self) -> Segment<'_> {
+        use self::Cow::*;
+
+        let segment = match &self.segment {
+            Borrowed(borrowed) => *borrowed,
+            Owned(owned) => owned.as_str(),
+        };
+
+        Segment {
+            normalized: self.normalized,
+            segment: Cow::Borrowed(segment),
+        }
+    }
+
+    /// Returns the segment with percent-decoding applied.
+    ///
+    /// Any decoded bytes that do not form valid UTF-8 are replaced with `U+FFFD REPLACEMENT
+    /// CHARACTER`, use [`Segment::as_decoded_bytes`] to get the exact bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("a%2Fb%20c").unwrap();
+    /// assert_eq!(segment.as_decoded(), "a/b c");
+    /// ```
+    pub fn as_decoded(&self) -> Cow<'_, str> {
+        percent_decode(&self.segment)
+    }
+
+    /// Returns the bytes of the segment with percent-decoding applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("%FFa").unwrap();
+    /// assert_eq!(&*segment.as_decoded_bytes(), b"\xffa");
+    /// ```
+    pub fn as_decoded_bytes(&self) -> Cow<'_, [u8]> {
+        percent_decode_bytes(self.segment.as_bytes())
+    }
+
+    /// Returns a `str` representation of the segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("segment").unwrap();
+    /// assert_eq!(segment.as_str(), "segment");
+    /// ```
+    pub const fn as_str(&self) -> &str {
+        match self.segment {
+            Cow::Borrowed(segment) => segment,
+            Cow::Owned(ref segment) => segment.as_str(),
+        }
+    }
+
+    /// Constructs a segment that is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Segment;
+    ///
+    /// assert_eq!(Segment::empty(),  "");
+    /// ```
+    pub fn empty() -> Segment<'static> {
+        Segment {
+            normalized: true,
+            segment: Cow::from(""),
+        }
+    }
+
+    /// Constructs a segment from arbitrary unencoded text.
+    ///
+    /// Any character that is not allowed in a segment, including `'/'`, as well as `'%'` itself,
+    /// is percent-encoded. The resulting segment is always normalized. Note that `"."` and `".."`
+    /// are still dot segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::encode("50% off/sale");
+    /// assert_eq!(segment, "50%25%20off%2Fsale");
+    /// assert!(segment.is_normalized());
+    /// ```
+    pub fn encode(value: &str) -> Segment<'static> {
+        Segment::encode_bytes(value.as_bytes())
+    }
+
+    /// Constructs a segment from arbitrary unencoded bytes.
+    fn encode_bytes(value: &[u8]) -> Segment<'static> {
+        let mut segment = String::with_capacity(value.len());
+        percent_encode(value, &SEGMENT_ENCODE_CHAR_MAP, &mut segment);
+
+        Segment {
+            normalized: true,
+            segment: Cow::from(segment),
+        }
+    }
+
+    /// Constructs a segment with matrix parameters from the given unencoded name and
+    /// `key=value` parameters, e.g. `"users;id=3"`.
+    ///
+    /// The name, keys and values are percent-encoded, including any `';'` and `'='` they contain,
+    /// so they are returned as is by [`Segment::matrix_name`] and [`Segment::matrix_parameters`].
+    /// A parameter with a value of [`None`] has no `'='`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::from_matrix_parameters(
+    ///     "users",
+    ///     vec![("id", Some("3")), ("sort", Some("a;b=c")), ("flag", None)],
+    /// );
+    /// assert_eq!(segment, "users;id=3;sort=a%3Bb%3Dc;flag");
+    /// ```
+    pub fn from_matrix_parameters<'a, I>(name: &str, parameters: I) -> Segment<'static>
+    where
+        I: IntoIterator<Item = (&'a str, Option<&'a str>)>,
+    {
+        let mut segment = String::with_capacity(name.len());
+        percent_encode(name.as_bytes(), &MATRIX_ENCODE_CHAR_MAP, &mut segment);
+
+        for (key, value) in parameters {
+            segment.push(';');
+            percent_encode(key.as_bytes(), &MATRIX_ENCODE_CHAR_MAP, &mut segment);
+
+            if let Some(value) = value {
+                segment.push('=');
+                percent_encode(value.as_bytes(), &MATRIX_ENCODE_CHAR_MAP, &mut segment);
+            }
+        }
+
+        Segment {
+            normalized: true,
+            segment: Cow::from(segment),
+        }
+    }
+
+    /// Constructs a segment from the given string in a `const` context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid segment. In a `const` or `static` item, this results in
+    /// a compilation error instead.
+    pub(crate) const fn from_static(value: &'static str) -> Segment<'static> {
+        match scan_segment(value.as_bytes()) {
+            Ok(normalized) => Segment {
+                normalized,
+                segment: Cow::Borrowed(value),
+            },
+            Err(PathError::InvalidPercentEncoding) => panic!("invalid segment percent encoding"),
+            Err(_) => panic!("invalid segment character"),
+        }
+    }
+
+    /// Converts the [`Segment`] into an owned copy.
+    ///
+    /// If you construct the segment from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the segment will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Segment<'static> {
+        Segment {
+            normalized: self.normalized,
+            segment: Cow::from(self.segment.into_owned()),
+        }
+    }
+
+    /// Returns whether the segment is a dot segment, i.e., is `"."` or `".."`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("segment").unwrap();
+    /// assert!(!segment.is_dot_segment());
+    ///
+    /// let segment = Segment::try_from(".").unwrap();
+    /// assert!(segment.is_dot_segment());
+    ///
+    /// let segment = Segment::try_from("..").unwrap();
+    /// assert!(segment.is_dot_segment());
+    /// ```
+    pub fn is_dot_segment(&self) -> bool {
+        self == "." || self == ".."
+    }
+
+    /// Returns whether the segment is a dot segment, i.e., is `".."`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("segment").unwrap();
+    /// assert!(!segment.is_double_dot_segment());
+    ///
+    /// let segment = Segment::try_from(".").unwrap();
+    /// assert!(!segment.is_double_dot_segment());
+    ///
+    /// let segment = Segment::try_from("..").unwrap();
+    /// assert!(segment.is_double_dot_segment());
+    /// ```
+    pub fn is_double_dot_segment(&self) -> bool {
+        self == ".."
+    }
+
+    /// Returns whether the segment is normalized.
+    ///
+    /// A normalized segment will have no bytes that are in the unreserved character set
+    /// percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("segment").unwrap();
+    /// assert!(segment.is_normalized());
+    ///
+    /// let mut segment = Segment::try_from("%ff%ff").unwrap();
+    /// assert!(!segment.is_normalized());
+    /// segment.normalize();
+    /// assert!(segment.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Returns whether the segment is a dot segment, i.e., is `"."`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("segment").unwrap();
+    /// assert!(!segment.is_single_dot_segment());
+    ///
+    /// let segment = Segment::try_from(".").unwrap();
+    /// assert!(segment.is_single_dot_segment());
+    ///
+    /// let segment = Segment::try_from("..").unwrap();
+    /// assert!(!segment.is_single_dot_segment());
+    /// ```
+    pub fn is_single_dot_segment(&self) -> bool {
+        self == "."
+    }
+
+    /// Returns the percent-decoded name of the segment when it is interpreted as having matrix
+    /// parameters, i.e. everything before the first `';'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("my%20users;id=3").unwrap();
+    /// assert_eq!(segment.matrix_name(), "my users");
+    /// ```
+    pub fn matrix_name(&self) -> Cow<'_, str> {
+        let name = match self.segment.find(';') {
+            Some(index) => &self.segment[..index],
+            None => &self.segment,
+        };
+        percent_decode(name)
+    }
+
+    /// Returns an iterator over the `key=value` matrix parameters of the segment, e.g. the
+    /// parameters `id=3` and `active` of the segment `"users;id=3;active"`.
+    ///
+    /// Parameters are separated by `';'` and follow the name of the segment (see
+    /// [`Segment::matrix_name`]). Empty parameters are skipped. If a parameter has no `'='`, its
+    /// value will be [`None`]. Both the key and the value are percent-decoded, so percent-encoded
+    /// `';'` and `'='` do not separate parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("users;id=3;;q=a%3Bb;active").unwrap();
+    /// let parameters: Vec<_> = segment.matrix_parameters().collect();
+    /// assert_eq!(parameters[0], ("id".into(), Some("3".into())));
+    /// assert_eq!(parameters[1], ("q".into(), Some("a;b".into())));
+    /// assert_eq!(parameters[2], ("active".into(), None));
+    /// assert_eq!(parameters.len(), 3);
+    /// ```
+    pub fn matrix_parameters(&self) -> MatrixParameters<'_> {
+        let mut parameters = self.segment.split(';');
+        parameters.next();
+        MatrixParameters { parameters }
+    }
+
+    /// Normalizes the segment such that it will have no bytes that are in the unreserved character
+    /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// If the segment is already normalized, the function will return immediately. Otherwise, if
+    /// the segment is not owned, this function will perform an allocation to clone it. The
+    /// normalization itself though, is done in-place with no extra memory allocations required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let mut segment = Segment::try_from("segment").unwrap();
+    /// segment.normalize();
+    /// assert_eq!(segment, "segment");
+    ///
+    /// let mut segment = Segment::try_from("%ff%41").unwrap();
+    /// assert_eq!(segment, "%ff%41");
+    /// segment.normalize();
+    /// assert_eq!(segment, "%FFA");
+    /// ```
+    pub fn normalize(&mut self) {
+        if !self.normalized {
+            // Unsafe: Paths must be valid ASCII-US, so this is safe.
+            unsafe { normalize_string(self.segment.to_mut(), true) };
+            self.normalized = true;
+        }
+    }
+}
+
+impl AsRef<[u8]> for Segment<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.segment.as_bytes()
+    }
+}
+
+impl AsRef<str> for Segment<'_> {
+    fn as_ref(&self) -> &str {
+        &self.segment
+    }
+}
+
+impl Deref for Segment<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.segment
+    }
+}
+
+impl Display for Segment<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(&self.segment)
+    }
+}
+
+impl Eq for Segment<'_> {}
+
+impl<'segment> From<Segment<'segment>> for String {
+    fn from(value: Segment<'segment>) -> Self {
+        value.to_string()
+    }
+}
+
+impl Hash for Segment<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        percent_encoded_hash(self.segment.as_bytes(), state, true);
+    }
+}
+
+impl Ord for Segment<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(self.segment.as_bytes(), other.segment.as_bytes(), true)
+    }
+}
+
+impl PartialEq for Segment<'_> {
+    fn eq(&self, other: &Segment) -> bool {
+        *self == *other.as_bytes()
+    }
+}
+
+impl PartialEq<[u8]> for Segment<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        percent_encoded_equality(self.segment.as_bytes(), other, true)
+    }
+}
+
+impl<'segment> PartialEq<Segment<'segment>> for [u8] {
+    fn eq(&self, other: &Segment<'segment>) -> bool {
+        other == self
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for Segment<'_> {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self == *other
+    }
+}
+
+impl<'segment> PartialEq<Segment<'segment>> for &[u8] {
+    fn eq(&self, other: &Segment<'segment>) -> bool {
+        other == *self
+    }
+}
+
+impl PartialEq<str> for Segment<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'segment> PartialEq<Segment<'segment>> for str {
+    fn eq(&self, other: &Segment<'segment>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Segment<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'segment> PartialEq<Segment<'segment>> for &str {
+    fn eq(&self, other: &Segment<'segment>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl PartialOrd for Segment<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'segment> TryFrom<&'segment [u8]> for Segment<'segment> {
+    type Error = PathError;
+
+    fn try_from(value: &'segment [u8]) -> Result<Self, Self::Error> {
+        let normalized = scan_segment(value)?;
+
+        // Unsafe: The function above [`scan_segment`] ensures this is valid ASCII-US.
+        let segment = Segment {
+            normalized,
+            segment: Cow::Borrowed(unsafe { str::from_utf8_unchecked(value) }),
+        };
+        Ok(segment)
+    }
+}
+
+impl<'segment> TryFrom<&'segment str> for Segment<'segment> {
+    type Error = PathError;
+
+    fn try_from(value: &'segment str) -> Result<Self, Self::Error> {
+        Segment::try_from(value.as_bytes())
+    }
+}
+
+/// An iterator over a path and its ancestors.
+///
+/// This is created by [`Path::ancestors`].
+#[derive(Clone, Debug)]
+pub struct Ancestors<'path> {
+    /// The path to be yielded next.
+    next: Option<Path<'path>>,
+}
+
+impl<'path> Iterator for Ancestors<'path> {
+    type Item = Path<'path>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let path = self.next.take()?;
+
+        if !path.is_empty_path() {
+            let mut parent = path.clone();
+            parent.pop();
+            self.next = Some(parent);
+        }
+
+        Some(path)
+    }
+}
+
+/// An iterator over the `key=value` matrix parameters of a segment.
+///
+/// This is created by [`Segment::matrix_parameters`].
+#[derive(Clone, Debug)]
+pub struct MatrixParameters<'segment> {
+    /// The iterator over the raw separated parameters.
+    parameters: str::Split<'segment, char>,
+}
+
+impl<'segment> Iterator for MatrixParameters<'segment> {
+    type Item = (Cow<'segment, str>, Option<Cow<'segment, str>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let parameter = self.parameters.next()?;
+
+            if parameter.is_empty() {
+                continue;
+            }
+
+            return Some(match parameter.find('=') {
+                Some(index) => (
+                    percent_decode(&parameter[..index]),
+                    Some(percent_decode(&parameter[index + 1..])),
+                ),
+                None => (percent_decode(parameter), None),
+            });
+        }
+    }
+}
+
+/// An error representing a failed conversion between a path and a path of the standard library,
+/// i.e. [`PathBuf`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum PathBufError {
+    /// A segment contained a percent-encoded separator, which was not allowed by the
+    /// [`PathBufOptions`].
+    EncodedSeparator,
+
+    /// The path would have had more segments than the maximum allowed.
+    ExceededMaximumLength,
+
+    /// A segment or component was not valid UTF-8 on a platform that requires it.
+    InvalidUtf8,
+
+    /// The path of a `file:` URI was not absolute.
+    NotAbsolute,
+
+    /// A segment contained a percent-encoded NUL character.
+    NulCharacter,
+
+    /// The path had a Windows prefix other than a drive letter or UNC prefix, e.g. a device
+    /// namespace prefix (`\\.\COM1`).
+    UnsupportedPrefix,
+}
+
+impl Display for PathBufError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::PathBufError::*;
+
+        match self {
+            EncodedSeparator => write!(formatter, "encoded separator in path segment"),
+            ExceededMaximumLength => write!(formatter, "exceeded maximum path length"),
+            InvalidUtf8 => write!(formatter, "invalid UTF-8 in path segment"),
+            NotAbsolute => write!(formatter, "file path not absolute"),
+            NulCharacter => write!(formatter, "NUL character in path segment"),
+            UnsupportedPrefix => write!(formatter, "unsupported file path prefix"),
+        }
+    }
+}
+
+impl Error for PathBufError {}
+
+/// Options for the conversion of a path into a [`PathBuf`] with
+/// [`Path::to_path_buf_with_options`].
+///
+/// The default options reject percent-encoded separators and treat the path as a plain path.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PathBufOptions {
+    /// Whether segments may contain percent-encoded separators (i.e. `"%2F"`, and `"%5C"` on
+    /// Windows), which then separate the resulting path components like any other separator.
+    pub allow_encoded_separators: bool,
+
+    /// Whether the path is the path of a `file:` URI.
+    ///
+    /// The path must then be absolute. On Windows, a leading drive letter segment is converted
+    /// (`"/C:/dir"` becomes `C:\dir`), as is a leading empty segment that is the UNC form
+    /// [[RFC8089, Appendix E.3](https://tools.ietf.org/html/rfc8089#appendix-E.3)]
+    /// (`"//server/share/dir"` becomes `\\server\share\dir`).
+    pub file_uri: bool,
+}
+
+/// An error representing an invalid path.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum PathError {
+    /// The path exceeded the maximum length allowed. Due to implementation reasons, the maximum
+    /// length a path can be is 2^16 or 65536 characters.
+    ExceededMaximumLength,
+
+    /// The path contained an invalid character.
+    InvalidCharacter,
+
+    /// The path contained an invalid percent encoding (e.g. `"%ZZ"`).
+    InvalidPercentEncoding,
+}
+
+impl Display for PathError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::PathError::*;
+
+        match self {
+            ExceededMaximumLength => write!(formatter, "exceeded maximum path length"),
+            InvalidCharacter => write!(formatter, "invalid path character"),
+            InvalidPercentEncoding => write!(formatter, "invalid path percent encoding"),
+        }
+    }
+}
+
+impl Error for PathError {}
+
+impl From<Infallible> for PathError {
+    fn from(_: Infallible) -> Self {
+        PathError::InvalidCharacter
+    }
+}
+
+/// Options for the normalization of a path with [`Path::normalize_with_options`].
+///
+/// The default options normalize exactly as described in
+/// [[RFC3986, Section 6.2.2](https://tools.ietf.org/html/rfc3986#section-6.2.2)].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PathNormalizeOptions {
+    /// Whether empty segments are removed, except for a trailing one (e.g. `"/a//b//"` becomes
+    /// `"/a/b/"`).
+    ///
+    /// The RFC considers paths that differ in empty segments to be different, but many servers
+    /// treat duplicate slashes as a single one. Empty segments are removed before dot segments,
+    /// so `"/a//.."` becomes `"/"`.
+    pub collapse_empty_segments: bool,
+}
+
+/// Returns the index of the `'.'` separating the extension from the given file name, if any.
+fn split_extension(file_name: &str) -> Option<usize> {
+    match file_name.rfind('.') {
+        Some(0) | None => None,
+        Some(index) => Some(index),
+    }
+}
+
+/// Percent-encodes a component of a path of the standard library into a segment.
+fn encode_os_str(value: &OsStr) -> Result<Segment<'static>, PathBufError> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        Ok(Segment::encode_bytes(value.as_bytes()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let value = value.to_str().ok_or(PathBufError::InvalidUtf8)?;
+        Ok(Segment::encode(value))
+    }
+}
+
+/// Validates the segment in the given byte string and returns whether it is normalized.
+const fn scan_segment(value: &[u8]) -> Result<bool, PathError> {
+    let mut index = 0;
+    let mut normalized = true;
+
+    while index < value.len() {
+        match PATH_CHAR_MAP[value[index] as usize] {
+            0 => return Err(PathError::InvalidCharacter),
+            b'%' => match get_percent_encoded_value_at(value, index) {
+                Ok((hex_value, uppercase)) => {
+                    if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
+                        normalized = false;
+                    }
+
+                    index += 3;
+                }
+                Err(_) => return Err(PathError::InvalidPercentEncoding),
+            },
+            _ => index += 1,
+        }
+    }
+
+    Ok(normalized)
+}
+
+/// Parses the path from the given byte string.
+///
+/// On failure, the byte index of the error within the given byte string is returned alongside it.
+pub(crate) fn parse_path(value: &[u8]) -> Result<(Path<'_>, &[u8]), (PathError, usize)> {
+    struct SegmentInfo {
+        absolute: bool,
+        double_dot_segment_count: u16,
+        index: u16,
+        last_double_dot_segment: Option<u16>,
+        leading_double_dot_segment_count: u16,
+        normalized: bool,
+        single_dot_segment_count: u16,
+        unnormalized_count: u16,
+    }
+
+    impl SegmentInfo {
+        fn into_path<'path>(self, segments: SegmentList<'path>) -> Path<'path> {
+            Path {
+                absolute: self.absolute,
+                double_dot_segment_count: self.double_dot_segment_count,
+                leading_double_dot_segment_count: self.leading_double_dot_segment_count,
+                segments,
+                single_dot_segment_count: self.single_dot_segment_count,
+                unnormalized_count: self.unnormalized_count,
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_segment<'segment>(
+        segment: &'segment [u8],
+        segment_info: &mut SegmentInfo,
+    ) -> Segment<'segment> {
+        if !segment_info.normalized {
+            segment_info.unnormalized_count += 1;
+        }
+
+        if segment == b"." {
+            segment_info.single_dot_segment_count += 1;
+        }
+
+        if segment == b".." {
+            let index = segment_info.index - 1;
+            segment_info.double_dot_segment_count += 1;
+
+            if index == 0 || segment_info.last_double_dot_segment == Some(index - 1) {
+                segment_info.leading_double_dot_segment_count += 1;
+                segment_info.last_double_dot_segment = Some(index);
+            }
+        }
+
+        // Unsafe: The loop above makes sure the byte string is valid ASCII-US.
+        Segment {
+            normalized: segment_info.normalized,
+            segment: Cow::from(unsafe { str::from_utf8_unchecked(segment) }),
+        }
+    }
+
+    let (value, absolute) = if value.starts_with(b"/") {
+        (&value[1..], true)
+    } else {
+        (value, false)
+    };
+    let offset = usize::from(absolute);
+
+    let mut bytes = value.iter();
+    let mut segment_info = SegmentInfo {
+        absolute,
+        double_dot_segment_count: 0,
+        index: 1,
+        last_double_dot_segment: None,
+        leading_double_dot_segment_count: 0,
+        normalized: true,
+        single_dot_segment_count: 0,
+        unnormalized_count: 0,
+    };
+    let mut segment_end_index = 0;
+    let mut segment_start_index = 0;
+
+    let mut segments = SegmentList::new();
+
+    while let Some(&byte) = bytes.next() {
+        match PATH_CHAR_MAP[byte as usize] {
+            0 if byte == b'?' || byte == b'#' => {
+                let segment = new_segment(
+                    &value[segment_start_index..segment_end_index],
+                    &mut segment_info,
+                );
+                segments.push(segment);
+                let path = segment_info.into_path(segments);
+                return Ok((path, &value[segment_end_index..]));
+            }
+            0 if byte == b'/' => {
+                let segment = new_segment(
+                    &value[segment_start_index..segment_end_index],
+                    &mut segment_info,
+                );
+                segments.push(segment);
+                segment_info.index = segment_info
+                    .index
+                    .checked_add(1)
+                    .ok_or((PathError::ExceededMaximumLength, offset + segment_end_index))?;
+                segment_end_index += 1;
+                segment_start_index = segment_end_index;
+                segment_info.normalized = true;
+            }
+            0 => return Err((PathError::InvalidCharacter, offset + segment_end_index)),
+            b'%' => match get_percent_encoded_value(bytes.next().cloned(), bytes.next().cloned()) {
+                Ok((hex_value, uppercase)) => {
+                    if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
+                        segment_info.normalized = false;
+                    }
+
+                    segment_end_index += 3;
+                }
+                Err(_) => {
+                    return Err((
+                        PathError::InvalidPercentEncoding,
+                        offset + segment_end_index,
+                    ))
+                }
+            },
+            _ => segment_end_index += 1,
+        }
+    }
+
+    let segment = new_segment(&value[segment_start_index..], &mut segment_info);
+    segments.push(segment);
+    let path = segment_info.into_path(segments);
+    Ok((path, b""))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_path_encode() {
+        fn test_case(value: &str, expected: &str) {
+            let path = Path::encode(value).unwrap();
+            assert_eq!(path.to_string(), expected);
+            assert_eq!(
+                path.decoded_segments().collect::<Vec<_>>().join("/"),
+                value.strip_prefix('/').unwrap_or(value)
+            );
+        }
+
+        test_case("", "");
+        test_case("/", "/");
+        test_case("a b/c", "a%20b/c");
+        test_case("/a//b/", "/a//b/");
+        test_case("/100%/?#", "/100%25/%3F%23");
+        test_case("/gr\u{fc}\u{df}e", "/gr%C3%BC%C3%9Fe");
+        test_case("a:b/./..", "a:b/./..");
+    }
+
+    #[test]
+    fn test_path_equals() {
+        assert_eq!(
+            Path::try_from("segment").unwrap(),
+            Path::try_from("s%65gment").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_path_from_static_segments() {
+        fn test_case(absolute: bool, segments: &'static [Segment<'static>], value: &str) {
+            let path = Path::from_static_segments(absolute, segments);
+            let expected = Path::try_from(value).unwrap();
+            assert_eq!(path, expected);
+            assert_eq!(path.to_string(), value);
+
+            for &as_reference in &[false, true] {
+                assert_eq!(
+                    path.is_normalized(as_reference),
+                    expected.is_normalized(as_reference),
+                    "{}",
+                    value
+                );
+
+                let mut path = path.clone();
+                let mut expected = expected.clone();
+                path.normalize(as_reference);
+                expected.normalize(as_reference);
+                assert_eq!(path.to_string(), expected.to_string(), "{}", value);
+            }
+        }
+
+        const EMPTY: &[Segment<'static>] = &[Segment::from_static("")];
+        const DOTS: &[Segment<'static>] = &[
+            Segment::from_static(".."),
+            Segment::from_static(".."),
+            Segment::from_static("."),
+            Segment::from_static("a"),
+            Segment::from_static(".."),
+        ];
+        const UNNORMALIZED: &[Segment<'static>] =
+            &[Segment::from_static("%41"), Segment::from_static("b")];
+
+        test_case(false, EMPTY, "");
+        test_case(true, EMPTY, "/");
+        test_case(false, DOTS, "../.././a/..");
+        test_case(true, DOTS, "/../.././a/..");
+        test_case(true, UNNORMALIZED, "/%41/b");
+    }
+
+    #[test]
+    #[should_panic(expected = "path has no segments")]
+    fn test_path_from_static_segments_empty() {
+        Path::from_static_segments(true, &[]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_path_from_std_path() {
+        fn test_case(value: &str, expected: &str) {
+            let path = Path::from_std_path(value.as_ref()).unwrap();
+            assert_eq!(path.to_string(), expected);
+            assert_eq!(
+                path.to_path_buf().unwrap(),
+                std::path::Path::new(value)
+                    .components()
+                    .collect::<PathBuf>()
+            );
+        }
+
+        test_case("/", "/");
+        test_case("", "");
+        test_case("/a/b", "/a/b");
+        test_case("/a/b/", "/a/b/");
+        test_case("a/./b/../c", "a/b/../c");
+        test_case("./a", "./a");
+        test_case("/a b/c%d/e?f#g", "/a%20b/c%25d/e%3Ff%23g");
+        test_case("/C:\\dir", "/C:%5Cdir");
+    }
+
+    #[test]
+    fn test_path_make_relative() {
+        fn test_case(value: &str, base: &str, expected: &str) {
+            let path = Path::try_from(value).unwrap();
+            let base = Path::try_from(base).unwrap();
+            let relative = path.make_relative(&base).unwrap();
+            assert_eq!(relative.to_string(), expected);
+
+            let mut resolved = base.merge(&relative, true).unwrap();
+            resolved.remove_dot_segments();
+            let mut path = path;
+            path.remove_dot_segments();
+            assert_eq!(resolved, path);
+        }
+
+        test_case("/a/b/c", "/a/b/d", "c");
+        test_case("/a/b/c", "/a/b/", "c");
+        test_case("/a/b/c", "/a/x/y", "../b/c");
+        test_case("/a", "/a/b/c", "../../a");
+        test_case("/a/b", "/a/b", "b");
+        test_case("/a/", "/a/b", "./");
+        test_case("/", "/a/b", "../");
+        test_case("//b", "/c", ".//b");
+        test_case("/a:b", "/c", "./a:b");
+        test_case("/a/./b/../c", "/a/d", "c");
+        test_case("/%61/b", "/a/c", "b");
+
+        let base = Path::try_from("/a").unwrap();
+        assert!(Path::try_from("a").unwrap().make_relative(&base).is_none());
+        assert!(base.make_relative(&Path::try_from("a").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_path_merge() {
+        fn test_case(base: &str, reference: &str, base_has_authority: bool, expected: &str) {
+            let base = Path::try_from(base).unwrap();
+            let reference = Path::try_from(reference).unwrap();
+            let path = base.merge(&reference, base_has_authority).unwrap();
+            assert_eq!(path.to_string(), expected);
+        }
+
+        test_case("/b/c/d;p", "g", true, "/b/c/g");
+        test_case("/b/c/d;p", "g/", true, "/b/c/g/");
+        test_case("/b/c/d;p", "./g", true, "/b/c/./g");
+        test_case("/b/c/d;p", "../..", true, "/b/c/../..");
+        test_case("/b/c/", "g", true, "/b/c/g");
+        test_case("/b", "g", true, "/g");
+        test_case("", "g/h", true, "/g/h");
+        test_case("", "g/h", false, "g/h");
+        test_case("a", "g", false, "g");
+        test_case("a/b", "", false, "a/");
+    }
+
+    #[test]
+    fn test_path_normalize() {
+        fn test_case(value: &str, expected: &str, as_reference: bool) {
+            let mut path = Path::try_from(value).unwrap();
+            path.normalize(as_reference);
+
+            let expected_single_dot_segment_count = if expected.starts_with("./") { 1 } else { 0 };
+            let expected_double_dot_segment_count = expected
+                .split('/')
+                .filter(|&segment| segment == "..")
+                .count() as u16;
+
+            assert!(!path.segments().is_empty());
+            assert!(path.is_normalized(as_reference));
+            assert_eq!(
+                path.single_dot_segment_count,
+                expected_single_dot_segment_count
+            );
+            assert_eq!(
+                path.double_dot_segment_count,
+                expected_double_dot_segment_count
+            );
+            assert_eq!(
+                path.leading_double_dot_segment_count,
+                expected_double_dot_segment_count
+            );
+            assert_eq!(path.to_string(), expected);
+        }
+
+        test_case("", "", true);
+        test_case(".", "", true);
+        test_case("..", "..", true);
+        test_case("../", "../", true);
+        test_case("/.", "/", true);
+        test_case("./././././././.", "", true);
+        test_case("././././././././", "", true);
+        test_case("/..", "/", true);
+        test_case("../..", "../..", true);
+        test_case("../a/../..", "../..", true);
+        test_case("a", "a", true);
+        test_case("a/..", "", true);
+        test_case("a/../", "", true);
+        test_case("a/../..", "..", true);
+        test_case("./a:b", "./a:b", true);
+        test_case("./a:b/..", "", true);
+        test_case("./a:b/../c:d", "./c:d", true);
+        test_case("./../a:b", "../a:b", true);
+        test_case("../a/../", "../", true);
+        test_case("../../.././.././../../../.", "../../../../../../..", true);
+        test_case("a/.././a:b", "./a:b", true);
+
+        test_case("", "", false);
+        test_case(".", "", false);
+        test_case("..", "", false);
+        test_case("../", "", false);
+        test_case("/.", "/", false);
+        test_case("/..", "/", false);
+        test_case("../../.././.././../../../.", "", false);
+        test_case("a/../..", "/", false);
+        test_case("a/../../", "/", false);
+        test_case("/a/../../../../", "/", false);
+        test_case("/a/./././././././c", "/a/c", false);
+        test_case("/a/.", "/a/", false);
+        test_case("/a/./", "/a/", false);
+        test_case("/a/..", "/", false);
+        test_case("/a/b/./..", "/a/", false);
+        test_case("/a/b/./../", "/a/", false);
+        test_case("/a/b/c/./../../g", "/a/g", false);
+        test_case("mid/content=5/../6", "mid/6", false);
+
+        test_case("this/is/a/t%65st/path/%ff", "this/is/a/test/path/%FF", true);
+        test_case(
+            "this/is/a/t%65st/path/%ff",
+            "this/is/a/test/path/%FF",
+            false,
+        );
+    }
+
+    #[test]
+    fn test_path_normalize_with_options() {
+        fn test_case(value: &str, expected: &str, as_reference: bool) {
+            let options = PathNormalizeOptions {
+                collapse_empty_segments: true,
+            };
+            let mut path = Path::try_from(value).unwrap();
+            path.normalize_with_options(as_reference, &options);
+
+            let expected_double_dot_segment_count = expected
+                .split('/')
+                .filter(|&segment| segment == "..")
+                .count() as u16;
+
+            assert!(path.is_normalized(as_reference));
+            assert_eq!(
+                path.leading_double_dot_segment_count,
+                expected_double_dot_segment_count
+            );
+            assert_eq!(path.to_string(), expected);
+        }
+
+        test_case("", "", true);
+        test_case("/", "/", true);
+        test_case("//", "/", true);
+        test_case("a//b", "a/b", true);
+        test_case("a//b//", "a/b/", true);
+        test_case("..//..", "../..", true);
+        test_case(".//a:b", "./a:b", true);
+        test_case("//a///b/", "/a/b/", false);
+        test_case("/a//..", "/", false);
+        test_case("/a/%62//c", "/a/b/c", false);
+    }
+
+    #[test]
+    fn test_path_ordering() {
+        fn sorted(values: &[&'static str]) -> Vec<String> {
+            let mut paths: Vec<_> = values
+                .iter()
+                .map(|value| Path::try_from(*value).unwrap())
+                .collect();
+            paths.sort();
+            paths.iter().map(Path::to_string).collect()
+        }
+
+        assert_eq!(
+            sorted(&["/a-b", "/a/b", "/a", "/%61/a", "/"]),
+            vec!["/", "/a", "/%61/a", "/a/b", "/a-b"]
+        );
+        assert_eq!(sorted(&["/%3A", "/-", "/:"]), vec!["/%3A", "/-", "/:"]);
+
+        let mut set = std::collections::BTreeSet::new();
+        assert!(set.insert(Path::try_from("/a/%7e").unwrap()));
+        assert!(!set.insert(Path::try_from("/%61/~").unwrap()));
+        assert!(set.insert(Path::try_from("/a/%2f").unwrap()));
+        assert!(!set.insert(Path::try_from("/a/%2F").unwrap()));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_path_parse() {
+        use self::PathError::*;
+
+        let slash = "/".to_string();
+
+        assert_eq!(Path::try_from("").unwrap(), "");
+        assert_eq!(Path::try_from("/").unwrap(), "/");
+        assert_eq!(
+            Path::try_from("/tHiS/iS/a/PaTh").unwrap(),
+            "/tHiS/iS/a/PaTh"
+        );
+        assert_eq!(Path::try_from("%ff%ff%ff%41").unwrap(), "%ff%ff%ff%41");
+        assert!(Path::try_from(&*slash.repeat(65535)).is_ok());
+
+        assert_eq!(
+            Path::try_from(&*slash.repeat(65536)),
+            Err(ExceededMaximumLength)
+        );
+        assert_eq!(Path::try_from(" "), Err(InvalidCharacter));
+        assert_eq!(Path::try_from("#"), Err(InvalidCharacter));
+        assert_eq!(Path::try_from("%"), Err(InvalidPercentEncoding));
+        assert_eq!(Path::try_from("%f"), Err(InvalidPercentEncoding));
+        assert_eq!(Path::try_from("%zz"), Err(InvalidPercentEncoding));
+    }
+
+    #[test]
+    fn test_path_to_path_bytes() {
+        fn test_case(
+            value: &str,
+            allow_encoded_separators: bool,
+            file_uri: bool,
+            windows: bool,
+            expected: Result<&[u8], PathBufError>,
+        ) {
+            let options = PathBufOptions {
+                allow_encoded_separators,
+                file_uri,
+            };
+            let path = Path::try_from(value).unwrap();
+            let bytes = path.to_path_bytes(&options, windows);
+            assert_eq!(bytes.as_deref().map_err(|&error| error), expected);
+        }
+
+        test_case("/a/b%20c", false, false, false, Ok(b"/a/b c"));
+        test_case("a/b", false, false, true, Ok(b"a\\b"));
+        test_case("/", false, false, false, Ok(b"/"));
+        test_case("", false, false, false, Ok(b""));
+        test_case("/%FF", false, false, false, Ok(b"/\xff"));
+        test_case(
+            "a%2Fb",
+            false,
+            false,
+            false,
+            Err(PathBufError::EncodedSeparator),
+        );
+        test_case("a%5Cb", false, false, false, Ok(b"a\\b"));
+        test_case(
+            "a%5Cb",
+            false,
+            false,
+            true,
+            Err(PathBufError::EncodedSeparator),
+        );
+        test_case("a%2Fb", true, false, false, Ok(b"a/b"));
+        test_case("a%2Fb", true, false, true, Ok(b"a\\b"));
+        test_case("a%00", true, false, false, Err(PathBufError::NulCharacter));
+        test_case("a", false, true, false, Err(PathBufError::NotAbsolute));
+        test_case("/C:/dir/file", false, true, false, Ok(b"/C:/dir/file"));
+        test_case("/C:/dir/file", false, true, true, Ok(b"C:\\dir\\file"));
+        test_case("/c%7C/dir", false, true, true, Ok(b"c:\\dir"));
+        test_case("/C:", false, true, true, Ok(b"C:\\"));
+        test_case("/C:/", false, true, true, Ok(b"C:\\"));
+        test_case(
+            "//server/share/file",
+            false,
+            true,
+            true,
+            Ok(b"\\\\server\\share\\file"),
+        );
+        test_case(
+            "//server/share/file",
+            false,
+            true,
+            false,
+            Ok(b"//server/share/file"),
+        );
+        test_case("/dir/file", false, true, true, Ok(b"\\dir\\file"));
+    }
+
+    #[test]
+    fn test_path_remove_dot_segments() {
+        fn test_case(value: &str, expected: &str) {
+            let mut path = Path::try_from(value).unwrap();
+            path.remove_dot_segments();
+            assert!(!path.segments().is_empty());
+            assert_eq!(path.single_dot_segment_count, 0);
+            assert_eq!(path.double_dot_segment_count, 0);
+            assert_eq!(path.leading_double_dot_segment_count, 0);
+            assert_eq!(path.to_string(), expected);
+        }
+
+        test_case("", "");
+        test_case(".", "");
+        test_case("..", "");
+        test_case("../", "");
+        test_case("/.", "/");
+        test_case("/..", "/");
+        test_case("../../.././.././../../../.", "");
+        test_case("a/../..", "/");
+        test_case("a/../../", "/");
+        test_case("/a/../../../..", "/");
+        test_case("/a/../../../../", "/");
+        test_case("/a/./././././././c", "/a/c");
+        test_case("/a/.", "/a/");
+        test_case("/a/./", "/a/");
+        test_case("/a/..", "/");
+        test_case("/a/b/./..", "/a/");
+        test_case("/a/b/./../", "/a/");
+        test_case("/a/b/c/./../../g", "/a/g");
+        test_case("mid/content=5/../6", "mid/6");
+    }
+
+    #[test]
+    fn test_path_strip_prefix() {
+        fn test_case(value: &str, prefix: &str, expected: Option<&str>) {
+            let path = Path::try_from(value).unwrap();
+            let prefix = Path::try_from(prefix).unwrap();
+            let remainder = path.strip_prefix(&prefix);
+            assert_eq!(
+                remainder.as_ref().map(Path::to_string).as_deref(),
+                expected,
+                "{}",
+                value
+            );
+
+            if let Some(remainder) = remainder.filter(|remainder| remainder != "") {
+                assert!(remainder.is_relative());
+                assert_eq!(prefix.join_path(&remainder).unwrap(), path, "{}", value);
+            }
+        }
+
+        test_case("/api/users", "/api", Some("users"));
+        test_case("/api/users/", "/api/", Some("users/"));
+        test_case("/api", "/api/", Some(""));
+        test_case("/api/", "/api", Some(""));
+        test_case("/api//x", "/api", Some(".//x"));
+        test_case("/api//x", "/api/", Some(".//x"));
+        test_case("/api///", "/api", Some(".///"));
+        test_case("/apiary", "/api", None);
+        test_case("api/users", "/api", None);
+    }
+
+    #[test]
+    fn test_segment_from_static() {
+        fn test_case(value: &'static str) {
+            let segment = Segment::from_static(value);
+            let expected = Segment::try_from(value).unwrap();
+            assert_eq!(segment, expected);
+            assert_eq!(
+                segment.is_normalized(),
+                expected.is_normalized(),
+                "{}",
+                value
+            );
+        }
+
+        test_case("");
+        test_case("segment");
+        test_case("a:b@c");
+        test_case("%41");
+        test_case("%c3%a9");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid segment character")]
+    fn test_segment_from_static_invalid() {
+        Segment::from_static("a/b");
+    }
+
+    #[test]
+    fn test_segment_normalize() {
+        fn test_case(value: &str, expected: &str) {
+            let mut segment = Segment::try_from(value).unwrap();
+            segment.normalize();
+            assert_eq!(segment, expected);
+        }
+
+        test_case("", "");
+        test_case("%ff", "%FF");
+        test_case("%41", "A");
+    }
+
+    #[test]
+    fn test_segment_parse() {
+        use self::PathError::*;
+
+        assert_eq!(Segment::try_from("").unwrap(), "");
+        assert_eq!(Segment::try_from("segment").unwrap(), "segment");
+        assert_eq!(Segment::try_from("sEgMeNt").unwrap(), "sEgMeNt");
+        assert_eq!(Segment::try_from("%ff%ff%ff%41").unwrap(), "%ff%ff%ff%41");
+
+        assert_eq!(Segment::try_from(" "), Err(InvalidCharacter));
+        assert_eq!(Segment::try_from("/"), Err(InvalidCharacter));
+        assert_eq!(Segment::try_from("%"), Err(InvalidPercentEncoding));
+        assert_eq!(Segment::try_from("%f"), Err(InvalidPercentEncoding));
+        assert_eq!(Segment::try_from("%zz"), Err(InvalidPercentEncoding));
+    }
+}
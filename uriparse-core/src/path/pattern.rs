@@ -0,0 +1,382 @@
+//! Path Patterns
+//!
+//! A path pattern such as `"/users/{id}/files/**"` is written like a path, but some of its
+//! segments are placeholders:
+//!
+//!  - `*` matches exactly one non-empty segment.
+//!  - `**` matches any number of segments, including none.
+//!  - `{name}` matches exactly one non-empty segment and captures it under the given name.
+//!
+//! All other segments are literals. These may be percent-encoded (e.g. `"%2A"` for a literal
+//! `'*'`) and are compared against the segments of a path after percent-decoding both, so
+//! `"caf%C3%A9"` matches `"caf%c3%a9"` and `"%7Bid%7D"` matches `"{id}"`. Captured values are
+//! percent-decoded as well.
+//!
+//! Paths are matched as they are, so dot segments are not removed. If that is required, call
+//! [`Path::normalize`] before matching.
+
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use super::{Path, PathError, Segment};
+
+/// A parsed path pattern.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use uriparse::path::pattern::PathPattern;
+/// use uriparse::Path;
+///
+/// let pattern = PathPattern::try_from("/users/{id}/files/**").unwrap();
+/// let path = Path::try_from("/users/jane%20doe/files/a/b.txt").unwrap();
+///
+/// let captures = pattern.captures(&path).unwrap();
+/// assert_eq!(captures.get("id"), Some("jane doe"));
+/// assert!(!pattern.matches(&Path::try_from("/users/jane/photos").unwrap()));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PathPattern {
+    /// Whether the pattern only matches absolute paths.
+    absolute: bool,
+
+    /// The sequence of elements that compose the pattern.
+    elements: Vec<Element>,
+
+    /// The source of the pattern, used for display.
+    source: String,
+}
+
+impl PathPattern {
+    /// Returns a `str` representation of the pattern.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the captured segments if the path matches the pattern, or [`None`] otherwise.
+    ///
+    /// If the pattern contains multiple `**` placeholders, earlier ones match as few segments as
+    /// possible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::path::pattern::PathPattern;
+    /// use uriparse::Path;
+    ///
+    /// let pattern = PathPattern::try_from("/{owner}/{repository}").unwrap();
+    /// let path = Path::try_from("/rust-lang/rust").unwrap();
+    /// let captures = pattern.captures(&path).unwrap();
+    /// assert_eq!(captures.len(), 2);
+    /// assert_eq!(captures.get("owner"), Some("rust-lang"));
+    /// assert_eq!(captures.get("repository"), Some("rust"));
+    ///
+    /// let path = Path::try_from("/rust-lang/").unwrap();
+    /// assert!(pattern.captures(&path).is_none());
+    /// ```
+    pub fn captures<'path>(&self, path: &'path Path<'_>) -> Option<PathCaptures<'_, 'path>> {
+        if self.absolute != path.is_absolute() {
+            return None;
+        }
+
+        let mut captures = Vec::new();
+
+        if match_elements(&self.elements, path.segments(), &mut captures) {
+            Some(PathCaptures { captures })
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the path matches the pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::path::pattern::PathPattern;
+    /// use uriparse::Path;
+    ///
+    /// let pattern = PathPattern::try_from("/static/*/**").unwrap();
+    /// assert!(pattern.matches(&Path::try_from("/static/css").unwrap()));
+    /// assert!(pattern.matches(&Path::try_from("/static/css/main.css").unwrap()));
+    /// assert!(!pattern.matches(&Path::try_from("/static").unwrap()));
+    /// assert!(!pattern.matches(&Path::try_from("static/css").unwrap()));
+    /// ```
+    pub fn matches(&self, path: &Path<'_>) -> bool {
+        self.captures(path).is_some()
+    }
+}
+
+impl Display for PathPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(&self.source)
+    }
+}
+
+impl TryFrom<&str> for PathPattern {
+    type Error = PathPatternError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (absolute, rest) = match value.strip_prefix('/') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let mut elements: Vec<Element> = Vec::new();
+
+        for segment in rest.split('/') {
+            let element = match segment {
+                "*" => Element::Wildcard,
+                "**" => Element::MultiWildcard,
+                _ if segment.starts_with('{') && segment.ends_with('}') && segment.len() > 1 => {
+                    let name = &segment[1..segment.len() - 1];
+
+                    if name.is_empty() {
+                        return Err(PathPatternError::EmptyCaptureName);
+                    }
+
+                    if name.contains(&['{', '}'][..]) {
+                        return Err(PathPatternError::InvalidCapture);
+                    }
+
+                    let duplicate = elements
+                        .iter()
+                        .any(|element| matches!(element, Element::Capture(other) if other == name));
+
+                    if duplicate {
+                        return Err(PathPatternError::DuplicateCaptureName);
+                    }
+
+                    Element::Capture(name.to_string())
+                }
+                _ if segment.contains(&['{', '}'][..]) => {
+                    return Err(PathPatternError::InvalidCapture);
+                }
+                _ => {
+                    let segment = Segment::try_from(segment)?;
+                    Element::Literal(segment.as_decoded_bytes().into_owned())
+                }
+            };
+
+            elements.push(element);
+        }
+
+        Ok(PathPattern {
+            absolute,
+            elements,
+            source: value.to_string(),
+        })
+    }
+}
+
+/// The segments captured by matching a path against a [`PathPattern`].
+///
+/// This is created by [`PathPattern::captures`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PathCaptures<'pattern, 'path> {
+    /// The names and percent-decoded values of the captures, in the order of the pattern.
+    captures: Vec<(&'pattern str, Cow<'path, str>)>,
+}
+
+impl PathCaptures<'_, '_> {
+    /// Returns the percent-decoded value of the capture with the given name, if any.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.captures
+            .iter()
+            .find(|(capture_name, _)| *capture_name == name)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Returns whether there are no captures.
+    pub fn is_empty(&self) -> bool {
+        self.captures.is_empty()
+    }
+
+    /// Returns an iterator over the names and percent-decoded values of the captures, in the
+    /// order of the pattern.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.captures
+            .iter()
+            .map(|(name, value)| (*name, value.as_ref()))
+    }
+
+    /// Returns the number of captures.
+    pub fn len(&self) -> usize {
+        self.captures.len()
+    }
+}
+
+/// An error representing an invalid path pattern.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum PathPatternError {
+    /// The pattern contained multiple captures with the same name.
+    DuplicateCaptureName,
+
+    /// The pattern contained a capture without a name, i.e. `"{}"`.
+    EmptyCaptureName,
+
+    /// The pattern contained a brace that is not part of a capture spanning an entire segment,
+    /// e.g. `"file-{id}"`.
+    InvalidCapture,
+
+    /// A literal segment of the pattern was invalid.
+    Path(PathError),
+}
+
+impl Display for PathPatternError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::PathPatternError::*;
+
+        match self {
+            DuplicateCaptureName => write!(formatter, "duplicate path pattern capture name"),
+            EmptyCaptureName => write!(formatter, "empty path pattern capture name"),
+            InvalidCapture => write!(formatter, "invalid path pattern capture"),
+            Path(error) => error.fmt(formatter),
+        }
+    }
+}
+
+impl Error for PathPatternError {}
+
+impl From<PathError> for PathPatternError {
+    fn from(value: PathError) -> Self {
+        PathPatternError::Path(value)
+    }
+}
+
+/// An element of a path pattern, matching one or more segments.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Element {
+    /// Matches one non-empty segment and captures it.
+    Capture(String),
+
+    /// Matches one segment that is equal to the given percent-decoded bytes.
+    Literal(Vec<u8>),
+
+    /// Matches any number of segments.
+    MultiWildcard,
+
+    /// Matches one non-empty segment.
+    Wildcard,
+}
+
+/// Matches the segments against the elements, appending the captured segments on success.
+fn match_elements<'pattern, 'path>(
+    elements: &'pattern [Element],
+    segments: &'path [Segment<'_>],
+    captures: &mut Vec<(&'pattern str, Cow<'path, str>)>,
+) -> bool {
+    let (element, elements) = match elements.split_first() {
+        Some(split) => split,
+        None => return segments.is_empty(),
+    };
+
+    if let Element::MultiWildcard = element {
+        let captures_length = captures.len();
+
+        for skipped in 0..=segments.len() {
+            if match_elements(elements, &segments[skipped..], captures) {
+                return true;
+            }
+
+            captures.truncate(captures_length);
+        }
+
+        return false;
+    }
+
+    let (segment, segments) = match segments.split_first() {
+        Some(split) => split,
+        None => return false,
+    };
+
+    match element {
+        Element::Capture(name) if !segment.is_empty() => {
+            captures.push((name, segment.as_decoded()));
+        }
+        Element::Literal(literal) if *segment.as_decoded_bytes() == **literal => {}
+        Element::Wildcard if !segment.is_empty() => {}
+        _ => return false,
+    }
+
+    match_elements(elements, segments, captures)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_path_pattern_captures() {
+        fn test_case(pattern: &str, path: &str, expected: Option<&[(&str, &str)]>) {
+            let pattern = PathPattern::try_from(pattern).unwrap();
+            let path = Path::try_from(path).unwrap();
+            let captures = pattern.captures(&path);
+            let captures: Option<Vec<_>> =
+                captures.as_ref().map(|captures| captures.iter().collect());
+            assert_eq!(captures.as_deref(), expected);
+        }
+
+        test_case("", "", Some(&[]));
+        test_case("/", "/", Some(&[]));
+        test_case("/", "", None);
+        test_case("/a/b", "/a/b", Some(&[]));
+        test_case("/a/b", "/a/b/", None);
+        test_case("/a/b/", "/a/b/", Some(&[]));
+        test_case("a/b", "/a/b", None);
+        test_case("/caf%C3%A9", "/caf%c3%a9", Some(&[]));
+        test_case("/%2A", "/*", Some(&[]));
+        test_case("/%2A", "/a", None);
+        test_case("/%7Bid%7D", "/%7bid%7d", Some(&[]));
+        test_case("/*", "/a", Some(&[]));
+        test_case("/*", "/", None);
+        test_case("/*", "/a/b", None);
+        test_case("/**", "/", Some(&[]));
+        test_case("/a/**", "/a", Some(&[]));
+        test_case("/a/**", "/a/", Some(&[]));
+        test_case("/a/**", "/a/b/c", Some(&[]));
+        test_case("/a/**/c", "/a/c", Some(&[]));
+        test_case("/a/**/c", "/a/b//c", Some(&[]));
+        test_case("/a/**/c", "/a/b/d", None);
+        test_case(
+            "/users/{id}",
+            "/users/jane%20doe",
+            Some(&[("id", "jane doe")]),
+        );
+        test_case("/users/{id}", "/users/", None);
+        test_case(
+            "/**/{name}/**/{file}",
+            "/a/b/c/d",
+            Some(&[("name", "a"), ("file", "d")]),
+        );
+        test_case("{a}/*/{b}", "x/y/z", Some(&[("a", "x"), ("b", "z")]));
+    }
+
+    #[test]
+    fn test_path_pattern_parse() {
+        use self::PathPatternError::*;
+
+        fn test_case(value: &str, expected: Result<(), PathPatternError>) {
+            assert_eq!(PathPattern::try_from(value).map(|_| ()), expected);
+        }
+
+        test_case("/users/{id}/**", Ok(()));
+        test_case("/a*/*b/***", Ok(()));
+        test_case("/{}", Err(EmptyCaptureName));
+        test_case("/{a}/{a}", Err(DuplicateCaptureName));
+        test_case("/file-{id}", Err(InvalidCapture));
+        test_case("/{a{b}", Err(InvalidCapture));
+        test_case("/{", Err(InvalidCapture));
+        test_case("/a b", Err(Path(PathError::InvalidCharacter)));
+        test_case("/%ZZ", Err(Path(PathError::InvalidPercentEncoding)));
+        test_case("/a?b", Err(Path(PathError::InvalidCharacter)));
+    }
+}
@@ -0,0 +1,301 @@
+//! Segment List
+//!
+//! A small-size-optimized list of path segments. Most paths only consist of a few segments, so
+//! storing these inline avoids a heap allocation for every parsed path. Unused inline slots hold
+//! empty borrowed segments, which do not allocate themselves. Paths constructed in a `const`
+//! context borrow their segments instead, which are copied into owned storage when modified.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+
+use super::Segment;
+
+/// The number of segments that can be stored without a heap allocation.
+const INLINE_CAPACITY: usize = 4;
+
+/// A list of segments that is stored inline until it exceeds [`INLINE_CAPACITY`] segments.
+#[derive(Clone, Debug)]
+pub(super) enum SegmentList<'path> {
+    /// The segments are borrowed, e.g. from a `static` slice.
+    Borrowed(&'path [Segment<'path>]),
+
+    /// The segments are stored on the heap.
+    Heap(Vec<Segment<'path>>),
+
+    /// The first `length` segments are stored inline, the rest are empty.
+    Inline {
+        length: usize,
+        segments: [Segment<'path>; INLINE_CAPACITY],
+    },
+}
+
+impl<'path> SegmentList<'path> {
+    /// Returns the segments of the list as a slice.
+    pub const fn as_slice(&self) -> &[Segment<'path>] {
+        match self {
+            SegmentList::Borrowed(segments) => segments,
+            SegmentList::Heap(segments) => segments.as_slice(),
+            SegmentList::Inline { length, segments } => segments.split_at(*length).0,
+        }
+    }
+
+    /// Removes all segments from the list.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Converts each segment of the list using the given function, reusing the storage kind.
+    /// Borrowed segments are cloned into owned storage first.
+    pub fn map<'other, TFunction>(self, function: TFunction) -> SegmentList<'other>
+    where
+        TFunction: FnMut(Segment<'path>) -> Segment<'other>,
+    {
+        match self {
+            SegmentList::Borrowed(segments) => segments.iter().cloned().map(function).collect(),
+            SegmentList::Heap(segments) => {
+                SegmentList::Heap(segments.into_iter().map(function).collect())
+            }
+            SegmentList::Inline { length, segments } => SegmentList::Inline {
+                length,
+                segments: segments.map(function),
+            },
+        }
+    }
+
+    /// Constructs a new empty list. This does not allocate.
+    pub fn new() -> Self {
+        SegmentList::Inline {
+            length: 0,
+            segments: [
+                Segment::empty(),
+                Segment::empty(),
+                Segment::empty(),
+                Segment::empty(),
+            ],
+        }
+    }
+
+    /// Removes the last segment from the list and returns it, or [`None`] if it is empty.
+    pub fn pop(&mut self) -> Option<Segment<'path>> {
+        match self {
+            SegmentList::Borrowed(segments) => {
+                let (last, rest) = segments.split_last()?;
+                *segments = rest;
+                Some(last.clone())
+            }
+            SegmentList::Heap(segments) => segments.pop(),
+            SegmentList::Inline { length: 0, .. } => None,
+            SegmentList::Inline { length, segments } => {
+                *length -= 1;
+                Some(mem::replace(&mut segments[*length], Segment::empty()))
+            }
+        }
+    }
+
+    /// Appends a segment to the end of the list, moving the segments to the heap if the inline
+    /// capacity is exceeded.
+    pub fn push(&mut self, segment: Segment<'path>) {
+        match self {
+            SegmentList::Borrowed(_) => {
+                self.make_owned();
+                self.push(segment);
+            }
+            SegmentList::Heap(segments) => segments.push(segment),
+            SegmentList::Inline { length, segments } if *length < INLINE_CAPACITY => {
+                segments[*length] = segment;
+                *length += 1;
+            }
+            SegmentList::Inline { segments, .. } => {
+                let mut heap_segments = Vec::with_capacity(INLINE_CAPACITY * 2);
+                heap_segments.extend(segments.iter_mut().map(|segment| {
+                    // Inline segments are replaced with empty borrowed segments, so nothing is
+                    // cloned when moving to the heap.
+                    mem::replace(segment, Segment::empty())
+                }));
+                heap_segments.push(segment);
+                *self = SegmentList::Heap(heap_segments);
+            }
+        }
+    }
+
+    /// Shortens the list to the given length, dropping the remaining segments. This has no effect
+    /// if the list is already shorter.
+    pub fn truncate(&mut self, new_length: usize) {
+        match self {
+            SegmentList::Borrowed(segments) => {
+                *segments = &segments[..new_length.min(segments.len())]
+            }
+            SegmentList::Heap(segments) => segments.truncate(new_length),
+            SegmentList::Inline { length, segments } => {
+                for segment in segments.iter_mut().take(*length).skip(new_length) {
+                    *segment = Segment::empty();
+                }
+
+                *length = (*length).min(new_length);
+            }
+        }
+    }
+
+    /// Moves borrowed segments into owned storage, cloning them. This has no effect if the
+    /// segments are already owned.
+    fn make_owned(&mut self) {
+        if let SegmentList::Borrowed(segments) = self {
+            *self = segments.iter().cloned().collect();
+        }
+    }
+}
+
+impl<'path> Deref for SegmentList<'path> {
+    type Target = [Segment<'path>];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for SegmentList<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.make_owned();
+
+        match self {
+            SegmentList::Borrowed(_) => unreachable!("segments should be owned"),
+            SegmentList::Heap(segments) => segments,
+            SegmentList::Inline { length, segments } => &mut segments[..*length],
+        }
+    }
+}
+
+impl Eq for SegmentList<'_> {}
+
+impl<'path> Extend<Segment<'path>> for SegmentList<'path> {
+    fn extend<TIterator>(&mut self, iterator: TIterator)
+    where
+        TIterator: IntoIterator<Item = Segment<'path>>,
+    {
+        for segment in iterator {
+            self.push(segment);
+        }
+    }
+}
+
+impl<'path> From<Vec<Segment<'path>>> for SegmentList<'path> {
+    fn from(value: Vec<Segment<'path>>) -> Self {
+        if value.len() > INLINE_CAPACITY {
+            SegmentList::Heap(value)
+        } else {
+            value.into_iter().collect()
+        }
+    }
+}
+
+impl<'path> FromIterator<Segment<'path>> for SegmentList<'path> {
+    fn from_iter<TIterator>(iterator: TIterator) -> Self
+    where
+        TIterator: IntoIterator<Item = Segment<'path>>,
+    {
+        let mut segments = SegmentList::new();
+        segments.extend(iterator);
+        segments
+    }
+}
+
+impl Hash for SegmentList<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        (**self).hash(state)
+    }
+}
+
+impl Ord for SegmentList<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl PartialEq for SegmentList<'_> {
+    fn eq(&self, other: &SegmentList<'_>) -> bool {
+        **self == **other
+    }
+}
+
+impl PartialOrd for SegmentList<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'path> serde::Deserialize<'de> for SegmentList<'path> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::<Segment<'path>>::deserialize(deserializer).map(SegmentList::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SegmentList<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    fn segment(value: &str) -> Segment<'_> {
+        Segment::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn test_segment_list_push_pop() {
+        let mut segments = SegmentList::new();
+        assert!(segments.is_empty());
+
+        for value in &["a", "b", "c", "d"] {
+            segments.push(segment(value));
+        }
+
+        assert!(matches!(segments, SegmentList::Inline { length: 4, .. }));
+
+        segments.push(segment("e"));
+        assert!(matches!(segments, SegmentList::Heap(_)));
+        assert_eq!(segments.len(), 5);
+        assert_eq!(segments[4], "e");
+        assert_eq!(segments.pop().unwrap(), "e");
+
+        let mut segments: SegmentList<'_> = vec![segment("a"), segment("b")].into();
+        assert!(matches!(segments, SegmentList::Inline { length: 2, .. }));
+        assert_eq!(segments.pop().unwrap(), "b");
+        assert_eq!(segments.pop().unwrap(), "a");
+        assert!(segments.pop().is_none());
+    }
+
+    #[test]
+    fn test_segment_list_truncate() {
+        let mut segments: SegmentList<'_> = ["a", "b", "c"].iter().map(|v| segment(v)).collect();
+        segments.truncate(5);
+        assert_eq!(segments.len(), 3);
+
+        segments.truncate(1);
+        assert_eq!(&*segments, &[segment("a")]);
+
+        segments.push(segment("d"));
+        assert_eq!(&*segments, &[segment("a"), segment("d")]);
+
+        segments.clear();
+        assert!(segments.is_empty());
+    }
+}
@@ -0,0 +1,1859 @@
+//! Query Component
+//!
+//! See [[RFC3986, Section 3.4](https://tools.ietf.org/html/rfc3986#section-3.4)].
+//!
+//! The RFC does not define any structure for the query, but the common convention of `&`-separated
+//! `key=value` pairs is supported through [`Query::pairs`] and [`Query::from_pairs`]. Anything
+//! beyond that (e.g. nested keys) will need another crate (e.g.
+//! [queryst](https://github.com/rustless/queryst)).
+
+#[cfg(feature = "serde-query")]
+mod de;
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::convert::{Infallible, TryFrom};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::{self, FromStr, Split};
+
+#[cfg(feature = "serde-query")]
+pub use self::de::QueryDeserializeError;
+
+use crate::utility::{
+    get_percent_encoded_value, get_percent_encoded_value_at, normalize_string, percent_decode,
+    percent_decoded_equality, percent_encode, percent_encoded_cmp, percent_encoded_equality,
+    percent_encoded_hash, UNRESERVED_CHAR_MAP,
+};
+
+/// A map of byte characters that determines if a character is a valid query character.
+#[rustfmt::skip]
+const QUERY_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$', b'%', b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.', b'/', // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0, b'=',    0, b'?', // 3
+ b'@', b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A map of byte characters that can be left unencoded when encoding arbitrary text as a query.
+///
+/// This is the same as [`QUERY_CHAR_MAP`] except that `'%'` is excluded so that it is always
+/// percent-encoded.
+#[rustfmt::skip]
+const QUERY_ENCODE_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$',    0, b'&',b'\'', b'(', b')', b'*', b'+', b',', b'-', b'.', b'/', // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0, b'=',    0, b'?', // 3
+ b'@', b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// A map of byte characters that can be left unencoded in the key or value of a query pair.
+///
+/// This is the same as [`QUERY_CHAR_MAP`] except that `'%'`, `'&'`, `'+'`, `';'`, and `'='` are
+/// excluded so that they are always percent-encoded. `';'` is included so that the encoded pairs
+/// are also correctly split by consumers that treat it as a pair separator.
+#[rustfmt::skip]
+const QUERY_PAIR_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$',    0,    0,b'\'', b'(', b')', b'*',    0, b',', b'-', b'.', b'/', // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':',    0,    0,    0,    0, b'?', // 3
+ b'@', b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// The query component as defined in
+/// [[RFC3986, Section 3.4](https://tools.ietf.org/html/rfc3986#section-3.4)].
+///
+/// The query is case-sensitive. Furthermore, percent-encoding plays no role in equality checking
+/// for characters in the unreserved character set meaning that `"query"` and `"que%72y"` are
+/// identical. Both of these attributes are reflected in the equality and hash functions.
+///
+/// However, be aware that just because percent-encoding plays no role in equality checking does not
+/// mean that the query is normalized. If the query needs to be normalized, use the
+/// [`Query::normalize`] function.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Query<'query> {
+    /// Whether the query is normalized.
+    normalized: bool,
+
+    /// The internal query source that is either owned or borrowed.
+    query: Cow<'query, str>,
+}
+
+impl Query<'_> {
+    /// Returns a new query which is identical but has a lifetime tied to this query.
+    pub fn as_borrowed(&self) -> Query<'_> {
+        use self::Cow::*;
+
+        let query = match &self.query {
+            Borrowed(borrowed) => *borrowed,
+            Owned(owned) => owned.as_str(),
+        };
+
+        Query {
+            normalized: self.normalized,
+            query: Cow::Borrowed(query),
+        }
+    }
+
+    /// Appends the given `key=value` pair to the end of the query.
+    ///
+    /// The key and value are percent-encoded in the same way as [`Query::from_pairs`]. Existing
+    /// pairs with the same key are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let mut query = Query::try_from("page=1").unwrap();
+    /// query.append_pair("page", "2");
+    /// query.append_pair("q", "a b");
+    /// assert_eq!(query, "page=1&page=2&q=a%20b");
+    /// ```
+    pub fn append_pair(&mut self, key: &str, value: &str) {
+        encode_pair(key, value, false, self.query.to_mut());
+    }
+
+    /// Returns the percent-decoded query.
+    ///
+    /// If the query contains no percent-encodings, the returned value borrows from the query.
+    /// Decoded bytes that are not valid UTF-8 are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("query").unwrap();
+    /// assert!(matches!(query.as_decoded(), Cow::Borrowed("query")));
+    ///
+    /// let query = Query::try_from("a%20b%26c").unwrap();
+    /// assert_eq!(query.as_decoded(), "a b&c");
+    /// ```
+    pub fn as_decoded(&self) -> Cow<'_, str> {
+        percent_decode(&self.query)
+    }
+
+    /// Returns a `str` representation of the query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("query").unwrap();
+    /// assert_eq!(query.as_str(), "query");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.query
+    }
+
+    /// Constructs a query from arbitrary unencoded text.
+    ///
+    /// Any character that is not allowed in a query, as well as `%` itself, is percent-encoded,
+    /// so the returned query always decodes back to the given text. All other characters
+    /// (including `&` and `=`) are left as is. The resulting query is always normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::encode("a b#c&d=100%");
+    /// assert_eq!(query, "a%20b%23c&d=100%25");
+    /// assert_eq!(query.as_decoded(), "a b#c&d=100%");
+    /// ```
+    pub fn encode(value: &str) -> Query<'static> {
+        let mut query = String::with_capacity(value.len());
+        percent_encode(value.as_bytes(), &QUERY_ENCODE_CHAR_MAP, &mut query);
+
+        Query {
+            normalized: true,
+            query: Cow::from(query),
+        }
+    }
+
+    /// Deserializes the `key=value` pairs of the query into the given type.
+    ///
+    /// The pairs are percent-decoded as returned by [`Query::pairs`] and can be deserialized into
+    /// any type that deserializes from a map (e.g. a struct or a `HashMap`) or from a sequence of
+    /// `(key, value)` tuples. Primitives such as numbers and booleans are parsed from their string
+    /// representation, and a pair without a `=` has an empty value.
+    ///
+    /// This function is only available with the `serde-query` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use serde::Deserialize;
+    /// use uriparse::Query;
+    ///
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Search {
+    ///     q: String,
+    ///     page: u32,
+    ///     lang: Option<String>,
+    /// }
+    ///
+    /// let query = Query::try_from("q=rust%20uri&page=2").unwrap();
+    /// let search: Search = query.deserialize_into().unwrap();
+    /// assert_eq!(
+    ///     search,
+    ///     Search {
+    ///         q: "rust uri".to_string(),
+    ///         page: 2,
+    ///         lang: None,
+    ///     }
+    /// );
+    /// ```
+    #[cfg(feature = "serde-query")]
+    pub fn deserialize_into<'de, T>(&'de self) -> Result<T, QueryDeserializeError>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        T::deserialize(de::PairsDeserializer::new(self.pairs()))
+    }
+
+    /// Returns whether the percent-decoded query is equal to the given unencoded string.
+    ///
+    /// Unlike comparing against [`Query::as_decoded`], this does not allocate, and percent-encoded
+    /// bytes that are not valid UTF-8 never compare equal to a replacement character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("a%20b%3Dc").unwrap();
+    /// assert!(query.eq_decoded("a b=c"));
+    /// assert!(!query.eq_decoded("a%20b%3Dc"));
+    /// ```
+    pub fn eq_decoded(&self, other: &str) -> bool {
+        percent_decoded_equality(self.query.as_bytes(), other.as_bytes())
+    }
+
+    /// Returns whether the two queries contain the same `key=value` pairs, ignoring their order.
+    ///
+    /// The pairs are compared after percent-decoding as returned by [`Query::pairs`], and repeated
+    /// pairs must appear the same number of times in both queries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("a=1&b=2").unwrap();
+    /// assert!(query.eq_unordered(&Query::try_from("b=2&%61=1").unwrap()));
+    /// assert!(!query.eq_unordered(&Query::try_from("b=2&a=1&a=1").unwrap()));
+    /// ```
+    pub fn eq_unordered(&self, other: &Query) -> bool {
+        let mut left: Vec<_> = self.pairs().collect();
+        let mut right: Vec<_> = other.pairs().collect();
+
+        if left.len() != right.len() {
+            return false;
+        }
+
+        left.sort_unstable();
+        right.sort_unstable();
+        left == right
+    }
+
+    /// Returns an iterator over the `key=value` pairs of the query using the
+    /// `application/x-www-form-urlencoded` rules.
+    ///
+    /// This is the same as [`Query::pairs`] except that `+` is decoded as a space, matching the
+    /// way HTML forms are submitted. A literal `+` must be percent-encoded as `%2B`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("name=John+Doe&sum=1+%2B+1").unwrap();
+    /// let pairs: Vec<_> = query.form_pairs().collect();
+    /// assert_eq!(pairs[0], ("name".into(), Some("John Doe".into())));
+    /// assert_eq!(pairs[1], ("sum".into(), Some("1 + 1".into())));
+    /// ```
+    pub fn form_pairs(&self) -> QueryPairs<'_> {
+        QueryPairs {
+            form: true,
+            pairs: self.query.split(QueryPairSeparator::Ampersand.characters()),
+        }
+    }
+
+    /// Constructs a query from the given `key=value` pairs using the
+    /// `application/x-www-form-urlencoded` rules.
+    ///
+    /// This is the same as [`Query::from_pairs`] except that spaces are encoded as `+`, matching
+    /// the way HTML forms are submitted. The pairs can be recovered with [`Query::form_pairs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::from_form_pairs(vec![("name", "John Doe"), ("sum", "1 + 1")]);
+    /// assert_eq!(query, "name=John+Doe&sum=1+%2B+1");
+    /// ```
+    pub fn from_form_pairs<I, K, V>(pairs: I) -> Query<'static>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        encode_pairs(pairs, true)
+    }
+
+    /// Constructs a query from the given byte string, percent-encoding any invalid characters.
+    ///
+    /// Unlike [`TryFrom`], this never fails. Any byte that is not allowed in a query (e.g. spaces,
+    /// `"` or `#`) is percent-encoded, as is any `%` that does not start a valid percent-encoding.
+    /// Valid percent-encodings are kept as is. If the byte string is already a valid query, it is
+    /// borrowed without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::from_lossy(b"q=\"a b\"&p=100%&x=%41");
+    /// assert_eq!(query, "q=%22a%20b%22&p=100%25&x=%41");
+    /// ```
+    pub fn from_lossy(value: &[u8]) -> Query<'_> {
+        if let Ok(query) = Query::try_from(value) {
+            return query;
+        }
+
+        let mut query = String::with_capacity(value.len());
+        let mut index = 0;
+
+        while index < value.len() {
+            let byte = value[index];
+
+            if byte == b'%'
+                && get_percent_encoded_value(
+                    value.get(index + 1).cloned(),
+                    value.get(index + 2).cloned(),
+                )
+                .is_ok()
+            {
+                // Unsafe: The percent-encoding was validated above, so it is valid ASCII-US.
+                query.push_str(unsafe { str::from_utf8_unchecked(&value[index..index + 3]) });
+                index += 3;
+            } else {
+                percent_encode(&value[index..=index], &QUERY_ENCODE_CHAR_MAP, &mut query);
+                index += 1;
+            }
+        }
+
+        let normalized = parse_query(query.as_bytes())
+            .map(|(query, _)| query.normalized)
+            .unwrap_or(false);
+
+        Query {
+            normalized,
+            query: Cow::from(query),
+        }
+    }
+
+    /// Constructs a query from the given `key=value` pairs.
+    ///
+    /// Each key and value is percent-encoded such that it can be recovered with [`Query::pairs`].
+    /// The pairs are then joined with `&`. The resulting query is always normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::from_pairs(vec![("name", "J\u{fc}rgen"), ("a&b", "1 + 1 = 2")]);
+    /// assert_eq!(query, "name=J%C3%BCrgen&a%26b=1%20%2B%201%20%3D%202");
+    /// ```
+    pub fn from_pairs<I, K, V>(pairs: I) -> Query<'static>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        encode_pairs(pairs, false)
+    }
+
+    /// Constructs a query from the given string in a `const` context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid query. In a `const` or `static` item, this results in a
+    /// compilation error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Query;
+    ///
+    /// const QUERY: Query<'static> = Query::from_static("format=json");
+    /// assert_eq!(QUERY.get("format").unwrap(), "json");
+    /// ```
+    pub const fn from_static(value: &'static str) -> Query<'static> {
+        match scan_query(value.as_bytes()) {
+            Ok((end_index, normalized)) if end_index == value.len() => Query {
+                normalized,
+                query: Cow::Borrowed(value),
+            },
+            Err((QueryError::InvalidPercentEncoding, _)) => {
+                panic!("invalid query percent encoding")
+            }
+            _ => panic!("invalid query character"),
+        }
+    }
+
+    /// Returns the percent-decoded value of the first pair with the given key.
+    ///
+    /// Keys are compared after percent-decoding. A pair without a `=` has an empty value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("q=a%20b&flag&q=c").unwrap();
+    /// assert_eq!(query.get("q").unwrap(), "a b");
+    /// assert_eq!(query.get("flag").unwrap(), "");
+    /// assert_eq!(query.get("missing"), None);
+    /// ```
+    pub fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.get_with_separator(key, QueryPairSeparator::Ampersand)
+    }
+
+    /// Returns an iterator over the percent-decoded values of all pairs with the given key.
+    ///
+    /// Keys are compared after percent-decoding. A pair without a `=` has an empty value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("tag=a&other=x&t%61g=b").unwrap();
+    /// let tags: Vec<_> = query.get_all("tag").collect();
+    /// assert_eq!(tags, vec!["a", "b"]);
+    /// ```
+    pub fn get_all<'a, 'key>(&'a self, key: &'key str) -> impl Iterator<Item = Cow<'a, str>> + 'key
+    where
+        'a: 'key,
+    {
+        self.get_all_with_separator(key, QueryPairSeparator::Ampersand)
+    }
+
+    /// Returns an iterator over the percent-decoded values of all pairs with the given key using
+    /// the given pair separator.
+    ///
+    /// This is the same as [`Query::get_all`] except that the query is split on the characters of
+    /// the given separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::query::{Query, QueryPairSeparator};
+    ///
+    /// let query = Query::try_from("tag=a;other=x&tag=b").unwrap();
+    /// let tags: Vec<_> = query
+    ///     .get_all_with_separator("tag", QueryPairSeparator::AmpersandOrSemicolon)
+    ///     .collect();
+    /// assert_eq!(tags, vec!["a", "b"]);
+    /// ```
+    pub fn get_all_with_separator<'a, 'key>(
+        &'a self,
+        key: &'key str,
+        separator: QueryPairSeparator,
+    ) -> impl Iterator<Item = Cow<'a, str>> + 'key
+    where
+        'a: 'key,
+    {
+        self.pairs_with_separator(separator)
+            .filter(move |(pair_key, _)| pair_key == key)
+            .map(|(_, value)| value.unwrap_or(Cow::Borrowed("")))
+    }
+
+    /// Returns the percent-decoded value of the first pair with the given key using the given pair
+    /// separator.
+    ///
+    /// This is the same as [`Query::get`] except that the query is split on the characters of the
+    /// given separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::query::{Query, QueryPairSeparator};
+    ///
+    /// let query = Query::try_from("a=1;b=2").unwrap();
+    /// assert_eq!(query.get_with_separator("b", QueryPairSeparator::Ampersand), None);
+    /// assert_eq!(
+    ///     query
+    ///         .get_with_separator("b", QueryPairSeparator::AmpersandOrSemicolon)
+    ///         .unwrap(),
+    ///     "2"
+    /// );
+    /// ```
+    pub fn get_with_separator(
+        &self,
+        key: &str,
+        separator: QueryPairSeparator,
+    ) -> Option<Cow<'_, str>> {
+        self.get_all_with_separator(key, separator).next()
+    }
+
+    /// Converts the [`Query`] into an owned copy.
+    ///
+    /// If you construct the query from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the query will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Query<'static> {
+        Query {
+            normalized: self.normalized,
+            query: Cow::from(self.query.into_owned()),
+        }
+    }
+
+    /// Returns whether the query is normalized.
+    ///
+    /// A normalized query will have no bytes that are in the unreserved character set
+    /// percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// This function runs in constant-time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("query").unwrap();
+    /// assert!(query.is_normalized());
+    ///
+    /// let mut query = Query::try_from("%ff%ff").unwrap();
+    /// assert!(!query.is_normalized());
+    /// query.normalize();
+    /// assert!(query.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Normalizes the query such that it will have no bytes that are in the unreserved character
+    /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// If the query is already normalized, the function will return immediately. Otherwise, if the
+    /// query is not owned, this function will perform an allocation to clone it. The normalization
+    /// itself though, is done in-place with no extra memory allocations required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let mut query = Query::try_from("query").unwrap();
+    /// query.normalize();
+    /// assert_eq!(query, "query");
+    ///
+    /// let mut query = Query::try_from("%ff%41").unwrap();
+    /// assert_eq!(query, "%ff%41");
+    /// query.normalize();
+    /// assert_eq!(query, "%FFA");
+    /// ```
+    pub fn normalize(&mut self) {
+        if !self.normalized {
+            // Unsafe: Queries must be valid ASCII-US, so this is safe.
+            unsafe { normalize_string(self.query.to_mut(), true) };
+            self.normalized = true;
+        }
+    }
+
+    /// Normalizes the query according to the given policy.
+    ///
+    /// The query is always normalized in the same way as [`Query::normalize`]. In addition, the
+    /// policy can enable stronger normalizations of the `key=value` pairs, where pairs are compared
+    /// after percent-decoding and split on the characters of the policy's
+    /// [`separator`](QueryNormalizationPolicy::separator). If any of these are enabled, the query
+    /// is rejoined from its pairs with `&`, which removes empty segments between separators (e.g.
+    /// in `"a&&b"`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::query::{Query, QueryNormalizationPolicy};
+    ///
+    /// let policy = QueryNormalizationPolicy {
+    ///     collapse_duplicate_keys: true,
+    ///     drop_empty_pairs: true,
+    ///     sort_pairs: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let mut query = Query::try_from("b=2&=&a=%31&b=3").unwrap();
+    /// query.normalize_with_policy(policy);
+    /// assert_eq!(query, "a=1&b=2");
+    /// ```
+    pub fn normalize_with_policy(&mut self, policy: QueryNormalizationPolicy) {
+        self.normalize();
+
+        if !policy.collapse_duplicate_keys && !policy.drop_empty_pairs && !policy.sort_pairs {
+            return;
+        }
+
+        let mut keys = HashSet::new();
+        let mut pairs = Vec::new();
+
+        for raw_pair in self
+            .query
+            .split(policy.separator.characters())
+            .filter(|pair| !pair.is_empty())
+        {
+            let (key, value) = decode_pair(raw_pair, false);
+
+            if policy.drop_empty_pairs
+                && key.is_empty()
+                && value.as_deref().unwrap_or("").is_empty()
+            {
+                continue;
+            }
+
+            if policy.collapse_duplicate_keys && !keys.insert(key.clone()) {
+                continue;
+            }
+
+            pairs.push((raw_pair, key, value));
+        }
+
+        if policy.sort_pairs {
+            pairs.sort_by(|left, right| (&left.1, &left.2).cmp(&(&right.1, &right.2)));
+        }
+
+        let query = pairs
+            .iter()
+            .map(|(raw_pair, _, _)| *raw_pair)
+            .collect::<Vec<_>>()
+            .join("&");
+        self.query = Cow::from(query);
+    }
+
+    /// Returns an iterator over the `key=value` pairs of the query.
+    ///
+    /// The query is split on `&` into pairs and each pair is split on the first `=` into a key and
+    /// a value. Empty pairs are skipped. If a pair has no `=`, its value will be [`None`]. Both the
+    /// key and the value are percent-decoded, and decoded bytes that are not valid UTF-8 are
+    /// replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("name=J%C3%BCrgen&flag&empty=").unwrap();
+    /// let pairs: Vec<_> = query.pairs().collect();
+    /// assert_eq!(pairs[0], ("name".into(), Some("J\u{fc}rgen".into())));
+    /// assert_eq!(pairs[1], ("flag".into(), None));
+    /// assert_eq!(pairs[2], ("empty".into(), Some("".into())));
+    /// ```
+    pub fn pairs(&self) -> QueryPairs<'_> {
+        self.pairs_with_separator(QueryPairSeparator::Ampersand)
+    }
+
+    /// Returns an iterator over the `key=value` pairs of the query using the given pair separator.
+    ///
+    /// This is the same as [`Query::pairs`] except that the query is split on the characters of
+    /// the given separator. This allows processing legacy queries that use `;` to separate pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::query::{Query, QueryPairSeparator};
+    ///
+    /// let query = Query::try_from("a=1;b=2&c=%3B").unwrap();
+    /// let pairs: Vec<_> = query
+    ///     .pairs_with_separator(QueryPairSeparator::AmpersandOrSemicolon)
+    ///     .collect();
+    /// assert_eq!(pairs[0], ("a".into(), Some("1".into())));
+    /// assert_eq!(pairs[1], ("b".into(), Some("2".into())));
+    /// assert_eq!(pairs[2], ("c".into(), Some(";".into())));
+    /// ```
+    pub fn pairs_with_separator(&self, separator: QueryPairSeparator) -> QueryPairs<'_> {
+        QueryPairs {
+            form: false,
+            pairs: self.query.split(separator.characters()),
+        }
+    }
+
+    /// Removes all pairs with the given key from the query.
+    ///
+    /// Keys are compared after percent-decoding. Returns whether any pairs were removed. The
+    /// remaining pairs and empty segments between separators are left as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let mut query = Query::try_from("utm_source=x&page=2&utm%5Fsource=y").unwrap();
+    /// assert!(query.remove_key("utm_source"));
+    /// assert_eq!(query, "page=2");
+    /// assert!(!query.remove_key("utm_source"));
+    /// ```
+    pub fn remove_key(&mut self, key: &str) -> bool {
+        self.remove_key_with_separator(key, QueryPairSeparator::Ampersand)
+    }
+
+    /// Removes all pairs with the given key from the query using the given pair separator.
+    ///
+    /// This is the same as [`Query::remove_key`] except that the query is split on the characters
+    /// of the given separator. The separators between the remaining pairs are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::query::{Query, QueryPairSeparator};
+    ///
+    /// let separator = QueryPairSeparator::AmpersandOrSemicolon;
+    /// let mut query = Query::try_from("a=1;utm_source=x;b=2").unwrap();
+    /// assert!(query.remove_key_with_separator("utm_source", separator));
+    /// assert_eq!(query, "a=1;b=2");
+    /// ```
+    pub fn remove_key_with_separator(&mut self, key: &str, separator: QueryPairSeparator) -> bool {
+        let mut removed = false;
+        let query = rewrite_segments(&self.query, separator, |segment| {
+            if !segment.is_empty() && pair_has_key(segment, key) {
+                removed = true;
+                None
+            } else {
+                Some(Cow::Borrowed(segment))
+            }
+        });
+
+        if removed {
+            self.set_raw_query(query);
+        }
+
+        removed
+    }
+
+    /// Sets the value of the given key in the query.
+    ///
+    /// The first pair with the given key has its value replaced and all further pairs with the same
+    /// key are removed. If there is no pair with the given key, the pair is appended to the end of
+    /// the query. Keys are compared after percent-decoding. The remaining pairs and empty segments
+    /// between separators are left as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let mut query = Query::try_from("page=1&q=rust&page=3").unwrap();
+    /// query.set_pair("page", "2");
+    /// assert_eq!(query, "page=2&q=rust");
+    ///
+    /// query.set_pair("lang", "en");
+    /// assert_eq!(query, "page=2&q=rust&lang=en");
+    /// ```
+    pub fn set_pair(&mut self, key: &str, value: &str) {
+        self.set_pair_with_separator(key, value, QueryPairSeparator::Ampersand)
+    }
+
+    /// Sets the value of the given key in the query using the given pair separator.
+    ///
+    /// This is the same as [`Query::set_pair`] except that the query is split on the characters of
+    /// the given separator. The separators between the remaining pairs are kept, and a new pair is
+    /// appended with `&`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::query::{Query, QueryPairSeparator};
+    ///
+    /// let separator = QueryPairSeparator::AmpersandOrSemicolon;
+    /// let mut query = Query::try_from("page=1;q=rust").unwrap();
+    /// query.set_pair_with_separator("page", "2", separator);
+    /// assert_eq!(query, "page=2;q=rust");
+    ///
+    /// query.set_pair_with_separator("lang", "en", separator);
+    /// assert_eq!(query, "page=2;q=rust&lang=en");
+    /// ```
+    pub fn set_pair_with_separator(
+        &mut self,
+        key: &str,
+        value: &str,
+        separator: QueryPairSeparator,
+    ) {
+        let mut found = false;
+        let mut query = rewrite_segments(&self.query, separator, |segment| {
+            if segment.is_empty() || !pair_has_key(segment, key) {
+                Some(Cow::Borrowed(segment))
+            } else if found {
+                None
+            } else {
+                let mut pair = String::new();
+                encode_pair(key, value, false, &mut pair);
+                found = true;
+                Some(Cow::Owned(pair))
+            }
+        });
+
+        if !found {
+            if !query.is_empty() && !query.ends_with(separator.characters()) {
+                query.push('&');
+            }
+
+            let mut pair = String::new();
+            encode_pair(key, value, false, &mut pair);
+            query.push_str(&pair);
+        }
+
+        self.set_raw_query(query);
+    }
+
+    /// Collects the `key=value` pairs of the query into a map from each key to all of its values.
+    ///
+    /// Both keys and values are percent-decoded. The values of each key are kept in the order they
+    /// appear in the query, and a pair without a `=` has an empty value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("tag=a&page=1&tag=b").unwrap();
+    /// let map = query.to_multimap();
+    /// assert_eq!(map["tag"], vec!["a", "b"]);
+    /// assert_eq!(map["page"], vec!["1"]);
+    /// ```
+    pub fn to_multimap(&self) -> HashMap<Cow<'_, str>, Vec<Cow<'_, str>>> {
+        self.to_multimap_with_separator(QueryPairSeparator::Ampersand)
+    }
+
+    /// Collects the `key=value` pairs of the query into a map from each key to all of its values
+    /// using the given pair separator.
+    ///
+    /// This is the same as [`Query::to_multimap`] except that the query is split on the characters
+    /// of the given separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::query::{Query, QueryPairSeparator};
+    ///
+    /// let query = Query::try_from("tag=a;page=1&tag=b").unwrap();
+    /// let map = query.to_multimap_with_separator(QueryPairSeparator::AmpersandOrSemicolon);
+    /// assert_eq!(map["tag"], vec!["a", "b"]);
+    /// assert_eq!(map["page"], vec!["1"]);
+    /// ```
+    pub fn to_multimap_with_separator(
+        &self,
+        separator: QueryPairSeparator,
+    ) -> HashMap<Cow<'_, str>, Vec<Cow<'_, str>>> {
+        let mut map: HashMap<_, Vec<_>> = HashMap::new();
+
+        for (key, value) in self.pairs_with_separator(separator) {
+            map.entry(key)
+                .or_default()
+                .push(value.unwrap_or(Cow::Borrowed("")));
+        }
+
+        map
+    }
+
+    /// Parses the query from the given byte string, rejecting it if it exceeds the given limits.
+    ///
+    /// The limits are checked before the query is validated, so oversized input is rejected
+    /// without being scanned in full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::query::{Query, QueryError, QueryLimits};
+    ///
+    /// let limits = QueryLimits {
+    ///     max_length: Some(16),
+    ///     max_pairs: Some(2),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(Query::try_from_with_limits(b"a=1&b=2", &limits).is_ok());
+    /// assert_eq!(
+    ///     Query::try_from_with_limits(b"a=1&b=2&c=3", &limits),
+    ///     Err(QueryError::ExceededMaximumPairCount)
+    /// );
+    /// assert_eq!(
+    ///     Query::try_from_with_limits(b"key=a-long-value", &limits).unwrap(),
+    ///     "key=a-long-value"
+    /// );
+    /// assert_eq!(
+    ///     Query::try_from_with_limits(b"key=a-longer-value", &limits),
+    ///     Err(QueryError::ExceededMaximumLength)
+    /// );
+    /// ```
+    pub fn try_from_with_limits<'query>(
+        value: &'query [u8],
+        limits: &QueryLimits,
+    ) -> Result<Query<'query>, QueryError> {
+        if let Some(max_length) = limits.max_length {
+            if value.len() > max_length {
+                return Err(QueryError::ExceededMaximumLength);
+            }
+        }
+
+        if let Some(max_pairs) = limits.max_pairs {
+            let separators = limits.separator.characters();
+            let pair_count = value
+                .split(|&byte| separators.contains(&char::from(byte)))
+                .filter(|pair| !pair.is_empty())
+                .take(max_pairs + 1)
+                .count();
+
+            if pair_count > max_pairs {
+                return Err(QueryError::ExceededMaximumPairCount);
+            }
+        }
+
+        Query::try_from(value)
+    }
+
+    /// Replaces the query with the given string, which must be a valid query.
+    fn set_raw_query(&mut self, query: String) {
+        let normalized = parse_query(query.as_bytes())
+            .map(|(query, _)| query.normalized)
+            .unwrap_or(false);
+        self.normalized = normalized;
+        self.query = Cow::from(query);
+    }
+}
+
+impl AsRef<[u8]> for Query<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.query.as_bytes()
+    }
+}
+
+impl AsRef<str> for Query<'_> {
+    fn as_ref(&self) -> &str {
+        &self.query
+    }
+}
+
+impl Deref for Query<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.query
+    }
+}
+
+impl Display for Query<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(&self.query)
+    }
+}
+
+impl Eq for Query<'_> {}
+
+impl<'query> From<Query<'query>> for String {
+    fn from(value: Query<'query>) -> Self {
+        value.to_string()
+    }
+}
+
+impl FromStr for Query<'static> {
+    type Err = QueryError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Query::try_from(value).map(Query::into_owned)
+    }
+}
+
+impl Hash for Query<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        percent_encoded_hash(self.query.as_bytes(), state, true);
+    }
+}
+
+impl Ord for Query<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(self.query.as_bytes(), other.query.as_bytes(), true)
+    }
+}
+
+impl PartialEq for Query<'_> {
+    fn eq(&self, other: &Query) -> bool {
+        *self == *other.as_bytes()
+    }
+}
+
+impl PartialEq<[u8]> for Query<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        percent_encoded_equality(self.query.as_bytes(), other, true)
+    }
+}
+
+impl<'query> PartialEq<Query<'query>> for [u8] {
+    fn eq(&self, other: &Query<'query>) -> bool {
+        other == self
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for Query<'_> {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self == *other
+    }
+}
+
+impl<'query> PartialEq<Query<'query>> for &[u8] {
+    fn eq(&self, other: &Query<'query>) -> bool {
+        other == *self
+    }
+}
+
+impl PartialEq<str> for Query<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'query> PartialEq<Query<'query>> for str {
+    fn eq(&self, other: &Query<'query>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Query<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'query> PartialEq<Query<'query>> for &str {
+    fn eq(&self, other: &Query<'query>) -> bool {
+        other == self.as_bytes()
+    }
+}
+
+impl PartialOrd for Query<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'query> TryFrom<&'query [u8]> for Query<'query> {
+    type Error = QueryError;
+
+    fn try_from(value: &'query [u8]) -> Result<Self, Self::Error> {
+        let (query, rest) = parse_query(value).map_err(|(error, _)| error)?;
+
+        if rest.is_empty() {
+            Ok(query)
+        } else {
+            Err(QueryError::InvalidCharacter)
+        }
+    }
+}
+
+impl<'query> TryFrom<&'query str> for Query<'query> {
+    type Error = QueryError;
+
+    fn try_from(value: &'query str) -> Result<Self, Self::Error> {
+        Query::try_from(value.as_bytes())
+    }
+}
+
+/// An error representing an invalid query.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum QueryError {
+    /// The query was longer than the maximum length allowed by the [`QueryLimits`].
+    ExceededMaximumLength,
+
+    /// The query had more pairs than the maximum number allowed by the [`QueryLimits`].
+    ExceededMaximumPairCount,
+
+    /// The fragment contained an invalid character.
+    InvalidCharacter,
+
+    /// The fragment contained an invalid percent encoding (e.g. `"%ZZ"`).
+    InvalidPercentEncoding,
+}
+
+impl Display for QueryError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::QueryError::*;
+
+        match self {
+            ExceededMaximumLength => write!(formatter, "exceeded maximum query length"),
+            ExceededMaximumPairCount => write!(formatter, "exceeded maximum query pair count"),
+            InvalidCharacter => write!(formatter, "invalid query character"),
+            InvalidPercentEncoding => write!(formatter, "invalid query percent encoding"),
+        }
+    }
+}
+
+impl Error for QueryError {}
+
+impl From<Infallible> for QueryError {
+    fn from(_: Infallible) -> Self {
+        QueryError::InvalidCharacter
+    }
+}
+
+/// Limits enforced when parsing a query with [`Query::try_from_with_limits`].
+///
+/// The default limits do not restrict the query at all.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct QueryLimits {
+    /// The maximum length of the query in bytes.
+    pub max_length: Option<usize>,
+
+    /// The maximum number of pairs in the query, not counting empty pairs.
+    pub max_pairs: Option<usize>,
+
+    /// The separator used to split the query into pairs when counting them.
+    pub separator: QueryPairSeparator,
+}
+
+/// A policy for the additional normalizations done by [`Query::normalize_with_policy`].
+///
+/// The default policy does no additional normalizations.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct QueryNormalizationPolicy {
+    /// Whether only the first pair of each key should be kept.
+    pub collapse_duplicate_keys: bool,
+
+    /// Whether pairs with an empty key and an empty value (e.g. `"="`) should be removed.
+    pub drop_empty_pairs: bool,
+
+    /// Whether the pairs should be sorted by key and then by value.
+    ///
+    /// The sort is stable, so pairs that are equal after percent-decoding keep their order.
+    pub sort_pairs: bool,
+
+    /// The separator used to split the query into pairs.
+    pub separator: QueryPairSeparator,
+}
+
+/// The characters that separate the `key=value` pairs of a query.
+///
+/// Pairs built by this crate (e.g. with [`Query::from_pairs`]) are always separated by `&`, and any
+/// `;` in their keys and values is percent-encoded, so they can be split with either separator.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum QueryPairSeparator {
+    /// Pairs are separated by `&` only, which is the default.
+    #[default]
+    Ampersand,
+
+    /// Pairs are separated by either `&` or `;`, as allowed by older specifications.
+    AmpersandOrSemicolon,
+}
+
+impl QueryPairSeparator {
+    /// Returns the characters that separate pairs.
+    fn characters(self) -> &'static [char] {
+        use self::QueryPairSeparator::*;
+
+        match self {
+            Ampersand => &['&'],
+            AmpersandOrSemicolon => &['&', ';'],
+        }
+    }
+}
+
+/// An iterator over the `key=value` pairs of a query.
+///
+/// This is created by [`Query::pairs`], [`Query::pairs_with_separator`], or [`Query::form_pairs`].
+#[derive(Clone, Debug)]
+pub struct QueryPairs<'query> {
+    /// Whether `+` should be decoded as a space.
+    form: bool,
+
+    /// The iterator over the raw separated pairs.
+    pairs: Split<'query, &'static [char]>,
+}
+
+impl<'query> Iterator for QueryPairs<'query> {
+    type Item = (Cow<'query, str>, Option<Cow<'query, str>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let pair = self.pairs.next()?;
+
+            if pair.is_empty() {
+                continue;
+            }
+
+            return Some(decode_pair(pair, self.form));
+        }
+    }
+}
+
+/// Splits the raw `key=value` pair into its key and value and decodes them, optionally decoding `+`
+/// as a space.
+fn decode_pair(pair: &str, form: bool) -> (Cow<'_, str>, Option<Cow<'_, str>>) {
+    let (key, value) = split_pair(pair);
+    (
+        decode_pair_component(key, form),
+        value.map(|value| decode_pair_component(value, form)),
+    )
+}
+
+/// Decodes the key or value of a query pair, optionally decoding `+` as a space.
+fn decode_pair_component(value: &str, form: bool) -> Cow<'_, str> {
+    if form && value.contains('+') {
+        Cow::from(percent_decode(&value.replace('+', " ")).into_owned())
+    } else {
+        percent_decode(value)
+    }
+}
+
+/// Encodes the given pair and appends it to the query, optionally encoding spaces as `+`.
+fn encode_pair(key: &str, value: &str, form: bool, query: &mut String) {
+    fn encode_component(value: &str, form: bool, query: &mut String) {
+        if form {
+            for (index, part) in value.split(' ').enumerate() {
+                if index > 0 {
+                    query.push('+');
+                }
+
+                percent_encode(part.as_bytes(), &QUERY_PAIR_CHAR_MAP, query);
+            }
+        } else {
+            percent_encode(value.as_bytes(), &QUERY_PAIR_CHAR_MAP, query);
+        }
+    }
+
+    if !query.is_empty() && !query.ends_with('&') {
+        query.push('&');
+    }
+
+    encode_component(key, form, query);
+    query.push('=');
+    encode_component(value, form, query);
+}
+
+/// Encodes the given pairs into a query, optionally encoding spaces as `+`.
+fn encode_pairs<I, K, V>(pairs: I, form: bool) -> Query<'static>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut query = String::new();
+
+    for (key, value) in pairs {
+        encode_pair(key.as_ref(), value.as_ref(), form, &mut query);
+    }
+
+    Query {
+        normalized: true,
+        query: Cow::from(query),
+    }
+}
+
+/// Returns whether the raw `key=value` pair has the given percent-decoded key.
+fn pair_has_key(pair: &str, key: &str) -> bool {
+    decode_pair_component(split_pair(pair).0, false) == key
+}
+
+/// Parses the query from the given byte string.
+///
+/// On failure, the byte index of the error within the given byte string is returned alongside it.
+pub(crate) fn parse_query(value: &[u8]) -> Result<(Query<'_>, &[u8]), (QueryError, usize)> {
+    let (end_index, normalized) = scan_query(value)?;
+    let (value, rest) = value.split_at(end_index);
+
+    // Unsafe: The scan above makes sure the byte string is valid ASCII-US.
+    let query = Query {
+        normalized,
+        query: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
+    };
+    Ok((query, rest))
+}
+
+/// Validates the query at the start of the given byte string, which ends at the first `'#'`, and
+/// returns the index it ends at and whether it is normalized.
+const fn scan_query(value: &[u8]) -> Result<(usize, bool), (QueryError, usize)> {
+    let mut end_index = 0;
+    let mut normalized = true;
+
+    while end_index < value.len() {
+        let byte = value[end_index];
+
+        match QUERY_CHAR_MAP[byte as usize] {
+            0 if byte == b'#' => break,
+            0 => return Err((QueryError::InvalidCharacter, end_index)),
+            b'%' => match get_percent_encoded_value_at(value, end_index) {
+                Ok((hex_value, uppercase)) => {
+                    if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
+                        normalized = false;
+                    }
+
+                    end_index += 3;
+                }
+                Err(_) => return Err((QueryError::InvalidPercentEncoding, end_index)),
+            },
+            _ => end_index += 1,
+        }
+    }
+
+    Ok((end_index, normalized))
+}
+
+/// Rebuilds the query from its segments split on the given separator, replacing each segment with
+/// the result of the given function, or removing it together with its separator if [`None`] is
+/// returned.
+///
+/// Segments that are kept are copied byte-for-byte, including empty segments and the separator in
+/// front of them.
+fn rewrite_segments<'query, TFunction>(
+    query: &'query str,
+    separator: QueryPairSeparator,
+    mut rewrite: TFunction,
+) -> String
+where
+    TFunction: FnMut(&'query str) -> Option<Cow<'query, str>>,
+{
+    let mut rewritten = String::with_capacity(query.len());
+    let mut first = true;
+    let mut index: usize = 0;
+
+    for segment in query.split(separator.characters()) {
+        // The separator in front of the segment, which is empty for the first segment.
+        let preceding_separator = &query[index.saturating_sub(1)..index];
+        index += segment.len() + 1;
+
+        if let Some(segment) = rewrite(segment) {
+            if !first {
+                rewritten.push_str(preceding_separator);
+            }
+
+            rewritten.push_str(&segment);
+            first = false;
+        }
+    }
+
+    rewritten
+}
+
+/// Splits the raw `key=value` pair on the first `=` without decoding it.
+fn split_pair(pair: &str) -> (&str, Option<&str>) {
+    match pair.find('=') {
+        Some(index) => (&pair[..index], Some(&pair[index + 1..])),
+        None => (pair, None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_query_encode() {
+        fn test_case(value: &str, expected: &str) {
+            let query = Query::encode(value);
+            assert_eq!(query, expected);
+            assert_eq!(query.as_decoded(), value);
+            assert!(query.is_normalized());
+            assert_eq!(Query::try_from(query.as_str()).unwrap(), query);
+        }
+
+        test_case("", "");
+        test_case("query", "query");
+        test_case("a=1&b=2", "a=1&b=2");
+        test_case(" #%", "%20%23%25");
+        test_case("[\u{fc}]", "%5B%C3%BC%5D");
+    }
+
+    #[test]
+    fn test_query_from_static() {
+        fn test_case(value: &'static str) {
+            let query = Query::from_static(value);
+            let expected = Query::try_from(value).unwrap();
+            assert_eq!(query, expected);
+            assert_eq!(query.is_normalized(), expected.is_normalized(), "{}", value);
+        }
+
+        test_case("");
+        test_case("a=b&c=d");
+        test_case("a=%2F?");
+        test_case("%c3%a9");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid query character")]
+    fn test_query_from_static_invalid() {
+        Query::from_static("a#b");
+    }
+
+    #[test]
+    fn test_query_eq_unordered() {
+        fn test_case(left: &str, right: &str, expected: bool) {
+            let left = Query::try_from(left).unwrap();
+            let right = Query::try_from(right).unwrap();
+            assert_eq!(left.eq_unordered(&right), expected);
+            assert_eq!(right.eq_unordered(&left), expected);
+        }
+
+        test_case("", "", true);
+        test_case("", "&&", true);
+        test_case("a=1&b=2", "b=2&a=1", true);
+        test_case("a=1&a=2", "a=2&a=1", true);
+        test_case("a", "a=", false);
+        test_case("a=1&a=1&b=2", "a=1&b=2&b=2", false);
+        test_case("a=%20", "a=%20&c", false);
+    }
+
+    #[test]
+    fn test_query_form_pairs() {
+        let query = Query::try_from("a+b=c+%2B+d&e=%20+").unwrap();
+        let pairs: Vec<_> = query.form_pairs().collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Cow::from("a b"), Some(Cow::from("c + d"))),
+                (Cow::from("e"), Some(Cow::from("  "))),
+            ]
+        );
+
+        let pairs = vec![("a b", "1+1"), (" ", "x&y")];
+        let query = Query::from_form_pairs(pairs.clone());
+        assert_eq!(query, "a+b=1%2B1&+=x%26y");
+        assert!(query.is_normalized());
+
+        let decoded: Vec<_> = query.form_pairs().collect();
+        let expected: Vec<_> = pairs
+            .into_iter()
+            .map(|(key, value)| (Cow::from(key), Some(Cow::from(value))))
+            .collect();
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn test_query_from_lossy() {
+        fn test_case(value: &[u8], expected: &str, normalized: bool) {
+            let query = Query::from_lossy(value);
+            assert_eq!(query.as_str(), expected);
+            assert_eq!(query.is_normalized(), normalized);
+            assert_eq!(Query::try_from(query.as_str()).unwrap(), query);
+        }
+
+        test_case(b"", "", true);
+        test_case(b"a=1&b=2", "a=1&b=2", true);
+        test_case(b"a b", "a%20b", true);
+        test_case(b"%", "%25", true);
+        test_case(b"%f", "%25f", true);
+        test_case(b"%zz%41", "%25zz%41", false);
+        test_case(b"#\"<>", "%23%22%3C%3E", true);
+        test_case(b"\xff\xfe%ff", "%FF%FE%ff", false);
+
+        assert!(matches!(
+            Query::from_lossy(b"query").query,
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_query_from_pairs() {
+        let pairs: Vec<(&str, &str)> = vec![];
+        assert_eq!(Query::from_pairs(pairs), "");
+        assert_eq!(Query::from_pairs(vec![("a", "")]), "a=");
+        assert_eq!(Query::from_pairs(vec![("a", "1"), ("b", "2")]), "a=1&b=2");
+        assert_eq!(Query::from_pairs(vec![("%", "#")]), "%25=%23");
+        assert_eq!(Query::from_pairs(vec![("?/:@", "+")]), "?/:@=%2B");
+
+        let pairs = vec![("a=b", "c&d"), ("e f", "\u{fc}")];
+        let query = Query::from_pairs(pairs.clone());
+        assert!(query.is_normalized());
+        assert!(Query::try_from(query.as_str()).unwrap().is_normalized());
+
+        let decoded: Vec<_> = query.pairs().collect();
+        let expected: Vec<_> = pairs
+            .into_iter()
+            .map(|(key, value)| (Cow::from(key), Some(Cow::from(value))))
+            .collect();
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn test_query_get() {
+        let query = Query::try_from("a=1&b&a=2&%61=3&c=").unwrap();
+        assert_eq!(query.get("a").unwrap(), "1");
+        assert_eq!(query.get("b").unwrap(), "");
+        assert_eq!(query.get("c").unwrap(), "");
+        assert_eq!(query.get("d"), None);
+        assert_eq!(query.get_all("a").collect::<Vec<_>>(), vec!["1", "2", "3"]);
+        assert_eq!(query.get_all("d").count(), 0);
+    }
+
+    #[test]
+    fn test_query_mutation() {
+        let mut query = Query::try_from("").unwrap();
+        query.append_pair("a", "1");
+        assert_eq!(query, "a=1");
+        query.append_pair("a&b", "=");
+        assert_eq!(query, "a=1&a%26b=%3D");
+
+        let mut query = Query::try_from("a=1&").unwrap();
+        query.append_pair("b", "2");
+        assert_eq!(query, "a=1&b=2");
+
+        let mut query = Query::try_from("%61=1&&b&a=2").unwrap();
+        assert!(!query.is_normalized());
+        assert!(query.remove_key("a"));
+        assert_eq!(query.as_str(), "&b");
+        assert!(query.is_normalized());
+
+        let mut query = Query::try_from("a=1&&b=2&").unwrap();
+        assert!(query.remove_key("b"));
+        assert_eq!(query.as_str(), "a=1&&");
+        assert!(!query.remove_key(""));
+        assert_eq!(query.as_str(), "a=1&&");
+
+        let mut query = Query::try_from("b&%61=1").unwrap();
+        query.set_pair("a", "x y");
+        assert_eq!(query, "b&a=x%20y");
+        assert!(query.is_normalized());
+
+        let mut query = Query::try_from("a=1&&b=2&a=3").unwrap();
+        query.set_pair("a", "5");
+        assert_eq!(query.as_str(), "a=5&&b=2");
+        query.set_pair("c", "6");
+        assert_eq!(query.as_str(), "a=5&&b=2&c=6");
+
+        let mut query = Query::try_from("").unwrap();
+        query.set_pair("a", "1");
+        assert_eq!(query, "a=1");
+    }
+
+    #[test]
+    fn test_query_normalize() {
+        fn test_case(value: &str, expected: &str) {
+            let mut query = Query::try_from(value).unwrap();
+            query.normalize();
+            assert_eq!(query, expected);
+        }
+
+        test_case("", "");
+        test_case("%ff", "%FF");
+        test_case("%41", "A");
+    }
+
+    #[test]
+    fn test_query_normalize_with_policy() {
+        fn test_case(value: &str, policy: QueryNormalizationPolicy, expected: &str) {
+            let mut query = Query::try_from(value).unwrap();
+            query.normalize_with_policy(policy);
+            assert_eq!(query.as_str(), expected);
+            assert!(query.is_normalized());
+        }
+
+        let all = QueryNormalizationPolicy {
+            collapse_duplicate_keys: true,
+            drop_empty_pairs: true,
+            sort_pairs: true,
+            ..Default::default()
+        };
+        let semicolon = QueryNormalizationPolicy {
+            sort_pairs: true,
+            separator: QueryPairSeparator::AmpersandOrSemicolon,
+            ..Default::default()
+        };
+        let collapse = QueryNormalizationPolicy {
+            collapse_duplicate_keys: true,
+            ..Default::default()
+        };
+        let drop = QueryNormalizationPolicy {
+            drop_empty_pairs: true,
+            ..Default::default()
+        };
+        let sort = QueryNormalizationPolicy {
+            sort_pairs: true,
+            ..Default::default()
+        };
+
+        test_case("b&&%61=%ff", Default::default(), "b&&a=%FF");
+        test_case("a=1&=&&b", drop, "a=1&b");
+        test_case("a=2&b&%61=1", collapse, "a=2&b");
+        test_case("c&b=2&a=%33&b=1&a", sort, "a&a=3&b=1&b=2&c");
+        test_case("b=2&=&a=1&%62=1", all, "a=1&b=2");
+        test_case("b=2;a=1&c", semicolon, "a=1&b=2&c");
+    }
+
+    #[test]
+    fn test_query_pairs() {
+        fn test_case(value: &str, expected: Vec<(&str, Option<&str>)>) {
+            let query = Query::try_from(value).unwrap();
+            let pairs: Vec<_> = query.pairs().collect();
+            let expected: Vec<_> = expected
+                .into_iter()
+                .map(|(key, value)| (Cow::from(key), value.map(Cow::from)))
+                .collect();
+            assert_eq!(pairs, expected);
+        }
+
+        test_case("", vec![]);
+        test_case("&&", vec![]);
+        test_case("a", vec![("a", None)]);
+        test_case("a=", vec![("a", Some(""))]);
+        test_case("=b", vec![("", Some("b"))]);
+        test_case("a=b=c", vec![("a", Some("b=c"))]);
+        test_case("a=1&b=2", vec![("a", Some("1")), ("b", Some("2"))]);
+        test_case("a%26b=%3D", vec![("a&b", Some("="))]);
+        test_case("a+b=c+d", vec![("a+b", Some("c+d"))]);
+    }
+
+    #[test]
+    fn test_query_pairs_with_separator() {
+        use self::QueryPairSeparator::*;
+
+        let query = Query::try_from("a=1;b=2&c;;d").unwrap();
+        let pairs: Vec<_> = query.pairs_with_separator(Ampersand).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Cow::from("a"), Some(Cow::from("1;b=2"))),
+                (Cow::from("c;;d"), None),
+            ]
+        );
+
+        let pairs: Vec<_> = query.pairs_with_separator(AmpersandOrSemicolon).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Cow::from("a"), Some(Cow::from("1"))),
+                (Cow::from("b"), Some(Cow::from("2"))),
+                (Cow::from("c"), None),
+                (Cow::from("d"), None),
+            ]
+        );
+
+        let query = Query::from_pairs(vec![("a;b", "c;d")]);
+        assert_eq!(query, "a%3Bb=c%3Bd");
+        let pairs: Vec<_> = query.pairs_with_separator(AmpersandOrSemicolon).collect();
+        assert_eq!(pairs, vec![(Cow::from("a;b"), Some(Cow::from("c;d")))]);
+    }
+
+    #[test]
+    fn test_query_parse() {
+        use self::QueryError::*;
+
+        assert_eq!(Query::try_from("").unwrap(), "");
+        assert_eq!(Query::try_from("query").unwrap(), "query");
+        assert_eq!(Query::try_from("qUeRy").unwrap(), "qUeRy");
+        assert_eq!(Query::try_from("%ff%ff%ff%41").unwrap(), "%ff%ff%ff%41");
+
+        assert_eq!(Query::try_from(" "), Err(InvalidCharacter));
+        assert_eq!(Query::try_from("#"), Err(InvalidCharacter));
+        assert_eq!(Query::try_from("%"), Err(InvalidPercentEncoding));
+        assert_eq!(Query::try_from("%f"), Err(InvalidPercentEncoding));
+        assert_eq!(Query::try_from("%zz"), Err(InvalidPercentEncoding));
+    }
+
+    #[test]
+    fn test_query_to_multimap() {
+        let query = Query::try_from("").unwrap();
+        assert!(query.to_multimap().is_empty());
+
+        let query = Query::try_from("a=1&b&a=2&%61=%33").unwrap();
+        let map = query.to_multimap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], vec!["1", "2", "3"]);
+        assert_eq!(map["b"], vec![""]);
+    }
+
+    #[test]
+    fn test_query_try_from_with_limits() {
+        use self::QueryError::*;
+
+        let unlimited = QueryLimits::default();
+        assert_eq!(
+            Query::try_from_with_limits(b"a=1&b=2", &unlimited).unwrap(),
+            "a=1&b=2"
+        );
+        assert_eq!(
+            Query::try_from_with_limits(b"a b", &unlimited),
+            Err(InvalidCharacter)
+        );
+
+        let limits = QueryLimits {
+            max_length: Some(3),
+            max_pairs: None,
+            ..Default::default()
+        };
+        assert_eq!(Query::try_from_with_limits(b"abc", &limits).unwrap(), "abc");
+        assert_eq!(
+            Query::try_from_with_limits(b"abcd", &limits),
+            Err(ExceededMaximumLength)
+        );
+
+        let limits = QueryLimits {
+            max_length: None,
+            max_pairs: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            Query::try_from_with_limits(b"&&a&&", &limits).unwrap(),
+            "&&a&&"
+        );
+        assert_eq!(
+            Query::try_from_with_limits(b"a&b", &limits),
+            Err(ExceededMaximumPairCount)
+        );
+
+        let limits = QueryLimits {
+            max_length: None,
+            max_pairs: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(Query::try_from_with_limits(b"", &limits).unwrap(), "");
+        assert_eq!(
+            Query::try_from_with_limits(b"a", &limits),
+            Err(ExceededMaximumPairCount)
+        );
+
+        let limits = QueryLimits {
+            max_length: None,
+            max_pairs: Some(1),
+            separator: QueryPairSeparator::AmpersandOrSemicolon,
+        };
+        assert_eq!(Query::try_from_with_limits(b"a;", &limits).unwrap(), "a;");
+        assert_eq!(
+            Query::try_from_with_limits(b"a;b", &limits),
+            Err(ExceededMaximumPairCount)
+        );
+    }
+
+    #[test]
+    fn test_query_with_separator() {
+        use self::QueryPairSeparator::*;
+
+        let query = Query::try_from("a=1;b=2&a=3;;c").unwrap();
+        assert_eq!(query.get_with_separator("a", Ampersand).unwrap(), "1;b=2");
+        assert_eq!(
+            query.get_with_separator("b", AmpersandOrSemicolon).unwrap(),
+            "2"
+        );
+        assert_eq!(
+            query
+                .get_all_with_separator("a", AmpersandOrSemicolon)
+                .collect::<Vec<_>>(),
+            vec!["1", "3"]
+        );
+        assert_eq!(
+            query.to_multimap_with_separator(AmpersandOrSemicolon).len(),
+            3
+        );
+
+        let mut query = Query::try_from("a=1;b=2&a=3;;c").unwrap();
+        assert!(!query.remove_key_with_separator("b", Ampersand));
+        assert!(query.remove_key_with_separator("b", AmpersandOrSemicolon));
+        assert_eq!(query.as_str(), "a=1&a=3;;c");
+        assert!(query.remove_key_with_separator("a", AmpersandOrSemicolon));
+        assert_eq!(query.as_str(), ";c");
+
+        let mut query = Query::try_from("a=1;b=2;a=3;").unwrap();
+        query.set_pair_with_separator("a", "4", AmpersandOrSemicolon);
+        assert_eq!(query.as_str(), "a=4;b=2;");
+        query.set_pair_with_separator("c", ";", AmpersandOrSemicolon);
+        assert_eq!(query.as_str(), "a=4;b=2;c=%3B");
+        query.set_pair_with_separator("d", "5", AmpersandOrSemicolon);
+        assert_eq!(query.as_str(), "a=4;b=2;c=%3B&d=5");
+    }
+}
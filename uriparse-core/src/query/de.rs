@@ -0,0 +1,294 @@
+//! Deserialization of query `key=value` pairs into user types.
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::de::value::{BorrowedStrDeserializer, MapDeserializer};
+use serde::de::{self, Deserializer, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use super::QueryPairs;
+
+/// An error representing a failure to deserialize the pairs of a query into a type.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct QueryDeserializeError {
+    /// The message describing the failure.
+    message: String,
+}
+
+impl Display for QueryDeserializeError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(&self.message)
+    }
+}
+
+impl Error for QueryDeserializeError {}
+
+impl de::Error for QueryDeserializeError {
+    fn custom<T>(message: T) -> Self
+    where
+        T: Display,
+    {
+        QueryDeserializeError {
+            message: message.to_string(),
+        }
+    }
+}
+
+/// A deserializer over the pairs of a query.
+///
+/// The pairs can be deserialized either as a map (e.g. a struct or a `HashMap`) or as a sequence
+/// of `(key, value)` tuples.
+pub(super) struct PairsDeserializer<'de> {
+    /// The iterator over the pairs of the query.
+    pairs: QueryPairs<'de>,
+}
+
+impl<'de> PairsDeserializer<'de> {
+    pub(super) fn new(pairs: QueryPairs<'de>) -> Self {
+        PairsDeserializer { pairs }
+    }
+
+    fn into_map_deserializer(
+        self,
+    ) -> MapDeserializer<'de, impl Iterator<Item = (Part<'de>, Part<'de>)>, QueryDeserializeError>
+    {
+        MapDeserializer::new(
+            self.pairs
+                .map(|(key, value)| (Part(key), Part(value.unwrap_or(Cow::Borrowed(""))))),
+        )
+    }
+}
+
+impl<'de> Deserializer<'de> for PairsDeserializer<'de> {
+    type Error = QueryDeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let mut map = self.into_map_deserializer();
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let mut seq = self.into_map_deserializer();
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.into_map_deserializer().end()?;
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit_struct newtype_struct tuple tuple_struct struct enum identifier ignored_any
+    }
+}
+
+/// A deserializer for a single percent-decoded key or value of a query pair.
+///
+/// Since query values are untyped, primitives are parsed from their string representation.
+struct Part<'de>(Cow<'de, str>);
+
+impl<'de> Part<'de> {
+    fn parse<T>(&self) -> Result<T, QueryDeserializeError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.0.parse().map_err(de::Error::custom)
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Part<'de> {
+    type Error = QueryDeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Cow::Borrowed(value) => visitor.visit_borrowed_str(value),
+            Cow::Owned(value) => visitor.visit_string(value),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_char => visit_char,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Cow::Borrowed(value) => BorrowedStrDeserializer::<Self::Error>::new(value)
+                .deserialize_enum(name, variants, visitor),
+            Cow::Owned(value) => IntoDeserializer::<Self::Error>::into_deserializer(value)
+                .deserialize_enum(name, variants, visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit_struct seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, QueryDeserializeError> for Part<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+
+    use serde::Deserialize;
+
+    use crate::Query;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Order {
+        Ascending,
+        Descending,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Params<'a> {
+        name: &'a str,
+        decoded: String,
+        count: i32,
+        flag: bool,
+        ratio: f64,
+        order: Order,
+        missing: Option<u8>,
+    }
+
+    #[test]
+    fn test_deserialize_into() {
+        let query = Query::try_from(
+            "name=uri&decoded=a%20b&count=-3&flag=true&ratio=0.5&order=descending&extra",
+        )
+        .unwrap();
+        let params: Params = query.deserialize_into().unwrap();
+        assert_eq!(
+            params,
+            Params {
+                name: "uri",
+                decoded: "a b".to_string(),
+                count: -3,
+                flag: true,
+                ratio: 0.5,
+                order: Order::Descending,
+                missing: None,
+            }
+        );
+
+        let query = Query::try_from("a=1&b=2").unwrap();
+        let map: HashMap<String, u32> = query.deserialize_into().unwrap();
+        assert_eq!(map["a"], 1);
+        assert_eq!(map["b"], 2);
+
+        let query = Query::try_from("a=1&a=2").unwrap();
+        let pairs: Vec<(String, String)> = query.deserialize_into().unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                ("a".to_string(), "1".to_string()),
+                ("a".to_string(), "2".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_into_error() {
+        let query = Query::try_from("count=abc").unwrap();
+        assert!(query.deserialize_into::<HashMap<String, i32>>().is_err());
+
+        let query = Query::try_from("order=sideways").unwrap();
+        assert!(query.deserialize_into::<HashMap<String, Order>>().is_err());
+    }
+}
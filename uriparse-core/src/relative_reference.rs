@@ -1,19 +1,24 @@
 use std::convert::{Infallible, TryFrom};
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::str::FromStr;
 
 use crate::authority::{Authority, AuthorityError, Host, Password, Username};
 use crate::fragment::{Fragment, FragmentError};
 use crate::path::{Path, PathError};
 use crate::query::{Query, QueryError};
 use crate::scheme::Scheme;
-use crate::uri_reference::{URIReference, URIReferenceBuilder, URIReferenceError};
+use crate::uri::URI;
+use crate::uri_reference::{
+    Component, DisplayOptions, ParseError, URIReference, URIReferenceBuilder, URIReferenceError,
+};
 
 /// A relative reference as defined in
 /// [[RFC3986, Section 4.1]](https://tools.ietf.org/html/rfc3986#section-4.1).
 ///
 /// Specifically, a relative reference is a URI reference without a scheme.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct RelativeReference<'uri> {
     /// All relative references are also URI references, so we just maintain a [`URIReference`]
     /// underneath.
@@ -64,6 +69,70 @@ impl<'uri> RelativeReference<'uri> {
         RelativeReferenceBuilder::new()
     }
 
+    /// Returns a value that displays the relative reference with the password of its authority
+    /// replaced by `"****"`.
+    ///
+    /// This is useful for logging relative references without leaking credentials.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let reference = RelativeReference::try_from("//user:secret@example.com/path").unwrap();
+    /// assert_eq!(reference.display_redacted().to_string(), "//user:****@example.com/path");
+    /// assert!(!format!("{:?}", reference).contains("secret"));
+    /// ```
+    pub fn display_redacted(&self) -> impl Display + '_ {
+        self.uri_reference.display_redacted()
+    }
+
+    /// Returns a value that displays the relative reference according to the given options, see
+    /// [`URIReference::display_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{DisplayOptions, RelativeReference};
+    ///
+    /// let reference = RelativeReference::try_from("//user:secret@example.com/a#b").unwrap();
+    /// let options = DisplayOptions {
+    ///     omit_fragment: true,
+    ///     omit_user_info: true,
+    ///     ..DisplayOptions::default()
+    /// };
+    /// assert_eq!(reference.display_with(options).to_string(), "//example.com/a");
+    /// ```
+    pub fn display_with(&self, options: DisplayOptions) -> impl Display + '_ {
+        self.uri_reference.display_with(options)
+    }
+
+    /// Writes the relative reference to the given [`fmt::Write`] sink without allocating an
+    /// intermediate string, see [`URIReference::fmt_to`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let reference = RelativeReference::try_from("/path?query").unwrap();
+    /// let mut output = String::new();
+    /// reference.fmt_to(&mut output).unwrap();
+    /// assert_eq!(output, "/path?query");
+    /// ```
+    pub fn fmt_to<TWriter>(&self, writer: TWriter) -> fmt::Result
+    where
+        TWriter: fmt::Write,
+    {
+        self.uri_reference.fmt_to(writer)
+    }
+
     /// Constructs a new [`RelativeReference`] from the individual parts: authority, path, query,
     /// and fragment.
     ///
@@ -414,6 +483,55 @@ impl<'uri> RelativeReference<'uri> {
         self.uri_reference.is_relative_path_reference()
     }
 
+    /// Returns whether the relative reference refers to the same document as the given base URI,
+    /// i.e. whether it resolves to a URI that is equivalent to the base URI after scheme-based
+    /// normalization once fragments are ignored, see [`URI::is_same_document`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{RelativeReference, URI};
+    ///
+    /// let base_uri = URI::try_from("http://example.com/a/b?q").unwrap();
+    ///
+    /// let reference = RelativeReference::try_from("b?q#section").unwrap();
+    /// assert!(reference.is_same_document(&base_uri));
+    ///
+    /// let reference = RelativeReference::try_from("b#section").unwrap();
+    /// assert!(!reference.is_same_document(&base_uri));
+    /// ```
+    pub fn is_same_document(&self, base_uri: &URI<'_>) -> bool {
+        let base_uri = base_uri.to_borrowed();
+        let reference = self.to_borrowed();
+        base_uri
+            .resolve_relative(&reference)
+            .is_same_document(&base_uri)
+    }
+
+    /// Returns whether the relative reference is a same-document reference.
+    ///
+    /// A relative reference is a same-document reference if it is empty or consists of only a
+    /// fragment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let reference = RelativeReference::try_from("").unwrap();
+    /// assert!(reference.is_same_document_reference());
+    ///
+    /// let reference = RelativeReference::try_from("/#fragment").unwrap();
+    /// assert!(!reference.is_same_document_reference());
+    /// ```
+    pub fn is_same_document_reference(&self) -> bool {
+        self.uri_reference.is_same_document_reference()
+    }
+
     /// Maps the authority using the given map function.
     ///
     /// This function will panic if, as a result of the authority change, the relative reference
@@ -528,6 +646,39 @@ impl<'uri> RelativeReference<'uri> {
         self.uri_reference.normalize();
     }
 
+    /// Parses a relative reference from the given string or byte string, keeping track of where
+    /// parsing failed.
+    ///
+    /// See [`URIReference::parse`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::{Component, RelativeReference};
+    ///
+    /// let error = RelativeReference::parse("//[::1/").unwrap_err();
+    /// assert_eq!(error.component(), Component::Authority);
+    /// assert_eq!(error.position(), 2);
+    /// ```
+    pub fn parse<TValue>(value: &'uri TValue) -> Result<Self, ParseError<RelativeReferenceError>>
+    where
+        TValue: AsRef<[u8]> + ?Sized,
+    {
+        let uri_reference = URIReference::parse(value).map_err(|error| {
+            error.map_error(|error| RelativeReferenceError::try_from(error).unwrap())
+        })?;
+
+        if uri_reference.is_uri() {
+            Err(ParseError::new(
+                Component::Scheme,
+                RelativeReferenceError::NotRelativeReference,
+                0,
+            ))
+        } else {
+            Ok(RelativeReference { uri_reference })
+        }
+    }
+
     /// Returns the path of the relative reference.
     ///
     /// # Examples
@@ -594,6 +745,23 @@ impl<'uri> RelativeReference<'uri> {
         self.uri_reference.query()
     }
 
+    /// Returns the exact length of the relative reference when converted to a string, see
+    /// [`URIReference::serialized_len`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let reference = RelativeReference::try_from("//example.com/a/b?c#d").unwrap();
+    /// assert_eq!(reference.serialized_len(), reference.to_string().len());
+    /// ```
+    pub fn serialized_len(&self) -> usize {
+        self.uri_reference.serialized_len()
+    }
+
     /// Sets the authority of the relative reference.
     ///
     /// An error will be returned if the conversion to an [`Authority`] fails.
@@ -717,12 +885,30 @@ impl<'uri> RelativeReference<'uri> {
     /// reference.
     ///
     /// This function will perform a memory allocation.
-    pub fn to_borrowed(&self) -> RelativeReference {
+    pub fn to_borrowed(&self) -> RelativeReference<'_> {
         RelativeReference {
             uri_reference: self.uri_reference.to_borrowed(),
         }
     }
 
+    /// Returns a builder with the same values as this relative reference, borrowing from it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Fragment, RelativeReference};
+    ///
+    /// let reference = RelativeReference::try_from("/path#fragment").unwrap();
+    /// let mut builder = reference.to_builder();
+    /// builder.fragment(None::<Fragment>);
+    /// assert_eq!(builder.build().unwrap().to_string(), "/path");
+    /// ```
+    pub fn to_builder(&self) -> RelativeReferenceBuilder<'_> {
+        self.to_borrowed().into_builder()
+    }
+
     /// Returns the username, if present, of the relative reference.
     ///
     /// # Examples
@@ -738,6 +924,28 @@ impl<'uri> RelativeReference<'uri> {
     pub fn username(&self) -> Option<&Username<'uri>> {
         self.uri_reference.username()
     }
+
+    /// Writes the relative reference to the given [`io::Write`] sink without allocating an
+    /// intermediate string, see [`URIReference::write_to`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let reference = RelativeReference::try_from("/path?query").unwrap();
+    /// let mut output = Vec::new();
+    /// reference.write_to(&mut output).unwrap();
+    /// assert_eq!(output, "/path?query".as_bytes());
+    /// ```
+    pub fn write_to<TWriter>(&self, writer: TWriter) -> io::Result<()>
+    where
+        TWriter: io::Write,
+    {
+        self.uri_reference.write_to(writer)
+    }
 }
 
 impl Display for RelativeReference<'_> {
@@ -748,7 +956,7 @@ impl Display for RelativeReference<'_> {
 
 impl<'uri> From<RelativeReference<'uri>> for String {
     fn from(value: RelativeReference<'uri>) -> Self {
-        value.to_string()
+        String::from(value.uri_reference)
     }
 }
 
@@ -758,18 +966,19 @@ impl<'uri> From<RelativeReference<'uri>> for URIReference<'uri> {
     }
 }
 
+impl FromStr for RelativeReference<'static> {
+    type Err = RelativeReferenceError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        RelativeReference::try_from(value).map(RelativeReference::into_owned)
+    }
+}
+
 impl<'uri> TryFrom<&'uri [u8]> for RelativeReference<'uri> {
     type Error = RelativeReferenceError;
 
     fn try_from(value: &'uri [u8]) -> Result<Self, Self::Error> {
-        let uri_reference = URIReference::try_from(value)
-            .map_err(|error| RelativeReferenceError::try_from(error).unwrap())?;
-
-        if uri_reference.is_uri() {
-            Err(RelativeReferenceError::NotRelativeReference)
-        } else {
-            Ok(RelativeReference { uri_reference })
-        }
+        RelativeReference::parse(value).map_err(ParseError::into_error)
     }
 }
 
@@ -781,6 +990,14 @@ impl<'uri> TryFrom<&'uri str> for RelativeReference<'uri> {
     }
 }
 
+impl TryFrom<String> for RelativeReference<'static> {
+    type Error = RelativeReferenceError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        RelativeReference::try_from(value.as_str()).map(RelativeReference::into_owned)
+    }
+}
+
 impl<'uri> TryFrom<URIReference<'uri>> for RelativeReference<'uri> {
     type Error = RelativeReferenceError;
 
@@ -799,6 +1016,27 @@ impl<'uri> TryFrom<URIReference<'uri>> for RelativeReference<'uri> {
 ///
 /// You must use the [`RelativeReferenceBuilder::path`] function before building as relative
 /// references always have a path. Everything else is optional.
+///
+/// Components can also be set from unencoded text, which is percent-encoded as needed.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use uriparse::{Authority, RelativeReferenceBuilder};
+///
+/// let mut builder = RelativeReferenceBuilder::new();
+/// builder.path_raw("../style.css").unwrap().query_raw("v=2");
+/// assert_eq!(builder.build().unwrap().to_string(), "../style.css?v=2");
+///
+/// let mut builder = RelativeReferenceBuilder::new();
+/// builder
+///     .authority(Some(Authority::try_from("cdn.example.com").unwrap()))
+///     .path_raw("my fonts/a.woff")
+///     .unwrap();
+/// assert_eq!(builder.build().unwrap().to_string(), "//cdn.example.com/my%20fonts/a.woff");
+/// ```
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct RelativeReferenceBuilder<'uri> {
     /// All relative references are also URI references, so we just maintain a
@@ -807,6 +1045,25 @@ pub struct RelativeReferenceBuilder<'uri> {
 }
 
 impl<'uri> RelativeReferenceBuilder<'uri> {
+    /// Appends the given `key=value` pair to the query part of the relative reference, see
+    /// [`Query::append_pair`].
+    ///
+    /// The key and value are percent-encoded, and a query is added if there is none yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::RelativeReferenceBuilder;
+    ///
+    /// let mut builder = RelativeReferenceBuilder::new();
+    /// builder.try_path("search").unwrap().append_query_pair("q", "a b");
+    /// assert_eq!(builder.build().unwrap().to_string(), "search?q=a%20b");
+    /// ```
+    pub fn append_query_pair(&mut self, key: &str, value: &str) -> &mut Self {
+        self.uri_reference_builder.append_query_pair(key, value);
+        self
+    }
+
     /// Sets the authority part of the relative reference.
     ///
     /// It is optional to specify a authority.
@@ -869,6 +1126,28 @@ impl<'uri> RelativeReferenceBuilder<'uri> {
         })
     }
 
+    /// Appends the given `key=value` pairs to the query part of the relative reference, see
+    /// [`RelativeReferenceBuilder::append_query_pair`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::RelativeReferenceBuilder;
+    ///
+    /// let mut builder = RelativeReferenceBuilder::new();
+    /// builder.try_path("items").unwrap().extend_query_pairs(vec![("id", "1"), ("id", "2")]);
+    /// assert_eq!(builder.build().unwrap().to_string(), "items?id=1&id=2");
+    /// ```
+    pub fn extend_query_pairs<TPairs, TKey, TValue>(&mut self, pairs: TPairs) -> &mut Self
+    where
+        TPairs: IntoIterator<Item = (TKey, TValue)>,
+        TKey: AsRef<str>,
+        TValue: AsRef<str>,
+    {
+        self.uri_reference_builder.extend_query_pairs(pairs);
+        self
+    }
+
     /// Sets the fragment part of the relative reference.
     ///
     /// It is optional to specify a fragment.
@@ -892,6 +1171,15 @@ impl<'uri> RelativeReferenceBuilder<'uri> {
         self
     }
 
+    /// Sets the fragment part of the relative reference from arbitrary unencoded text, which is
+    /// percent-encoded as done by [`Fragment::encode`].
+    ///
+    /// See [`RelativeReferenceBuilder::path_raw`] for an example.
+    pub fn fragment_raw(&mut self, fragment: &str) -> &mut Self {
+        self.uri_reference_builder.fragment_raw(fragment);
+        self
+    }
+
     /// Constructs a new builder with nothing set.
     pub fn new() -> Self {
         RelativeReferenceBuilder::default()
@@ -920,6 +1208,25 @@ impl<'uri> RelativeReferenceBuilder<'uri> {
         self
     }
 
+    /// Sets the path part of the relative reference from arbitrary unencoded text, which is
+    /// percent-encoded as done by [`Path::encode`].
+    ///
+    /// An error is returned if the path would have too many segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::{RelativeReferenceBuilder};
+    ///
+    /// let mut builder = RelativeReferenceBuilder::new();
+    /// builder.path_raw("my files").unwrap().fragment_raw("top #1");
+    /// assert_eq!(builder.build().unwrap().to_string(), "my%20files#top%20%231");
+    /// ```
+    pub fn path_raw(&mut self, path: &str) -> Result<&mut Self, PathError> {
+        self.uri_reference_builder.path_raw(path)?;
+        Ok(self)
+    }
+
     /// Sets the query part of the relative reference.
     ///
     /// It is optional to specify a query.
@@ -943,6 +1250,15 @@ impl<'uri> RelativeReferenceBuilder<'uri> {
         self
     }
 
+    /// Sets the query part of the relative reference from arbitrary unencoded text, which is
+    /// percent-encoded as done by [`Query::encode`].
+    ///
+    /// See [`RelativeReferenceBuilder::path_raw`] for an example.
+    pub fn query_raw(&mut self, query: &str) -> &mut Self {
+        self.uri_reference_builder.query_raw(query);
+        self
+    }
+
     /// Sets the authority part of the relative reference.
     ///
     /// If the given authority is not a valid authority (i.e. the conversion fails), an error is
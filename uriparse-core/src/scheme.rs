@@ -9,12 +9,13 @@
 use fnv::FnvBuildHasher;
 use lazy_static::lazy_static;
 use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::convert::{Infallible, TryFrom};
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
 use std::hash::{Hash, Hasher};
-use std::str;
+use std::str::{self, FromStr};
 
 use crate::utility::normalize_string;
 
@@ -47,6 +48,35 @@ const SCHEME_CHAR_MAP: [u8; 256] = [
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
 ];
 
+/// Pairs of schemes and their secure counterparts used by [`Scheme::to_secure`] and
+/// [`Scheme::to_insecure`].
+const SECURE_SCHEME_PAIRS: [(Scheme<'static>, Scheme<'static>); 18] = [
+    (Scheme::AAA, Scheme::AAAS),
+    (Scheme::CoAP, Scheme::CoAPS),
+    (Scheme::CoAPTCP, Scheme::CoAPSTCP),
+    (Scheme::CoAPWS, Scheme::CoAPSWS),
+    (
+        Scheme::FTP,
+        Scheme::Unregistered(UnregisteredScheme {
+            normalized: true,
+            scheme: Cow::Borrowed("ftps"),
+        }),
+    ),
+    (Scheme::HTTP, Scheme::HTTPS),
+    (Scheme::IPP, Scheme::IPPS),
+    (Scheme::IRC, Scheme::IRCS),
+    (Scheme::LDAP, Scheme::LDAPS),
+    (Scheme::MSRP, Scheme::MSRPS),
+    (Scheme::News, Scheme::SNews),
+    (Scheme::Redis, Scheme::RedisS),
+    (Scheme::RTSP, Scheme::RTSPS),
+    (Scheme::SIP, Scheme::SIPS),
+    (Scheme::SOAPBEEP, Scheme::SOAPBEEPS),
+    (Scheme::STUN, Scheme::STUNS),
+    (Scheme::TURN, Scheme::TURNS),
+    (Scheme::WS, Scheme::WSS),
+];
+
 macro_rules! schemes {
     (
         $(
@@ -70,6 +100,13 @@ macro_rules! schemes {
             };
         }
 
+        /// All registered schemes in the order they are listed in.
+        static REGISTERED_SCHEMES: [Scheme<'static>; NUMBER_OF_SCHEMES] = [
+        $(
+            Scheme::$variant,
+        )+
+        ];
+
         /// The scheme component as defined in
         /// [[RFC3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5)]. The schemes
         /// listed here come from
@@ -79,6 +116,24 @@ macro_rules! schemes {
         ///
         /// An unregistered scheme is case-insensitive. Furthermore, percent-encoding is not allowed
         /// in schemes.
+        ///
+        /// Registered schemes are unit variants, so they can be used in `const` and `static`
+        /// contexts without any parsing.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::convert::TryFrom;
+        ///
+        /// use uriparse::Scheme;
+        ///
+        /// const DEFAULT_SCHEME: Scheme<'static> = Scheme::HTTPS;
+        /// static ALLOWED_SCHEMES: &[Scheme<'static>] = &[Scheme::File, Scheme::HTTPS];
+        ///
+        /// let scheme = Scheme::try_from("HTTPS").unwrap();
+        /// assert_eq!(scheme, DEFAULT_SCHEME);
+        /// assert!(ALLOWED_SCHEMES.contains(&scheme));
+        /// ```
         #[derive(Clone, Debug, Eq, Hash, PartialEq)]
         #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         #[non_exhaustive]
@@ -91,7 +146,7 @@ macro_rules! schemes {
 
         impl<'scheme> Scheme<'scheme> {
             /// Returns a new scheme which is identical but has a lifetime tied to this scheme.
-            pub fn as_borrowed(&self) -> Scheme {
+            pub fn as_borrowed(&self) -> Scheme<'_> {
                 use self::Scheme::*;
 
                 match self {
@@ -108,6 +163,11 @@ macro_rules! schemes {
             /// the string representation will be exactly that of the original string including
             /// case-sensitivity.
             ///
+            /// The original spelling of a registered scheme is not retained by the scheme itself,
+            /// since registered schemes are unit variants (which is what allows them to be used in
+            /// `const` contexts). A parsed URI reference keeps it instead, see
+            /// [`URIReference::original_scheme_str`](crate::URIReference::original_scheme_str).
+            ///
             /// # Examples
             ///
             /// ```
@@ -131,6 +191,56 @@ macro_rules! schemes {
                 }
             }
 
+            /// Constructs a scheme from the given string in a `const` context.
+            ///
+            /// Unlike schemes that are parsed at runtime, unregistered schemes can be constructed
+            /// this way as well.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the string is not a valid scheme. In a `const` or `static` item, this
+            /// results in a compilation error instead.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use uriparse::Scheme;
+            ///
+            /// const COAP_TCP: Scheme<'static> = Scheme::from_static("COAP+tcp");
+            /// const CUSTOM: Scheme<'static> = Scheme::from_static("x-custom");
+            /// assert_eq!(COAP_TCP, Scheme::CoAPTCP);
+            /// assert_eq!(CUSTOM.as_str(), "x-custom");
+            /// ```
+            ///
+            /// An invalid scheme in a `const` item fails to compile:
+            ///
+            /// ```compile_fail
+            /// use uriparse::Scheme;
+            ///
+            /// const INVALID: Scheme<'static> = Scheme::from_static("1http");
+            /// ```
+            pub const fn from_static(value: &'static str) -> Scheme<'static> {
+                let normalized = match scan_scheme(value.as_bytes()) {
+                    Ok((end_index, normalized)) if end_index == value.len() => normalized,
+                    Err(SchemeError::Empty) => panic!("scheme is empty"),
+                    Err(SchemeError::StartsWithNonAlphabetic) => {
+                        panic!("scheme starts with non-alphabetic character")
+                    }
+                    _ => panic!("invalid scheme character"),
+                };
+
+                $(
+                    if bytes_eq_ignore_ascii_case(value.as_bytes(), $name.as_bytes()) {
+                        return Scheme::$variant;
+                    }
+                )+
+
+                Scheme::Unregistered(UnregisteredScheme {
+                    normalized,
+                    scheme: Cow::Borrowed(value),
+                })
+            }
+
             /// Converts the [`Scheme`] into an owned copy.
             ///
             /// If you construct the scheme from a source with a non-static lifetime, you may run
@@ -173,9 +283,9 @@ macro_rules! schemes {
         }
 
         /// Parses the scheme from the given byte string.
-        pub(crate) fn parse_scheme(value: &[u8]) -> Result<(Scheme, &[u8]), SchemeError> {
-            fn unregistered_scheme(value: &[u8], normalized: bool) -> Scheme {
-                // Unsafe: The loop below makes sure the byte string is valid ASCII-US.
+        pub(crate) fn parse_scheme(value: &[u8]) -> Result<(Scheme<'_>, &[u8]), SchemeError> {
+            fn unregistered_scheme(value: &[u8], normalized: bool) -> Scheme<'_> {
+                // Unsafe: The scan below makes sure the byte string is valid ASCII-US.
                 let scheme = unsafe { str::from_utf8_unchecked(value) };
                 Scheme::Unregistered(UnregisteredScheme{
                     normalized,
@@ -183,32 +293,7 @@ macro_rules! schemes {
                 })
             }
 
-            if !value.iter().next().ok_or(SchemeError::Empty)?.is_ascii_alphabetic() {
-                return Err(SchemeError::StartsWithNonAlphabetic);
-            }
-
-            let mut end_index = 0;
-            let mut lowercase_scheme = [0; MAX_REGISTERED_SCHEME_LENGTH];
-            let mut normalized = true;
-
-            for &byte in value.iter() {
-                match SCHEME_CHAR_MAP[byte as usize] {
-                    0 if byte == b':' => break,
-                    0 => return Err(SchemeError::InvalidCharacter),
-                    _ => {
-                        if byte >= b'A' && byte <= b'Z' {
-                            normalized = false;
-                        }
-
-                        if end_index + 1 < MAX_REGISTERED_SCHEME_LENGTH {
-                            lowercase_scheme[end_index] = byte.to_ascii_lowercase();
-                        }
-
-                        end_index += 1;
-                    }
-                }
-            }
-
+            let (end_index, normalized) = scan_scheme(value)?;
             let (value, rest) = value.split_at(end_index);
 
             // It is important to make sure that [`MAX_REGISTERED_SCHEME_LENGTH`] is correctly
@@ -218,6 +303,12 @@ macro_rules! schemes {
                 return Ok((unregistered_scheme(value, normalized), rest));
             }
 
+            let mut lowercase_scheme = [0; MAX_REGISTERED_SCHEME_LENGTH];
+
+            for (index, byte) in value.iter().enumerate() {
+                lowercase_scheme[index] = byte.to_ascii_lowercase();
+            }
+
             let scheme = SCHEME_NAME_MAP
                 .get(&lowercase_scheme[..end_index])
                 .cloned()
@@ -228,7 +319,133 @@ macro_rules! schemes {
     }
 }
 
+/// Returns whether the given byte strings are equal ignoring ASCII case, in a `const` context.
+const fn bytes_eq_ignore_ascii_case(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut index = 0;
+
+    while index < left.len() {
+        if !left[index].eq_ignore_ascii_case(&right[index]) {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+/// Validates the scheme at the start of the given byte string, which ends at the first `':'`, and
+/// returns the index it ends at and whether it is normalized.
+const fn scan_scheme(value: &[u8]) -> Result<(usize, bool), SchemeError> {
+    if value.is_empty() {
+        return Err(SchemeError::Empty);
+    }
+
+    if !value[0].is_ascii_alphabetic() {
+        return Err(SchemeError::StartsWithNonAlphabetic);
+    }
+
+    let mut end_index = 0;
+    let mut normalized = true;
+
+    while end_index < value.len() {
+        let byte = value[end_index];
+
+        match SCHEME_CHAR_MAP[byte as usize] {
+            0 if byte == b':' => break,
+            0 => return Err(SchemeError::InvalidCharacter),
+            _ => {
+                if byte.is_ascii_uppercase() {
+                    normalized = false;
+                }
+
+                end_index += 1;
+            }
+        }
+    }
+
+    Ok((end_index, normalized))
+}
+
 impl Scheme<'_> {
+    /// Returns the port that is used when an authority of this scheme does not specify one, or
+    /// [`None`] if this is not known for the scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Scheme;
+    ///
+    /// assert_eq!(Scheme::HTTP.default_port(), Some(80));
+    /// assert_eq!(Scheme::CoAPS.default_port(), Some(5684));
+    /// assert_eq!(Scheme::try_from("ftps").unwrap().default_port(), Some(990));
+    /// assert_eq!(Scheme::URN.default_port(), None);
+    /// ```
+    pub fn default_port(&self) -> Option<u16> {
+        use self::Scheme::*;
+
+        match self {
+            AAA => Some(3868),
+            AAAS => Some(5658),
+            CoAP | CoAPTCP => Some(5683),
+            CoAPS | CoAPSTCP => Some(5684),
+            CoAPWS | HTTP | WS => Some(80),
+            CoAPSWS | HTTPS | WSS => Some(443),
+            DNS => Some(53),
+            FTP => Some(21),
+            Git => Some(9418),
+            Gopher => Some(70),
+            IMAP => Some(143),
+            IPP | IPPS => Some(631),
+            IRC => Some(6667),
+            IRCS => Some(6697),
+            LDAP => Some(389),
+            LDAPS => Some(636),
+            NFS => Some(2049),
+            NNTP => Some(119),
+            POP => Some(110),
+            Redis | RedisS => Some(6379),
+            RSync => Some(873),
+            RTSP | RTSPU => Some(554),
+            RTSPS => Some(322),
+            SFTP | SSH => Some(22),
+            SMB => Some(445),
+            SVN => Some(3690),
+            Telnet => Some(23),
+            TFTP => Some(69),
+            VNC => Some(5900),
+            Unregistered(scheme) if scheme == "ftps" => Some(990),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the scheme conventionally uses hierarchical paths (e.g. `"/a/b/c"`), or
+    /// [`None`] if this is not known for the scheme.
+    ///
+    /// For the schemes known to this function, hierarchical paths are used exactly when an
+    /// authority is used (see [`Scheme::uses_authority`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Scheme;
+    ///
+    /// assert_eq!(Scheme::HTTP.is_hierarchical(), Some(true));
+    /// assert_eq!(Scheme::URN.is_hierarchical(), Some(false));
+    /// assert_eq!(Scheme::try_from("test-scheme").unwrap().is_hierarchical(), None);
+    /// ```
+    pub fn is_hierarchical(&self) -> Option<bool> {
+        self.uses_authority()
+    }
+
     /// Returns whether the scheme is normalized.
     ///
     /// A normalized scheme will be all lowercase. All standardized schemes are always considered
@@ -261,6 +478,97 @@ impl Scheme<'_> {
         }
     }
 
+    /// Returns whether the scheme is registered under
+    /// [iana.org](https://www.iana.org/assignments/uri-schemes/uri-schemes.xhtml).
+    ///
+    /// This is true for permanent, provisional, and historical schemes. Use [`Scheme::status`] to
+    /// distinguish between them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Scheme;
+    ///
+    /// assert!(Scheme::HTTP.is_registered());
+    /// assert!(Scheme::Fax.is_registered());
+    /// assert!(!Scheme::try_from("test-scheme").unwrap().is_registered());
+    /// ```
+    pub fn is_registered(&self) -> bool {
+        !matches!(self, Scheme::Unregistered(_))
+    }
+
+    /// Returns whether the scheme implies a secure transport, such as TLS or SSH.
+    ///
+    /// Unregistered schemes are not considered secure, with the exception of `"ftps"` which is
+    /// widely used despite not being in the IANA registry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Scheme;
+    ///
+    /// assert!(Scheme::HTTPS.is_secure());
+    /// assert!(Scheme::WSS.is_secure());
+    /// assert!(!Scheme::HTTP.is_secure());
+    /// ```
+    pub fn is_secure(&self) -> bool {
+        use self::Scheme::*;
+
+        matches!(
+            self,
+            AAAS | CoAPS
+                | CoAPSTCP
+                | CoAPSWS
+                | HTTPS
+                | IPPS
+                | IRCS
+                | LDAPS
+                | MSRPS
+                | RedisS
+                | RTSPS
+                | SFTP
+                | SHTTP
+                | SIPS
+                | SNews
+                | SOAPBEEPS
+                | SSH
+                | STUNS
+                | TURNS
+                | WSS
+                | XMLRPCBEEPS
+        ) || SECURE_SCHEME_PAIRS.iter().any(|(_, secure)| secure == self)
+    }
+
+    /// Looks up the registered scheme with the given name.
+    ///
+    /// The name is compared case-insensitively. If the name is not a registered scheme, [`None`]
+    /// is returned without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Scheme;
+    ///
+    /// assert_eq!(Scheme::lookup("https"), Some(Scheme::HTTPS));
+    /// assert_eq!(Scheme::lookup("MailTo"), Some(Scheme::MailTo));
+    /// assert_eq!(Scheme::lookup("test-scheme"), None);
+    /// ```
+    pub fn lookup(name: &str) -> Option<Scheme<'static>> {
+        if name.len() > MAX_REGISTERED_SCHEME_LENGTH {
+            return None;
+        }
+
+        let mut lowercase_name = [0; MAX_REGISTERED_SCHEME_LENGTH];
+
+        for (index, byte) in name.bytes().enumerate() {
+            lowercase_name[index] = byte.to_ascii_lowercase();
+        }
+
+        SCHEME_NAME_MAP.get(&lowercase_name[..name.len()]).cloned()
+    }
+
     /// Normalizes the scheme so that it is all lowercase.
     ///
     /// # Examples
@@ -283,6 +591,91 @@ impl Scheme<'_> {
             scheme.normalize();
         }
     }
+
+    /// Returns an iterator over all registered schemes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Scheme;
+    ///
+    /// assert!(Scheme::registered().any(|scheme| scheme == Scheme::HTTP));
+    /// assert!(Scheme::registered().all(|scheme| scheme.is_registered()));
+    /// ```
+    pub fn registered() -> impl Iterator<Item = Scheme<'static>> {
+        REGISTERED_SCHEMES.iter().cloned()
+    }
+
+    /// Returns the insecure counterpart of the scheme, if the scheme is secure and has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Scheme;
+    ///
+    /// assert_eq!(Scheme::HTTPS.to_insecure(), Some(Scheme::HTTP));
+    /// assert_eq!(Scheme::WSS.to_insecure(), Some(Scheme::WS));
+    /// assert_eq!(Scheme::HTTP.to_insecure(), None);
+    /// ```
+    pub fn to_insecure(&self) -> Option<Scheme<'static>> {
+        SECURE_SCHEME_PAIRS
+            .iter()
+            .find(|(_, secure)| secure == self)
+            .map(|(insecure, _)| insecure.clone())
+    }
+
+    /// Returns the secure counterpart of the scheme, if the scheme is insecure and has one.
+    ///
+    /// Note that the secure counterpart of `"ftp"` is the unregistered scheme `"ftps"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Scheme;
+    ///
+    /// assert_eq!(Scheme::HTTP.to_secure(), Some(Scheme::HTTPS));
+    /// assert_eq!(Scheme::CoAP.to_secure(), Some(Scheme::CoAPS));
+    /// assert_eq!(Scheme::FTP.to_secure().unwrap(), "ftps");
+    /// assert_eq!(Scheme::HTTPS.to_secure(), None);
+    /// ```
+    pub fn to_secure(&self) -> Option<Scheme<'static>> {
+        SECURE_SCHEME_PAIRS
+            .iter()
+            .find(|(insecure, _)| insecure == self)
+            .map(|(_, secure)| secure.clone())
+    }
+
+    /// Returns whether the scheme conventionally uses an authority component (e.g.
+    /// `"http://example.com"`), or [`None`] if this is not known for the scheme.
+    ///
+    /// This can be used to warn about URIs such as `"mailto://user@example.com"`, which are
+    /// syntactically valid but unlikely to be what was intended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Scheme;
+    ///
+    /// assert_eq!(Scheme::HTTP.uses_authority(), Some(true));
+    /// assert_eq!(Scheme::MailTo.uses_authority(), Some(false));
+    /// assert_eq!(Scheme::try_from("test-scheme").unwrap().uses_authority(), None);
+    /// ```
+    pub fn uses_authority(&self) -> Option<bool> {
+        use self::Scheme::*;
+
+        match self {
+            AAA | AAAS | CoAP | CoAPS | CoAPSTCP | CoAPSWS | CoAPTCP | CoAPWS | DNS | File
+            | FTP | Git | Gopher | HTTP | HTTPS | IMAP | IPP | IPPS | IRC | IRCS | LDAP | LDAPS
+            | MSRP | MSRPS | NFS | NI | NNTP | POP | Redis | RedisS | RSync | RTSP | RTSPS
+            | RTSPU | SFTP | SMB | SSH | SVN | Telnet | TFTP | VNC | WS | WSS => Some(true),
+            About | Bitcoin | Blob | CID | Data | Fax | Geo | Jabber | Magnet | MailTo | MID
+            | News | SIP | SIPS | SMS | Tag | Tel | URN | XMPP => Some(false),
+            Unregistered(scheme) if scheme == "ftps" => Some(true),
+            _ => None,
+        }
+    }
 }
 
 impl AsRef<[u8]> for Scheme<'_> {
@@ -309,6 +702,28 @@ impl<'scheme> From<Scheme<'scheme>> for String {
     }
 }
 
+impl FromStr for Scheme<'static> {
+    type Err = SchemeError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Scheme::try_from(value).map(Scheme::into_owned)
+    }
+}
+
+/// Orders schemes by their names, ignoring case.
+impl Ord for Scheme<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn lowercase<'a>(scheme: &'a Scheme<'_>) -> impl Iterator<Item = u8> + 'a {
+            scheme
+                .as_str()
+                .bytes()
+                .map(|byte| byte.to_ascii_lowercase())
+        }
+
+        lowercase(self).cmp(lowercase(other))
+    }
+}
+
 impl PartialEq<[u8]> for Scheme<'_> {
     fn eq(&self, other: &[u8]) -> bool {
         self.as_str().as_bytes().eq_ignore_ascii_case(other)
@@ -327,7 +742,7 @@ impl<'a> PartialEq<&'a [u8]> for Scheme<'_> {
     }
 }
 
-impl<'a, 'scheme> PartialEq<Scheme<'scheme>> for &'a [u8] {
+impl<'scheme> PartialEq<Scheme<'scheme>> for &[u8] {
     fn eq(&self, other: &Scheme<'scheme>) -> bool {
         other == *self
     }
@@ -351,12 +766,18 @@ impl<'a> PartialEq<&'a str> for Scheme<'_> {
     }
 }
 
-impl<'a, 'scheme> PartialEq<Scheme<'scheme>> for &'a str {
+impl<'scheme> PartialEq<Scheme<'scheme>> for &str {
     fn eq(&self, other: &Scheme<'scheme>) -> bool {
         other == self.as_bytes()
     }
 }
 
+impl PartialOrd for Scheme<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<'scheme> TryFrom<&'scheme [u8]> for Scheme<'scheme> {
     type Error = SchemeError;
 
@@ -386,7 +807,7 @@ impl<'scheme> TryFrom<&'scheme str> for Scheme<'scheme> {
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnregisteredScheme<'scheme> {
-    /// Whether the fragment is normalized.
+    /// Whether the scheme is normalized.
     normalized: bool,
 
     /// The internal scheme source that is either owned or borrowed.
@@ -396,7 +817,7 @@ pub struct UnregisteredScheme<'scheme> {
 impl UnregisteredScheme<'_> {
     /// Returns a new unregistered scheme which is identical but has a lifetime tied to this
     /// unregistered scheme.
-    pub fn as_borrowed(&self) -> UnregisteredScheme {
+    pub fn as_borrowed(&self) -> UnregisteredScheme<'_> {
         use self::Cow::*;
 
         let scheme = match &self.scheme {
@@ -488,7 +909,7 @@ impl UnregisteredScheme<'_> {
     pub fn normalize(&mut self) {
         if !self.normalized {
             // Unsafe: Schemes must be valid ASCII-US, so this is safe.
-            unsafe { normalize_string(&mut self.scheme.to_mut(), true) };
+            unsafe { normalize_string(self.scheme.to_mut(), true) };
             self.normalized = true;
         }
     }
@@ -537,7 +958,7 @@ impl PartialEq for UnregisteredScheme<'_> {
 
 impl PartialEq<[u8]> for UnregisteredScheme<'_> {
     fn eq(&self, other: &[u8]) -> bool {
-        self.scheme.as_bytes().eq_ignore_ascii_case(&other)
+        self.scheme.as_bytes().eq_ignore_ascii_case(other)
     }
 }
 
@@ -553,7 +974,7 @@ impl<'a> PartialEq<&'a [u8]> for UnregisteredScheme<'_> {
     }
 }
 
-impl<'a, 'scheme> PartialEq<UnregisteredScheme<'scheme>> for &'a [u8] {
+impl<'scheme> PartialEq<UnregisteredScheme<'scheme>> for &[u8] {
     fn eq(&self, other: &UnregisteredScheme<'scheme>) -> bool {
         other == *self
     }
@@ -577,7 +998,7 @@ impl<'a> PartialEq<&'a str> for UnregisteredScheme<'_> {
     }
 }
 
-impl<'a, 'scheme> PartialEq<UnregisteredScheme<'scheme>> for &'a str {
+impl<'scheme> PartialEq<UnregisteredScheme<'scheme>> for &str {
     fn eq(&self, other: &UnregisteredScheme<'scheme>) -> bool {
         other == self.as_bytes()
     }
@@ -682,10 +1103,7 @@ impl SchemeStatus {
     /// assert_eq!(Scheme::HTTP.status().is_historical(), false);
     /// ```
     pub fn is_historical(self) -> bool {
-        match self {
-            SchemeStatus::Historical => true,
-            _ => false,
-        }
+        matches!(self, SchemeStatus::Historical)
     }
 
     /// Returns whether the scheme status is historical.
@@ -699,10 +1117,7 @@ impl SchemeStatus {
     /// assert_eq!(Scheme::IRC.status().is_permanent(), false);
     /// ```
     pub fn is_permanent(self) -> bool {
-        match self {
-            SchemeStatus::Permanent => true,
-            _ => false,
-        }
+        matches!(self, SchemeStatus::Permanent)
     }
 
     /// Returns whether the scheme status is historical.
@@ -716,10 +1131,24 @@ impl SchemeStatus {
     /// assert_eq!(Scheme::RTSP.status().is_provisional(), false);
     /// ```
     pub fn is_provisional(self) -> bool {
-        match self {
-            SchemeStatus::Provisional => true,
-            _ => false,
-        }
+        matches!(self, SchemeStatus::Provisional)
+    }
+
+    /// Returns whether the scheme status is anything other than unregistered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Scheme;
+    ///
+    /// assert_eq!(Scheme::Fax.status().is_registered(), true);
+    /// let scheme = Scheme::try_from("test-scheme").unwrap();
+    /// assert_eq!(scheme.status().is_registered(), false);
+    /// ```
+    pub fn is_registered(self) -> bool {
+        !self.is_unregistered()
     }
 
     /// Returns whether the scheme status is historical.
@@ -736,10 +1165,7 @@ impl SchemeStatus {
     /// assert_eq!(Scheme::HTTPS.status().is_unregistered(), false);
     /// ```
     pub fn is_unregistered(self) -> bool {
-        match self {
-            SchemeStatus::Unregistered => true,
-            _ => false,
-        }
+        matches!(self, SchemeStatus::Unregistered)
     }
 }
 
@@ -1054,6 +1480,45 @@ schemes! {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_scheme_from_static() {
+        fn test_case(value: &'static str) {
+            let scheme = Scheme::from_static(value);
+            assert_eq!(scheme, Scheme::try_from(value).unwrap(), "{}", value);
+            assert_eq!(scheme.as_str(), Scheme::try_from(value).unwrap().as_str());
+        }
+
+        for scheme in REGISTERED_SCHEMES.iter() {
+            test_case(scheme.as_str());
+        }
+
+        test_case("HTTP");
+        test_case("x-custom");
+        test_case("X-Custom");
+        test_case("a+b-c.d");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid scheme character")]
+    fn test_scheme_from_static_invalid() {
+        Scheme::from_static("http:");
+    }
+
+    #[test]
+    fn test_scheme_lookup() {
+        assert_eq!(Scheme::lookup("http"), Some(Scheme::HTTP));
+        assert_eq!(Scheme::lookup("HTTP"), Some(Scheme::HTTP));
+        assert_eq!(Scheme::lookup("z39.50r"), Some(Scheme::Z3950R));
+        assert_eq!(Scheme::lookup(""), None);
+        assert_eq!(Scheme::lookup("http:"), None);
+        assert_eq!(Scheme::lookup(&"a".repeat(100)), None);
+
+        for scheme in Scheme::registered() {
+            assert_eq!(Scheme::lookup(scheme.as_str()), Some(scheme.clone()));
+            assert_eq!(Scheme::try_from(scheme.as_str()).unwrap(), scheme);
+        }
+    }
+
     #[test]
     fn test_scheme_normalize() {
         fn test_case(value: &str, expected: &str) {
@@ -1078,4 +1543,34 @@ mod test {
         assert_eq!(Scheme::try_from("a:"), Err(InvalidCharacter));
         assert_eq!(Scheme::try_from("1"), Err(StartsWithNonAlphabetic));
     }
+
+    #[test]
+    fn test_scheme_secure_counterparts() {
+        for (insecure, secure) in SECURE_SCHEME_PAIRS.iter() {
+            assert!(!insecure.is_secure());
+            assert!(secure.is_secure());
+            assert_eq!(insecure.to_secure().as_ref(), Some(secure));
+            assert_eq!(secure.to_insecure().as_ref(), Some(insecure));
+            assert_eq!(insecure.to_insecure(), None);
+            assert_eq!(secure.to_secure(), None);
+        }
+
+        let ftps = Scheme::try_from("FTPS").unwrap();
+        assert_eq!(ftps.to_insecure(), Some(Scheme::FTP));
+        assert_eq!(Scheme::try_from("other").unwrap().to_secure(), None);
+    }
+
+    #[test]
+    fn test_unregistered_scheme_borrowed() {
+        match Scheme::try_from("Exotic-Scheme").unwrap() {
+            Scheme::Unregistered(scheme) => assert!(matches!(scheme.scheme, Cow::Borrowed(_))),
+            _ => panic!("expected unregistered scheme"),
+        }
+
+        let uri = crate::URI::try_from("exotic-scheme://example.com/path").unwrap();
+        match uri.scheme() {
+            Scheme::Unregistered(scheme) => assert!(matches!(scheme.scheme, Cow::Borrowed(_))),
+            _ => panic!("expected unregistered scheme"),
+        }
+    }
 }
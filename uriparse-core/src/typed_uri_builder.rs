@@ -0,0 +1,305 @@
+//! Type-State URI Builder
+//!
+//! [`URIBuilder`] accepts any combination of components and only checks them in
+//! [`URIBuilder::build`], so forgetting the scheme or setting a path that is invalid for the chosen
+//! authority is only noticed at runtime. [`TypedURIBuilder`] instead tracks in its type whether an
+//! authority was set:
+//!
+//!  - The scheme is required by the constructor and the path defaults to the empty path, so
+//!    neither can be missing.
+//!  - Once an authority is set, the builder moves to the [`HasAuthority`] state in which every
+//!    path is made absolute and setting it can no longer fail. There is no way back to the
+//!    [`NoAuthority`] state, since removing the authority could invalidate the path.
+//!  - In the [`NoAuthority`] state, only setting a path that starts with `"//"` can fail, and it
+//!    does so immediately instead of when building.
+//!
+//! As a result, [`TypedURIBuilder::build`] always succeeds.
+
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+use crate::authority::Authority;
+use crate::fragment::Fragment;
+use crate::path::Path;
+use crate::query::Query;
+use crate::scheme::Scheme;
+use crate::uri::{URIBuilder, URIError, URI};
+use crate::uri_reference::validate_absolute_path;
+
+/// The state of a [`TypedURIBuilder`] that has an authority.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum HasAuthority {}
+
+/// The state of a [`TypedURIBuilder`] that has no authority.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NoAuthority {}
+
+/// A builder for [`URI`] whose type prevents invalid combinations of components, see the
+/// [module documentation](self).
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use uriparse::{Authority, Path, Scheme, TypedURIBuilder};
+///
+/// let uri = TypedURIBuilder::new(Scheme::HTTP)
+///     .with_authority(Authority::try_from("example.com").unwrap())
+///     .with_path(Path::try_from("my/path").unwrap())
+///     .build();
+/// assert_eq!(uri.to_string(), "http://example.com/my/path");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypedURIBuilder<'uri, TState> {
+    /// The authority component of the URI, which is only set in the [`HasAuthority`] state.
+    authority: Option<Authority<'uri>>,
+
+    /// The fragment component of the URI.
+    fragment: Option<Fragment<'uri>>,
+
+    /// The path component of the URI, which is always valid for the current state.
+    path: Path<'uri>,
+
+    /// The query component of the URI.
+    query: Option<Query<'uri>>,
+
+    /// The scheme component of the URI.
+    scheme: Scheme<'uri>,
+
+    /// The state of the builder.
+    state: PhantomData<TState>,
+}
+
+impl<'uri> TypedURIBuilder<'uri, NoAuthority> {
+    /// Constructs a new builder with the given scheme, no authority and an empty path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::{Scheme, TypedURIBuilder};
+    ///
+    /// let uri = TypedURIBuilder::new(Scheme::About).build();
+    /// assert_eq!(uri.to_string(), "about:");
+    /// ```
+    pub fn new(scheme: Scheme<'uri>) -> Self {
+        TypedURIBuilder {
+            authority: None,
+            fragment: None,
+            path: Path::try_from("").unwrap(),
+            query: None,
+            scheme,
+            state: PhantomData,
+        }
+    }
+
+    /// Sets the path part of the URI.
+    ///
+    /// Without an authority, an absolute path must not start with `"//"`, as it would be parsed
+    /// as an authority, so this function will error in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Path, Scheme, TypedURIBuilder};
+    ///
+    /// let builder = TypedURIBuilder::new(Scheme::URN);
+    /// assert!(builder.clone().with_path(Path::try_from("//path").unwrap()).is_err());
+    ///
+    /// let uri = builder
+    ///     .with_path(Path::try_from("path").unwrap())
+    ///     .unwrap()
+    ///     .build();
+    /// assert_eq!(uri.to_string(), "urn:path");
+    /// ```
+    pub fn with_path(mut self, path: Path<'uri>) -> Result<Self, URIError> {
+        validate_absolute_path(None, &path).map_err(|error| URIError::try_from(error).unwrap())?;
+        self.path = path;
+        Ok(self)
+    }
+}
+
+impl<'uri> TypedURIBuilder<'uri, HasAuthority> {
+    /// Sets the path part of the URI.
+    ///
+    /// Since the URI has an authority, the path is made absolute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, Path, Scheme, TypedURIBuilder};
+    ///
+    /// let uri = TypedURIBuilder::new(Scheme::HTTP)
+    ///     .with_authority(Authority::try_from("example.com").unwrap())
+    ///     .with_path(Path::try_from("//path").unwrap())
+    ///     .build();
+    /// assert_eq!(uri.to_string(), "http://example.com//path");
+    /// ```
+    pub fn with_path(mut self, mut path: Path<'uri>) -> Self {
+        path.set_absolute(true);
+        self.path = path;
+        self
+    }
+}
+
+impl<'uri, TState> TypedURIBuilder<'uri, TState> {
+    /// Consumes the builder and builds the [`URI`], which cannot fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, Scheme, TypedURIBuilder};
+    ///
+    /// let uri = TypedURIBuilder::new(Scheme::HTTP)
+    ///     .with_authority(Authority::try_from("example.com").unwrap())
+    ///     .build();
+    /// assert_eq!(uri.to_string(), "http://example.com/");
+    /// ```
+    pub fn build(self) -> URI<'uri> {
+        URIBuilder::new()
+            .with_scheme(self.scheme)
+            .with_authority(self.authority)
+            .with_path(self.path)
+            .with_query(self.query)
+            .with_fragment(self.fragment)
+            .build()
+            .unwrap()
+    }
+
+    /// Sets the authority part of the URI and moves the builder to the [`HasAuthority`] state.
+    ///
+    /// The path that was set so far is made absolute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, Path, Scheme, TypedURIBuilder};
+    ///
+    /// let uri = TypedURIBuilder::new(Scheme::HTTP)
+    ///     .with_path(Path::try_from("my/path").unwrap())
+    ///     .unwrap()
+    ///     .with_authority(Authority::try_from("example.com").unwrap())
+    ///     .build();
+    /// assert_eq!(uri.to_string(), "http://example.com/my/path");
+    /// ```
+    pub fn with_authority(
+        mut self,
+        authority: Authority<'uri>,
+    ) -> TypedURIBuilder<'uri, HasAuthority> {
+        self.path.set_absolute(true);
+
+        TypedURIBuilder {
+            authority: Some(authority),
+            fragment: self.fragment,
+            path: self.path,
+            query: self.query,
+            scheme: self.scheme,
+            state: PhantomData,
+        }
+    }
+
+    /// Sets the fragment part of the URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Fragment, Scheme, TypedURIBuilder};
+    ///
+    /// let uri = TypedURIBuilder::new(Scheme::URN)
+    ///     .with_fragment(Some(Fragment::try_from("fragment").unwrap()))
+    ///     .build();
+    /// assert_eq!(uri.to_string(), "urn:#fragment");
+    /// ```
+    pub fn with_fragment(mut self, fragment: Option<Fragment<'uri>>) -> Self {
+        self.fragment = fragment;
+        self
+    }
+
+    /// Sets the query part of the URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Query, Scheme, TypedURIBuilder};
+    ///
+    /// let uri = TypedURIBuilder::new(Scheme::URN)
+    ///     .with_query(Some(Query::try_from("query").unwrap()))
+    ///     .build();
+    /// assert_eq!(uri.to_string(), "urn:?query");
+    /// ```
+    pub fn with_query(mut self, query: Option<Query<'uri>>) -> Self {
+        self.query = query;
+        self
+    }
+
+    /// Sets the scheme part of the URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::{Scheme, TypedURIBuilder};
+    ///
+    /// let uri = TypedURIBuilder::new(Scheme::HTTP).with_scheme(Scheme::URN).build();
+    /// assert_eq!(uri.to_string(), "urn:");
+    /// ```
+    pub fn with_scheme(mut self, scheme: Scheme<'uri>) -> Self {
+        self.scheme = scheme;
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let authority = Authority::try_from("example.com").unwrap();
+        let path = Path::try_from("a/b").unwrap();
+
+        let uri = TypedURIBuilder::new(Scheme::HTTP)
+            .with_path(path.clone())
+            .unwrap()
+            .with_authority(authority.clone())
+            .build();
+        assert_eq!(uri.to_string(), "http://example.com/a/b");
+
+        let uri = TypedURIBuilder::new(Scheme::HTTP)
+            .with_authority(authority.clone())
+            .with_path(path.clone())
+            .with_authority(Authority::try_from("example.org").unwrap())
+            .with_query(Some(Query::try_from("q").unwrap()))
+            .build();
+        assert_eq!(uri.to_string(), "http://example.org/a/b?q");
+
+        let uri = TypedURIBuilder::new(Scheme::HTTP)
+            .with_authority(authority)
+            .build();
+        assert_eq!(uri.to_string(), "http://example.com/");
+
+        assert_eq!(
+            TypedURIBuilder::new(Scheme::URN).with_path(Path::try_from("//a").unwrap()),
+            Err(URIError::AbsolutePathStartsWithTwoSlashes)
+        );
+        assert_eq!(
+            TypedURIBuilder::new(Scheme::URN)
+                .with_path(Path::try_from("/a:b").unwrap())
+                .unwrap()
+                .build()
+                .to_string(),
+            "urn:/a:b"
+        );
+    }
+}
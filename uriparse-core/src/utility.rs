@@ -0,0 +1,482 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Write};
+use std::hash::{Hash, Hasher};
+
+#[rustfmt::skip]
+pub const UNRESERVED_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9',    0,    0,    0,    0,    0,    0, // 3
+    0, b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
+/// Returns the length of the displayed value without allocating.
+pub fn display_len(value: &impl Display) -> usize {
+    struct LengthCounter(usize);
+
+    impl Write for LengthCounter {
+        fn write_str(&mut self, value: &str) -> fmt::Result {
+            self.0 += value.len();
+            Ok(())
+        }
+    }
+
+    let mut counter = LengthCounter(0);
+    write!(counter, "{}", value).expect("counting the length should never fail");
+    counter.0
+}
+
+pub const fn get_percent_encoded_value(
+    first_digit: Option<u8>,
+    second_digit: Option<u8>,
+) -> Result<(u8, bool), ()> {
+    match (first_digit, second_digit) {
+        (Some(first_digit), Some(second_digit)) => {
+            match (
+                hex_digit_to_decimal(first_digit),
+                hex_digit_to_decimal(second_digit),
+            ) {
+                (Ok(first_digit), Ok(second_digit)) => {
+                    let hex_value = first_digit.0 * 16 + second_digit.0;
+                    let uppercase = first_digit.1 && second_digit.1;
+                    Ok((hex_value, uppercase))
+                }
+                _ => Err(()),
+            }
+        }
+        _ => Err(()),
+    }
+}
+
+/// Returns the value of the percent-encoding starting with the `'%'` at the given index, see
+/// [`get_percent_encoded_value`].
+pub const fn get_percent_encoded_value_at(value: &[u8], index: usize) -> Result<(u8, bool), ()> {
+    if index + 2 < value.len() {
+        get_percent_encoded_value(Some(value[index + 1]), Some(value[index + 2]))
+    } else {
+        Err(())
+    }
+}
+
+const fn hex_digit_to_decimal(digit: u8) -> Result<(u8, bool), ()> {
+    match digit {
+        b'A'..=b'F' => Ok((digit - b'A' + 10, true)),
+        b'a'..=b'f' => Ok((digit - b'a' + 10, false)),
+        b'0'..=b'9' => Ok((digit - b'0', true)),
+        _ => Err(()),
+    }
+}
+
+/// This function is unsafe because it makes the assumption that the given string is valid ASCII-US.
+pub unsafe fn normalize_string(string: &mut String, case_sensitive: bool) {
+    let bytes = string.as_mut_vec();
+    let mut read_index = 0;
+    let mut write_index = 0;
+
+    while read_index < bytes.len() {
+        let byte = bytes[read_index];
+        read_index += 1;
+
+        if byte == b'%' {
+            let first_digit = bytes.get(read_index).cloned();
+            let second_digit = bytes.get(read_index + 1).cloned();
+            let (hex_value, _) = get_percent_encoded_value(first_digit, second_digit).unwrap();
+            read_index += 2;
+
+            if UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
+                bytes[write_index] = hex_value;
+                write_index += 1;
+            } else {
+                bytes[write_index] = b'%';
+                bytes[write_index + 1] = first_digit.unwrap().to_ascii_uppercase();
+                bytes[write_index + 2] = second_digit.unwrap().to_ascii_uppercase();
+                write_index += 3;
+            }
+        } else {
+            if !case_sensitive {
+                bytes[write_index] = byte.to_ascii_lowercase();
+            } else {
+                bytes[write_index] = byte;
+            }
+
+            write_index += 1;
+        }
+    }
+
+    bytes.truncate(write_index);
+}
+
+/// Percent-decodes the given string.
+///
+/// If the string contains no percent-encodings, it is returned as is without allocating. Any
+/// decoded bytes that do not form valid UTF-8 are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+/// Invalid percent-encodings (e.g. `"%ZZ"`) are left untouched.
+pub fn percent_decode(value: &str) -> Cow<'_, str> {
+    match percent_decode_bytes(value.as_bytes()) {
+        Cow::Borrowed(_) => Cow::Borrowed(value),
+        Cow::Owned(decoded) => match String::from_utf8(decoded) {
+            Ok(decoded) => Cow::Owned(decoded),
+            Err(error) => Cow::Owned(String::from_utf8_lossy(error.as_bytes()).into_owned()),
+        },
+    }
+}
+
+/// Percent-decodes the given bytes.
+///
+/// If the bytes contain no percent-encodings, they are returned as is without allocating. Invalid
+/// percent-encodings (e.g. `"%ZZ"`) are left untouched.
+pub fn percent_decode_bytes(value: &[u8]) -> Cow<'_, [u8]> {
+    if !value.contains(&b'%') {
+        return Cow::Borrowed(value);
+    }
+
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut index = 0;
+
+    while index < value.len() {
+        let byte = value[index];
+
+        if byte == b'%' {
+            let first_digit = value.get(index + 1).cloned();
+            let second_digit = value.get(index + 2).cloned();
+
+            if let Ok((hex_value, _)) = get_percent_encoded_value(first_digit, second_digit) {
+                decoded.push(hex_value);
+                index += 3;
+                continue;
+            }
+        }
+
+        decoded.push(byte);
+        index += 1;
+    }
+
+    Cow::Owned(decoded)
+}
+
+/// Returns whether the given percent-encoded bytes decode to exactly the given bytes.
+///
+/// This does not allocate. Invalid percent-encodings are compared as is.
+pub fn percent_decoded_equality(encoded: &[u8], decoded: &[u8]) -> bool {
+    let mut encoded_index = 0;
+    let mut decoded_index = 0;
+
+    while encoded_index < encoded.len() {
+        let mut byte = encoded[encoded_index];
+        encoded_index += 1;
+
+        if byte == b'%' {
+            let first_digit = encoded.get(encoded_index).cloned();
+            let second_digit = encoded.get(encoded_index + 1).cloned();
+
+            if let Ok((hex_value, _)) = get_percent_encoded_value(first_digit, second_digit) {
+                byte = hex_value;
+                encoded_index += 2;
+            }
+        }
+
+        if decoded.get(decoded_index) != Some(&byte) {
+            return false;
+        }
+
+        decoded_index += 1;
+    }
+
+    decoded_index == decoded.len()
+}
+
+/// Percent-encodes the given bytes into the output string.
+///
+/// Any byte whose entry in the character map is zero is percent-encoded using uppercase
+/// hexadecimal digits, all other bytes are written as is. The character map must only allow
+/// ASCII-US characters.
+pub fn percent_encode(value: &[u8], char_map: &[u8; 256], output: &mut String) {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+    for &byte in value {
+        if char_map[byte as usize] == 0 {
+            output.push('%');
+            output.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+            output.push(HEX_DIGITS[(byte & 0x0F) as usize] as char);
+        } else {
+            output.push(byte as char);
+        }
+    }
+}
+
+pub fn percent_encoded_hash<H>(value: &[u8], state: &mut H, case_sensitive: bool)
+where
+    H: Hasher,
+{
+    let mut bytes = value.iter();
+    let mut length = 0;
+
+    while let Some(byte) = bytes.next() {
+        length += 1;
+
+        match byte {
+            b'%' => {
+                let first_digit = bytes.next().cloned();
+                let second_digit = bytes.next().cloned();
+                let (hex_value, _) = get_percent_encoded_value(first_digit, second_digit).unwrap();
+
+                if UNRESERVED_CHAR_MAP[hex_value as usize] == 0 {
+                    b'%'.hash(state);
+                    first_digit.unwrap().to_ascii_uppercase().hash(state);
+                    second_digit.unwrap().to_ascii_uppercase().hash(state);
+                } else if case_sensitive {
+                    hex_value.hash(state);
+                } else {
+                    hex_value.to_ascii_lowercase().hash(state);
+                }
+            }
+            _ => {
+                if case_sensitive {
+                    byte.hash(state)
+                } else {
+                    byte.to_ascii_lowercase().hash(state)
+                }
+            }
+        }
+    }
+
+    length.hash(state);
+}
+
+pub fn percent_encoded_cmp(left: &[u8], right: &[u8], case_sensitive: bool) -> Ordering {
+    NormalizedBytes::new(left, case_sensitive).cmp(NormalizedBytes::new(right, case_sensitive))
+}
+
+pub fn percent_encoded_equality(left: &[u8], right: &[u8], case_sensitive: bool) -> bool {
+    NormalizedBytes::new(left, case_sensitive).eq(NormalizedBytes::new(right, case_sensitive))
+}
+
+/// An iterator over the bytes of a percent-encoded value as they would be after normalization.
+///
+/// Percent-encoded bytes in the unreserved character set are decoded, while all other
+/// percent-encodings are kept with uppercase hexadecimal digits. If not case-sensitive, all other
+/// bytes are lowercased. Comparing these bytes gives an equality and ordering that are consistent
+/// with [`percent_encoded_hash`].
+struct NormalizedBytes<'value> {
+    /// The remaining bytes of the value.
+    bytes: std::slice::Iter<'value, u8>,
+
+    /// Whether the value is case-sensitive.
+    case_sensitive: bool,
+
+    /// The digits of the current percent-encoding that have yet to be returned.
+    digits: [Option<u8>; 2],
+}
+
+impl<'value> NormalizedBytes<'value> {
+    fn new(value: &'value [u8], case_sensitive: bool) -> Self {
+        NormalizedBytes {
+            bytes: value.iter(),
+            case_sensitive,
+            digits: [None, None],
+        }
+    }
+}
+
+impl Iterator for NormalizedBytes<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for digit in &mut self.digits {
+            if let Some(digit) = digit.take() {
+                return Some(digit);
+            }
+        }
+
+        let byte = *self.bytes.next()?;
+
+        let byte = if byte == b'%' {
+            let first_digit = self.bytes.next().cloned();
+            let second_digit = self.bytes.next().cloned();
+
+            match get_percent_encoded_value(first_digit, second_digit) {
+                Ok((hex_value, _)) if UNRESERVED_CHAR_MAP[hex_value as usize] != 0 => hex_value,
+                _ => {
+                    self.digits = [
+                        first_digit.map(|digit| digit.to_ascii_uppercase()),
+                        second_digit.map(|digit| digit.to_ascii_uppercase()),
+                    ];
+                    return Some(b'%');
+                }
+            }
+        } else {
+            byte
+        };
+
+        if self.case_sensitive {
+            Some(byte)
+        } else {
+            Some(byte.to_ascii_lowercase())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+
+    use super::*;
+
+    #[test]
+    fn test_cmp() {
+        assert_eq!(percent_encoded_cmp(b"abc", b"%61bc", true), Ordering::Equal);
+        assert_eq!(percent_encoded_cmp(b"%2f", b"%2F", true), Ordering::Equal);
+        assert_eq!(percent_encoded_cmp(b"ab", b"abc", true), Ordering::Less);
+        assert_eq!(percent_encoded_cmp(b"%62", b"abc", true), Ordering::Greater);
+        assert_eq!(percent_encoded_cmp(b"B", b"a", true), Ordering::Less);
+        assert_eq!(percent_encoded_cmp(b"B", b"a", false), Ordering::Greater);
+        assert_eq!(percent_encoded_cmp(b"%3A", b"%2F", true), Ordering::Greater);
+        assert_eq!(percent_encoded_cmp(b"%3A", b"-", true), Ordering::Less);
+    }
+
+    #[test]
+    fn test_equality() {
+        // Case sensitive
+
+        assert!(percent_encoded_equality(b"abc", b"abc", true));
+        assert!(percent_encoded_equality(b"abc", b"%61bc", true));
+        assert!(percent_encoded_equality(b"MNO", b"%4DNO", true));
+        assert!(percent_encoded_equality(b"MNO", b"%4dNO", true));
+
+        assert!(percent_encoded_equality(b"%6a", b"%6A", true));
+        assert!(percent_encoded_equality(b"%2f", b"%2F", true));
+
+        assert!(!percent_encoded_equality(b"abc", b"xyz", true));
+        assert!(!percent_encoded_equality(b"abc", b"Abc", true));
+        assert!(!percent_encoded_equality(b"abc", b"%41bc", true));
+        assert!(!percent_encoded_equality(b"/", b"%2F", true));
+
+        // Case insensitive
+
+        assert!(percent_encoded_equality(b"abc", b"abc", false));
+        assert!(percent_encoded_equality(b"abc", b"ABC", false));
+        assert!(percent_encoded_equality(b"MNO", b"%4DNO", false));
+        assert!(percent_encoded_equality(b"MNO", b"%4dNO", false));
+        assert!(percent_encoded_equality(b"abc", b"%61bc", false));
+        assert!(percent_encoded_equality(b"abc", b"%41bc", false));
+
+        assert!(!percent_encoded_equality(b"abc", b"xyz", false));
+        assert!(!percent_encoded_equality(b"/", b"%2F", false));
+    }
+
+    #[test]
+    fn test_hash() {
+        fn hash<State>(value: &[u8], state: &State, case_sensitive: bool) -> u64
+        where
+            State: BuildHasher,
+        {
+            let mut hasher = state.build_hasher();
+            percent_encoded_hash(value, &mut hasher, case_sensitive);
+            hasher.finish()
+        }
+
+        fn compare_hashes<State>(
+            left: &[u8],
+            right: &[u8],
+            state: &State,
+            case_sensitive: bool,
+        ) -> bool
+        where
+            State: BuildHasher,
+        {
+            let left_hash = hash(left, state, case_sensitive);
+            let right_hash = hash(right, state, case_sensitive);
+            left_hash == right_hash
+        }
+
+        let state = RandomState::new();
+
+        // Case sensitive
+
+        assert!(compare_hashes(b"abc", b"abc", &state, true));
+        assert!(compare_hashes(b"abc", b"%61bc", &state, true));
+        assert!(compare_hashes(b"MNO", b"%4DNO", &state, true));
+        assert!(compare_hashes(b"MNO", b"%4dNO", &state, true));
+
+        assert!(!compare_hashes(b"abc", b"xyz", &state, true));
+        assert!(!compare_hashes(b"abc", b"Abc", &state, true));
+        assert!(!compare_hashes(b"abc", b"%41bc", &state, true));
+        assert!(!compare_hashes(b"/", b"%2F", &state, true));
+
+        // Case insensitive
+
+        assert!(compare_hashes(b"abc", b"abc", &state, false));
+        assert!(compare_hashes(b"abc", b"ABC", &state, false));
+        assert!(compare_hashes(b"MNO", b"%4DNO", &state, false));
+        assert!(compare_hashes(b"MNO", b"%4dNO", &state, false));
+        assert!(compare_hashes(b"abc", b"%61bc", &state, false));
+        assert!(compare_hashes(b"abc", b"%41bc", &state, false));
+
+        assert!(!compare_hashes(b"abc", b"xyz", &state, false));
+        assert!(!compare_hashes(b"/", b"%2F", &state, false));
+    }
+
+    #[test]
+    fn test_percent_decoded_equality() {
+        assert!(percent_decoded_equality(b"", b""));
+        assert!(percent_decoded_equality(b"abc", b"abc"));
+        assert!(percent_decoded_equality(b"a%20b", b"a b"));
+        assert!(percent_decoded_equality(b"%ff", b"\xff"));
+        assert!(percent_decoded_equality(b"%zz", b"%zz"));
+        assert!(!percent_decoded_equality(b"a%20b", b"a%20b"));
+        assert!(!percent_decoded_equality(b"abc", b"ab"));
+        assert!(!percent_decoded_equality(b"ab", b"abc"));
+        assert!(!percent_decoded_equality(b"%ff", "\u{fffd}".as_bytes()));
+    }
+
+    #[test]
+    fn test_percent_encode() {
+        fn encode(value: &str) -> String {
+            let mut output = String::new();
+            percent_encode(value.as_bytes(), &UNRESERVED_CHAR_MAP, &mut output);
+            output
+        }
+
+        assert_eq!(encode(""), "");
+        assert_eq!(encode("abc-._~"), "abc-._~");
+        assert_eq!(encode("a b/c"), "a%20b%2Fc");
+        assert_eq!(encode("%"), "%25");
+        assert_eq!(encode("\u{fc}"), "%C3%BC");
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert!(matches!(percent_decode("abc"), Cow::Borrowed("abc")));
+        assert_eq!(percent_decode("%61bc"), "abc");
+        assert_eq!(percent_decode("a%20b%2Fc"), "a b/c");
+        assert_eq!(percent_decode("%C3%BC"), "\u{fc}");
+        assert_eq!(percent_decode("%FF"), "\u{fffd}");
+        assert_eq!(percent_decode("%ZZ%4"), "%ZZ%4");
+    }
+
+    #[test]
+    fn test_percent_decode_bytes() {
+        assert!(matches!(
+            percent_decode_bytes(b"abc"),
+            Cow::Borrowed(b"abc")
+        ));
+        assert_eq!(&*percent_decode_bytes(b"a%20b%2Fc"), b"a b/c");
+        assert_eq!(&*percent_decode_bytes(b"%FF%fe"), b"\xff\xfe");
+        assert_eq!(&*percent_decode_bytes(b"%ZZ%4"), b"%ZZ%4");
+    }
+}
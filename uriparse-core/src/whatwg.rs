@@ -0,0 +1,907 @@
+//! WHATWG URL Standard Compatibility
+//!
+//! See the [URL Standard](https://url.spec.whatwg.org/).
+//!
+//! Browsers do not parse URLs according to [[RFC3986](https://tools.ietf.org/html/rfc3986)] but
+//! according to the URL Standard, which accepts many inputs the RFC rejects, such as backslashes,
+//! spaces, or missing slashes after the scheme, and which normalizes hosts and paths while parsing.
+//! This module follows the basic URL parser of the standard for inputs without a base URL and
+//! converts the result into a [`URI`], reporting every [`Divergence`] between how the standard and
+//! the RFC interpret the input.
+//!
+//! Characters that the standard leaves as is but the RFC does not allow, such as `'|'` in a path
+//! or a `'%'` that is not followed by two hexadecimal digits, are percent-encoded so that the
+//! result is always a valid URI. Internationalized hosts are converted using the conversion of
+//! [`Host::to_ascii`], which does not apply the UTS #46 mapping that browsers use.
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::{self, FromStr};
+
+use crate::authority::Host;
+use crate::uri::{URIError, URI};
+use crate::utility::{get_percent_encoded_value, percent_decode_bytes};
+
+/// A difference between how the URL Standard and RFC3986 interpret an input.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Divergence {
+    /// Backslashes were treated as slashes, which the URL Standard does for special schemes.
+    ConvertedBackslashes,
+
+    /// Characters were percent-encoded, either because the URL Standard encodes them or because
+    /// they are not allowed by the RFC.
+    EncodedCharacters,
+
+    /// A Windows drive letter at the start of the path of a `"file"` URL was written with `'|'`
+    /// instead of `':'`, e.g. `"file:///C|/x"`, or took the place of the host, e.g.
+    /// `"file://C:/x"`.
+    NormalizedDriveLetter,
+
+    /// The host was percent-decoded, converted to its ASCII compatible encoding, or was an IPv4
+    /// address not written in dotted decimal notation, e.g. `"0x7f.1"`.
+    NormalizedHost,
+
+    /// The slashes after the scheme were missing or repeated.
+    NormalizedSlashes,
+
+    /// The port was removed, since it is the default port of the scheme.
+    RemovedDefaultPort,
+
+    /// Dot segments were removed from the path, including percent-encoded ones such as `"%2e"`.
+    RemovedDotSegments,
+
+    /// An empty port was removed, e.g. the `':'` of `"http://example.com:/"`.
+    RemovedEmptyPort,
+
+    /// Tabs or newlines were removed.
+    RemovedTabsOrNewlines,
+
+    /// The host `"localhost"` of a `"file"` URL was replaced with an empty host.
+    ReplacedLocalhost,
+
+    /// Leading or trailing control characters or spaces were trimmed.
+    TrimmedControlsOrSpaces,
+}
+
+/// An error representing an input that the URL Standard fails to parse.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum WhatwgError {
+    /// The host contained a forbidden code point or could not be converted to its ASCII compatible
+    /// encoding.
+    InvalidHost,
+
+    /// The host ended in a number but was not a valid IPv4 address.
+    InvalidIPv4Address,
+
+    /// The host was enclosed in brackets but was not a valid IPv6 address.
+    InvalidIPv6Address,
+
+    /// The port contained a non-digit or was larger than 65535.
+    InvalidPort,
+
+    /// The host was empty although the scheme requires one.
+    MissingHost,
+
+    /// The input did not start with a scheme.
+    MissingScheme,
+
+    /// The result could not be converted into a valid URI.
+    URI(URIError),
+}
+
+impl Display for WhatwgError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::WhatwgError::*;
+
+        match self {
+            InvalidHost => write!(formatter, "invalid host"),
+            InvalidIPv4Address => write!(formatter, "invalid IPv4 address"),
+            InvalidIPv6Address => write!(formatter, "invalid IPv6 address"),
+            InvalidPort => write!(formatter, "invalid port"),
+            MissingHost => write!(formatter, "missing host"),
+            MissingScheme => write!(formatter, "missing scheme"),
+            URI(error) => error.fmt(formatter),
+        }
+    }
+}
+
+impl Error for WhatwgError {}
+
+impl From<URIError> for WhatwgError {
+    fn from(value: URIError) -> Self {
+        WhatwgError::URI(value)
+    }
+}
+
+/// Parses the input the way browsers do, according to the basic URL parser of the URL Standard.
+///
+/// The input must be absolute, since no base URL is supported. The divergences are returned in the
+/// order they were first encountered, each at most once.
+///
+/// # Examples
+///
+/// ```
+/// use uriparse::whatwg::{self, Divergence};
+///
+/// let (uri, divergences) = whatwg::parse(" HTTP:\\\\EXAMPLE.com:80\\a/../b c?d").unwrap();
+/// assert_eq!(uri.to_string(), "http://example.com/b%20c?d");
+/// assert_eq!(
+///     divergences,
+///     [
+///         Divergence::TrimmedControlsOrSpaces,
+///         Divergence::ConvertedBackslashes,
+///         Divergence::RemovedDefaultPort,
+///         Divergence::RemovedDotSegments,
+///         Divergence::EncodedCharacters,
+///     ]
+/// );
+///
+/// let (uri, divergences) = whatwg::parse("https://example.com/").unwrap();
+/// assert_eq!(uri.to_string(), "https://example.com/");
+/// assert!(divergences.is_empty());
+/// ```
+pub fn parse(value: &str) -> Result<(URI<'static>, Vec<Divergence>), WhatwgError> {
+    let mut parser = Parser::default();
+
+    let trimmed = value.trim_matches(|character: char| character <= ' ');
+
+    if trimmed.len() != value.len() {
+        parser.diverge(Divergence::TrimmedControlsOrSpaces);
+    }
+
+    let input: String = trimmed
+        .chars()
+        .filter(|character| !matches!(character, '\t' | '\n' | '\r'))
+        .collect();
+
+    if input.len() != trimmed.len() {
+        parser.diverge(Divergence::RemovedTabsOrNewlines);
+    }
+
+    let output = parser.parse(&input)?;
+    let uri = URI::try_from(output.as_str())?.into_owned();
+    Ok((uri, parser.divergences))
+}
+
+#[derive(Default)]
+struct Parser {
+    divergences: Vec<Divergence>,
+}
+
+impl Parser {
+    fn diverge(&mut self, divergence: Divergence) {
+        if !self.divergences.contains(&divergence) {
+            self.divergences.push(divergence);
+        }
+    }
+
+    /// Percent-encodes all bytes that are not allowed, leaving valid percent-encodings as is.
+    fn encode(&mut self, value: &str, allowed: fn(u8) -> bool, output: &mut String) {
+        if encode(value, allowed, output) {
+            self.diverge(Divergence::EncodedCharacters);
+        }
+    }
+
+    /// Parses the input with tabs and newlines removed, returning it serialized as a URI.
+    fn parse(&mut self, input: &str) -> Result<String, WhatwgError> {
+        let (scheme, rest) = split_scheme(input).ok_or(WhatwgError::MissingScheme)?;
+        let scheme = scheme.to_ascii_lowercase();
+        let special = is_special(&scheme);
+
+        let (rest, fragment) = match rest.find('#') {
+            Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+            None => (rest, None),
+        };
+        let (rest, query) = match rest.find('?') {
+            Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+            None => (rest, None),
+        };
+
+        let mut output = scheme.clone();
+        output.push(':');
+
+        if scheme == "file" {
+            let path = self.parse_file_authority(rest, &mut output)?;
+            self.parse_path(path, &scheme, &mut output);
+        } else if special {
+            let separators = count_separators(rest);
+
+            if rest[..separators].contains('\\') {
+                self.diverge(Divergence::ConvertedBackslashes);
+            }
+
+            if separators != 2 {
+                self.diverge(Divergence::NormalizedSlashes);
+            }
+
+            let rest = &rest[separators..];
+            let end = rest.find(['/', '\\']);
+            let (authority, path) = rest.split_at(end.unwrap_or(rest.len()));
+            output.push_str("//");
+            self.parse_authority(authority, &scheme, &mut output)?;
+            self.parse_path(path, &scheme, &mut output);
+        } else if let Some(rest) = rest.strip_prefix("//") {
+            let end = rest.find('/');
+            let (authority, path) = rest.split_at(end.unwrap_or(rest.len()));
+            output.push_str("//");
+            self.parse_authority(authority, &scheme, &mut output)?;
+            self.parse_path(path, &scheme, &mut output);
+        } else if rest.starts_with('/') {
+            let start = output.len();
+            self.parse_path(rest, &scheme, &mut output);
+
+            // A path starting with two slashes would be mistaken for an authority, which is
+            // prevented the same way the URL Standard serializes such paths.
+            if output[start..].starts_with("//") {
+                output.insert_str(start, "/.");
+            }
+        } else {
+            self.encode(rest, is_path_character, &mut output);
+        }
+
+        if let Some(query) = query {
+            output.push('?');
+
+            if special {
+                self.encode(query, is_special_query_character, &mut output);
+            } else {
+                self.encode(query, is_query_character, &mut output);
+            }
+        }
+
+        if let Some(fragment) = fragment {
+            output.push('#');
+            self.encode(fragment, is_query_character, &mut output);
+        }
+
+        Ok(output)
+    }
+
+    fn parse_authority(
+        &mut self,
+        authority: &str,
+        scheme: &str,
+        output: &mut String,
+    ) -> Result<(), WhatwgError> {
+        let special = is_special(scheme);
+
+        let host_and_port = match authority.rfind('@') {
+            Some(index) => {
+                let (username, password) = match authority[..index].find(':') {
+                    Some(colon) => (&authority[..colon], &authority[colon + 1..index]),
+                    None => (&authority[..index], ""),
+                };
+
+                if !username.is_empty() || !password.is_empty() {
+                    self.encode(username, is_user_info_character, output);
+
+                    if !password.is_empty() {
+                        output.push(':');
+                        self.encode(password, is_user_info_character, output);
+                    }
+
+                    output.push('@');
+                }
+
+                let host_and_port = &authority[index + 1..];
+
+                if special && host_and_port.is_empty() {
+                    return Err(WhatwgError::MissingHost);
+                }
+
+                host_and_port
+            }
+            None => authority,
+        };
+
+        let host_end = if host_and_port.starts_with('[') {
+            host_and_port.find(']').map(|index| index + 1)
+        } else {
+            None
+        }
+        .unwrap_or(0);
+        let (host, port) = match host_and_port[host_end..].find(':') {
+            Some(index) => (
+                &host_and_port[..host_end + index],
+                Some(&host_and_port[host_end + index + 1..]),
+            ),
+            None => (host_and_port, None),
+        };
+
+        if special {
+            if host.is_empty() {
+                return Err(WhatwgError::MissingHost);
+            }
+
+            self.parse_host(host, output)?;
+        } else {
+            self.parse_opaque_host(host, output)?;
+        }
+
+        match port {
+            Some(port) if !port.is_empty() => {
+                let port = parse_port(port)?;
+
+                if Some(port) == default_port(scheme) {
+                    self.diverge(Divergence::RemovedDefaultPort);
+                } else {
+                    write!(output, ":{}", port).unwrap();
+                }
+            }
+            Some(_) => self.diverge(Divergence::RemovedEmptyPort),
+            None => (),
+        }
+
+        Ok(())
+    }
+
+    /// Parses the slashes and host of a `"file"` URL, returning the remaining path.
+    fn parse_file_authority<'input>(
+        &mut self,
+        rest: &'input str,
+        output: &mut String,
+    ) -> Result<&'input str, WhatwgError> {
+        let separators = count_separators(rest);
+
+        if rest[..separators.min(2)].contains('\\') {
+            self.diverge(Divergence::ConvertedBackslashes);
+        }
+
+        output.push_str("//");
+
+        if separators < 2 {
+            self.diverge(Divergence::NormalizedSlashes);
+            return Ok(rest);
+        }
+
+        let rest = &rest[2..];
+        let end = rest.find(['/', '\\']);
+        let (host, path) = rest.split_at(end.unwrap_or(rest.len()));
+
+        // A drive letter in place of the host is part of the path, e.g. `"file://C:/x"`.
+        if is_windows_drive_letter(host) {
+            self.diverge(Divergence::NormalizedDriveLetter);
+            return Ok(rest);
+        }
+
+        if !host.is_empty() {
+            let mut parsed_host = String::new();
+            self.parse_host(host, &mut parsed_host)?;
+
+            if parsed_host == "localhost" {
+                self.diverge(Divergence::ReplacedLocalhost);
+            } else {
+                output.push_str(&parsed_host);
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Parses the host of a special scheme.
+    fn parse_host(&mut self, host: &str, output: &mut String) -> Result<(), WhatwgError> {
+        if host.starts_with('[') {
+            return parse_ipv6_address(host, output);
+        }
+
+        let decoded = percent_decode_bytes(host.as_bytes());
+        let decoded = str::from_utf8(&decoded).map_err(|_| WhatwgError::InvalidHost)?;
+
+        if decoded != host {
+            self.diverge(Divergence::NormalizedHost);
+        }
+
+        // The replacement character is disallowed by the IDNA mapping of the standard.
+        if decoded
+            .chars()
+            .any(|character| character == '\u{FFFD}' || is_forbidden_domain_code_point(character))
+        {
+            return Err(WhatwgError::InvalidHost);
+        }
+
+        let domain = if decoded.is_ascii() {
+            decoded.to_ascii_lowercase()
+        } else {
+            self.diverge(Divergence::NormalizedHost);
+
+            let mut encoded = String::new();
+            encode(decoded, is_registered_name_character, &mut encoded);
+            Host::try_from(encoded.as_str())
+                .ok()
+                .and_then(|host| host.to_ascii().ok())
+                .ok_or(WhatwgError::InvalidHost)?
+                .to_string()
+        };
+
+        if ends_in_number(&domain) {
+            let address = parse_ipv4_address(&domain)?.to_string();
+
+            if address != domain {
+                self.diverge(Divergence::NormalizedHost);
+            }
+
+            output.push_str(&address);
+        } else {
+            self.encode(&domain, is_registered_name_character, output);
+        }
+
+        Ok(())
+    }
+
+    /// Parses the host of a non-special scheme, which is only validated and percent-encoded.
+    fn parse_opaque_host(&mut self, host: &str, output: &mut String) -> Result<(), WhatwgError> {
+        if host.starts_with('[') {
+            return parse_ipv6_address(host, output);
+        }
+
+        if host
+            .chars()
+            .any(|character| character != '%' && is_forbidden_domain_code_point(character))
+        {
+            return Err(WhatwgError::InvalidHost);
+        }
+
+        self.encode(host, is_registered_name_character, output);
+        Ok(())
+    }
+
+    /// Parses a hierarchical path, removing dot segments.
+    ///
+    /// Backslashes are treated as slashes if the scheme is special, in which case an empty path is
+    /// also replaced with `"/"`. A missing leading slash is only possible for `"file"` URLs, e.g.
+    /// `"file:etc"`, and is added. A Windows drive letter at the start of the path of a `"file"`
+    /// URL is written with `':'` and is never removed by a `".."` segment.
+    fn parse_path(&mut self, path: &str, scheme: &str, output: &mut String) {
+        let special = is_special(scheme);
+        let file = scheme == "file";
+
+        if path.is_empty() {
+            if special {
+                output.push('/');
+            }
+
+            return;
+        }
+
+        if special && path.contains('\\') {
+            self.diverge(Divergence::ConvertedBackslashes);
+        }
+
+        let mut segments: Vec<String> = Vec::new();
+        let is_separator = |character| character == '/' || (special && character == '\\');
+        let path = path.strip_prefix(is_separator).unwrap_or(path);
+        let mut pieces = path.split(is_separator).peekable();
+
+        while let Some(piece) = pieces.next() {
+            let last = pieces.peek().is_none();
+
+            if is_double_dot_segment(piece) {
+                self.diverge(Divergence::RemovedDotSegments);
+
+                if !(file
+                    && segments.len() == 1
+                    && is_normalized_windows_drive_letter(&segments[0]))
+                {
+                    segments.pop();
+                }
+
+                if last {
+                    segments.push(String::new());
+                }
+            } else if is_single_dot_segment(piece) {
+                self.diverge(Divergence::RemovedDotSegments);
+
+                if last {
+                    segments.push(String::new());
+                }
+            } else if file && segments.is_empty() && is_windows_drive_letter(piece) {
+                if !is_normalized_windows_drive_letter(piece) {
+                    self.diverge(Divergence::NormalizedDriveLetter);
+                }
+
+                segments.push(format!("{}:", &piece[..1]));
+            } else {
+                let mut segment = String::new();
+                self.encode(piece, is_segment_character, &mut segment);
+                segments.push(segment);
+            }
+        }
+
+        for segment in segments {
+            output.push('/');
+            output.push_str(&segment);
+        }
+    }
+}
+
+/// Counts the leading slashes and backslashes, which are equivalent for special schemes.
+fn count_separators(value: &str) -> usize {
+    value
+        .bytes()
+        .take_while(|&byte| byte == b'/' || byte == b'\\')
+        .count()
+}
+
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "ftp" => Some(21),
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        _ => None,
+    }
+}
+
+/// Percent-encodes all bytes that are not allowed, leaving valid percent-encodings as is.
+///
+/// Returns whether any byte was percent-encoded.
+fn encode(value: &str, allowed: fn(u8) -> bool, output: &mut String) -> bool {
+    let bytes = value.as_bytes();
+    let mut encoded = false;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+
+        if byte == b'%'
+            && get_percent_encoded_value(
+                bytes.get(index + 1).cloned(),
+                bytes.get(index + 2).cloned(),
+            )
+            .is_ok()
+        {
+            output.push_str(&value[index..index + 3]);
+            index += 3;
+            continue;
+        }
+
+        if allowed(byte) {
+            output.push(byte as char);
+        } else {
+            write!(output, "%{:02X}", byte).unwrap();
+            encoded = true;
+        }
+
+        index += 1;
+    }
+
+    encoded
+}
+
+/// Returns whether the domain would be parsed as an IPv4 address by the URL Standard.
+fn ends_in_number(domain: &str) -> bool {
+    let mut parts: Vec<&str> = domain.split('.').collect();
+
+    if parts.len() > 1 && parts.last() == Some(&"") {
+        parts.pop();
+    }
+
+    let last = parts.last().cloned().unwrap_or("");
+
+    if !last.is_empty() && last.bytes().all(|byte| byte.is_ascii_digit()) {
+        return true;
+    }
+
+    match last.strip_prefix("0x").or_else(|| last.strip_prefix("0X")) {
+        Some(hex) => hex.bytes().all(|byte| byte.is_ascii_hexdigit()),
+        None => false,
+    }
+}
+
+fn is_double_dot_segment(segment: &str) -> bool {
+    ["..", ".%2e", "%2e.", "%2e%2e"]
+        .iter()
+        .any(|dots| segment.eq_ignore_ascii_case(dots))
+}
+
+fn is_forbidden_domain_code_point(character: char) -> bool {
+    character <= ' '
+        || matches!(
+            character,
+            '#' | '%' | '/' | ':' | '<' | '>' | '?' | '@' | '[' | '\\' | ']' | '^' | '|' | '\x7F'
+        )
+}
+
+/// Returns whether the value is an ASCII letter followed by `':'`.
+fn is_normalized_windows_drive_letter(value: &str) -> bool {
+    is_windows_drive_letter(value) && value.ends_with(':')
+}
+
+fn is_path_character(byte: u8) -> bool {
+    is_segment_character(byte) || byte == b'/'
+}
+
+fn is_query_character(byte: u8) -> bool {
+    is_path_character(byte) || byte == b'?'
+}
+
+fn is_registered_name_character(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric()
+        || matches!(
+            byte,
+            b'-' | b'.'
+                | b'_'
+                | b'~'
+                | b'!'
+                | b'$'
+                | b'&'
+                | b'\''
+                | b'('
+                | b')'
+                | b'*'
+                | b'+'
+                | b','
+                | b';'
+                | b'='
+        )
+}
+
+fn is_segment_character(byte: u8) -> bool {
+    is_registered_name_character(byte) || byte == b':' || byte == b'@'
+}
+
+fn is_single_dot_segment(segment: &str) -> bool {
+    segment == "." || segment.eq_ignore_ascii_case("%2e")
+}
+
+fn is_special(scheme: &str) -> bool {
+    matches!(scheme, "file" | "ftp" | "http" | "https" | "ws" | "wss")
+}
+
+/// The URL Standard percent-encodes `'\''` in the queries of special schemes.
+fn is_special_query_character(byte: u8) -> bool {
+    is_query_character(byte) && byte != b'\''
+}
+
+/// Returns whether the value is an ASCII letter followed by `':'` or `'|'`.
+fn is_windows_drive_letter(value: &str) -> bool {
+    match value.as_bytes() {
+        [letter, b':'] | [letter, b'|'] => letter.is_ascii_alphabetic(),
+        _ => false,
+    }
+}
+
+/// The URL Standard percent-encodes `';'`, `'='` and `':'` in the user information, which the RFC
+/// allows as is.
+fn is_user_info_character(byte: u8) -> bool {
+    is_registered_name_character(byte) && byte != b';' && byte != b'='
+}
+
+fn parse_ipv4_address(domain: &str) -> Result<Ipv4Addr, WhatwgError> {
+    let mut parts: Vec<&str> = domain.split('.').collect();
+
+    if parts.last() == Some(&"") {
+        parts.pop();
+    }
+
+    if parts.len() > 4 {
+        return Err(WhatwgError::InvalidIPv4Address);
+    }
+
+    let numbers = parts
+        .iter()
+        .map(|part| parse_ipv4_number(part))
+        .collect::<Option<Vec<u64>>>()
+        .ok_or(WhatwgError::InvalidIPv4Address)?;
+    let (last, rest) = numbers
+        .split_last()
+        .ok_or(WhatwgError::InvalidIPv4Address)?;
+
+    if rest.iter().any(|&number| number > 255) || *last >= 256u64.pow(5 - numbers.len() as u32) {
+        return Err(WhatwgError::InvalidIPv4Address);
+    }
+
+    let address = rest
+        .iter()
+        .enumerate()
+        .fold(*last, |address, (index, &number)| {
+            address + number * 256u64.pow(3 - index as u32)
+        });
+    Ok(Ipv4Addr::from(address as u32))
+}
+
+fn parse_ipv4_number(part: &str) -> Option<u64> {
+    let (digits, radix) = match part.strip_prefix("0x").or_else(|| part.strip_prefix("0X")) {
+        Some(digits) => (digits, 16),
+        None if part.len() > 1 && part.starts_with('0') => (&part[1..], 8),
+        None => (part, 10),
+    };
+
+    if part.is_empty() {
+        None
+    } else if digits.is_empty() {
+        Some(0)
+    } else if digits.bytes().all(|byte| (byte as char).is_digit(radix)) {
+        // Numbers too large for a `u64` cannot be valid either.
+        u64::from_str_radix(digits, radix).ok()
+    } else {
+        None
+    }
+}
+
+fn parse_ipv6_address(host: &str, output: &mut String) -> Result<(), WhatwgError> {
+    let address = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .and_then(|address| Ipv6Addr::from_str(address).ok())
+        .ok_or(WhatwgError::InvalidIPv6Address)?;
+    write!(output, "[{}]", address).unwrap();
+    Ok(())
+}
+
+fn parse_port(port: &str) -> Result<u16, WhatwgError> {
+    if !port.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(WhatwgError::InvalidPort);
+    }
+
+    let port = port.trim_start_matches('0');
+
+    if port.is_empty() {
+        Ok(0)
+    } else {
+        u16::from_str(port).map_err(|_| WhatwgError::InvalidPort)
+    }
+}
+
+/// Splits the scheme from the input, which must start with an ASCII letter followed by ASCII
+/// alphanumeric characters, `'+'`, `'-'` or `'.'`, and end with `':'`.
+fn split_scheme(input: &str) -> Option<(&str, &str)> {
+    let end = input.find(':')?;
+    let scheme = &input[..end];
+    let mut bytes = scheme.bytes();
+
+    if bytes.next()?.is_ascii_alphabetic()
+        && bytes.all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.'))
+    {
+        Some((scheme, &input[end + 1..]))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        use self::Divergence::*;
+
+        fn test_case(value: &str, expected: &str, expected_divergences: &[Divergence]) {
+            let (uri, divergences) = parse(value).unwrap();
+            assert_eq!(uri.to_string(), expected, "{:?}", value);
+            assert_eq!(divergences, expected_divergences, "{:?}", value);
+        }
+
+        test_case("http://example.com/", "http://example.com/", &[]);
+        test_case("HTTP://EXAMPLE.COM", "http://example.com/", &[]);
+        test_case(
+            "\t http://exa\nmple.com/ ",
+            "http://example.com/",
+            &[TrimmedControlsOrSpaces, RemovedTabsOrNewlines],
+        );
+        test_case(
+            "http:example.com",
+            "http://example.com/",
+            &[NormalizedSlashes],
+        );
+        test_case(
+            "https:///example.com/a",
+            "https://example.com/a",
+            &[NormalizedSlashes],
+        );
+        test_case(
+            "http:\\\\example.com\\a\\b",
+            "http://example.com/a/b",
+            &[ConvertedBackslashes],
+        );
+        test_case(
+            "https://example.com:443/",
+            "https://example.com/",
+            &[RemovedDefaultPort],
+        );
+        test_case(
+            "http://example.com:0080/",
+            "http://example.com/",
+            &[RemovedDefaultPort],
+        );
+        test_case("http://example.com:8080/", "http://example.com:8080/", &[]);
+        test_case(
+            "http://example.com:/",
+            "http://example.com/",
+            &[RemovedEmptyPort],
+        );
+        test_case("http://0x7f.1/", "http://127.0.0.1/", &[NormalizedHost]);
+        test_case("http://2130706433/", "http://127.0.0.1/", &[NormalizedHost]);
+        test_case("http://127.0.0.1./", "http://127.0.0.1/", &[NormalizedHost]);
+        test_case(
+            "http://%65xample.com/",
+            "http://example.com/",
+            &[NormalizedHost],
+        );
+        test_case(
+            "http://b\u{fc}cher.de/",
+            "http://xn--bcher-kva.de/",
+            &[NormalizedHost],
+        );
+        test_case("http://[::FFFF:0:1]/", "http://[::ffff:0.0.0.1]/", &[]);
+        test_case("http://[1:0:0:2:0:0:0:3]/", "http://[1:0:0:2::3]/", &[]);
+        test_case("http://[1:0:0:2::]/", "http://[1:0:0:2::]/", &[]);
+        test_case(
+            "http://a/b/./c/../d/%2e%2E/e",
+            "http://a/b/e",
+            &[RemovedDotSegments],
+        );
+        test_case("http://a/b/..", "http://a/", &[RemovedDotSegments]);
+        test_case("http://a/..", "http://a/", &[RemovedDotSegments]);
+        test_case("http://a/b c|d", "http://a/b%20c%7Cd", &[EncodedCharacters]);
+        test_case("http://a/%zz%41", "http://a/%25zz%41", &[EncodedCharacters]);
+        test_case(
+            "http://a/?b c'd#e f",
+            "http://a/?b%20c%27d#e%20f",
+            &[EncodedCharacters],
+        );
+        test_case(
+            "http://us;er:pa:ss@a/",
+            "http://us%3Ber:pa%3Ass@a/",
+            &[EncodedCharacters],
+        );
+        test_case("http://:@a/", "http://a/", &[]);
+        test_case("file:///etc/hosts", "file:///etc/hosts", &[]);
+        test_case("file:/etc/hosts", "file:///etc/hosts", &[NormalizedSlashes]);
+        test_case("file:etc/hosts", "file:///etc/hosts", &[NormalizedSlashes]);
+        test_case("file://localhost/etc", "file:///etc", &[ReplacedLocalhost]);
+        test_case("file://HOST/share", "file://host/share", &[]);
+        test_case("file:///C|/x", "file:///C:/x", &[NormalizedDriveLetter]);
+        test_case("file:///C:/x", "file:///C:/x", &[]);
+        test_case(
+            "file:c|\\x",
+            "file:///c:/x",
+            &[
+                NormalizedSlashes,
+                ConvertedBackslashes,
+                NormalizedDriveLetter,
+            ],
+        );
+        test_case("file://C:/x", "file:///C:/x", &[NormalizedDriveLetter]);
+        test_case("file:///C:/../x", "file:///C:/x", &[RemovedDotSegments]);
+        test_case("file:///a/C|/x", "file:///a/C%7C/x", &[EncodedCharacters]);
+        test_case(
+            "foo://Host:1/a\\b",
+            "foo://Host:1/a%5Cb",
+            &[EncodedCharacters],
+        );
+        test_case("foo:/a/../b", "foo:/b", &[RemovedDotSegments]);
+        test_case("foo:/.//b", "foo:/.//b", &[RemovedDotSegments]);
+        test_case("mailto:user@example.com", "mailto:user@example.com", &[]);
+        test_case("urn:a b", "urn:a%20b", &[EncodedCharacters]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        use self::WhatwgError::*;
+
+        fn test_case(value: &str, expected: WhatwgError) {
+            assert_eq!(parse(value).unwrap_err(), expected, "{:?}", value);
+        }
+
+        test_case("example.com", MissingScheme);
+        test_case("1http://example.com", MissingScheme);
+        test_case("http://", MissingHost);
+        test_case("http://user@/", MissingHost);
+        test_case("http://exa<mple.com/", InvalidHost);
+        test_case("http://exa%20mple.com/", InvalidHost);
+        test_case("http://ex%FFample/", InvalidHost);
+        test_case("http://ex\u{FFFD}ample/", InvalidHost);
+        test_case("foo://exa<mple.com/", InvalidHost);
+        test_case("http://1.2.3.256/", InvalidIPv4Address);
+        test_case("http://1.2.3.4.5/", InvalidIPv4Address);
+        test_case("http://0x1g.1/", InvalidIPv4Address);
+        test_case("http://[::1/", InvalidIPv6Address);
+        test_case("http://example.com:65536/", InvalidPort);
+        test_case("http://example.com:8a/", InvalidPort);
+    }
+}